@@ -0,0 +1,652 @@
+//! Derive macros for [`bitendian`](https://docs.rs/bitendian)'s `Encode` and
+//! `Decode` traits.
+//!
+//! Use these through the `derive` feature of `bitendian`, rather than
+//! depending on this crate directly.
+//!
+//! See [`Decode`](derive@Decode) for the attributes they accept.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned as _, Data, DeriveInput, Fields, Generics,
+    Index, Member,
+};
+
+/// Derive `bitendian::io::Encode`, writing fields in declaration order.
+///
+/// See [`Decode`](derive@Decode) for examples, and the `#[bitendian(...)]`
+/// attributes both derives accept.
+#[proc_macro_derive(Encode, attributes(bitendian))]
+pub fn derive_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_encode(parse_macro_input!(input))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `bitendian::io::Decode`, reading fields in declaration order.
+///
+/// The generated implementations are the same as those for
+/// [`Encode`](derive@Encode), so the examples below derive both.
+///
+/// Fields may be annotated with `#[bitendian(...)]`:
+/// - `big` or `little` fixes the endianness of that field, regardless of the
+///   endianness passed at call time.
+/// - `pad_before = N` skips `N` bytes before the field when decoding, and
+///   writes `N` zeroes when encoding.
+/// - `align = N` does the same for as many bytes as are needed to start the
+///   field at a multiple of `N`, counted from the start of the struct.
+/// - `len_prefix = P` on a `Vec<T>`, `String` or (with `bitendian`'s
+///   `smallvec` feature) `SmallVec<[T; M]>` precedes it with its length as a
+///   `P`.
+/// - `skip` leaves the field off the wire, decoding it as [`Default::default`].
+/// - `calc = EXPR` encodes `EXPR` in place of the field, e.g a length or
+///   checksum computed from other fields through `self`.
+///
+/// Structs and enums may be annotated with `#[bitendian(magic = ...)]`, which
+/// precedes the fields with a byte string (e.g `b"RIFF"`) or suffixed integer
+/// (e.g `0xCAFEu16`), which is checked when decoding.
+///
+/// ```
+/// use bitendian::io::{Decode, DecodeExt as _, Encode, EncodeExt as _};
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Header {
+///     count: u16,
+///     offset: i32,
+/// }
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Pair<T>(T, T);
+///
+/// let header = Header { count: 1, offset: -1 };
+/// let mut buf = vec![];
+/// buf.encode_le(&header).unwrap();
+/// buf.encode_le(&Pair(1u8, 2u8)).unwrap();
+/// assert_eq!(buf, [1, 0, 0xff, 0xff, 0xff, 0xff, 1, 2]);
+///
+/// let mut r = buf.as_slice();
+/// assert_eq!(r.decode_le::<Header>().unwrap(), header);
+/// assert_eq!(r.decode_le::<Pair<u8>>().unwrap(), Pair(1, 2));
+/// ```
+///
+/// Fields can be given a fixed endianness with `#[bitendian(big)]` or
+/// `#[bitendian(little)]`, for mixed-endian formats.
+/// ```
+/// use bitendian::io::{Encode, EncodeExt as _};
+///
+/// #[derive(Encode)]
+/// struct Record {
+///     #[bitendian(big)]
+///     magic: u16,
+///     len: u16,
+/// }
+///
+/// let mut buf = vec![];
+/// buf.encode_le(&Record { magic: 0x0102, len: 3 }).unwrap();
+/// assert_eq!(buf, [1, 2, 3, 0]);
+/// ```
+///
+/// Fields of `bitendian::Be` and `bitendian::Le` types, e.g `U32Be`, are
+/// always in their stored byte order, too.
+/// ```
+/// use bitendian::io::{Decode, DecodeExt as _, Encode, EncodeExt as _};
+/// use bitendian::U32Be;
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Record {
+///     magic: U32Be,
+///     len: u16,
+/// }
+///
+/// let record = Record { magic: U32Be::new(0x01020304), len: 5 };
+/// let mut buf = vec![];
+/// buf.encode_le(&record).unwrap();
+/// assert_eq!(buf, [1, 2, 3, 4, 5, 0]);
+/// assert_eq!(buf.as_slice().decode_le::<Record>().unwrap(), record);
+/// ```
+///
+/// Reserved bytes can be skipped when decoding, and zeroed when encoding, with
+/// `#[bitendian(pad_before = N)]` and `#[bitendian(align = N)]`.
+/// Alignment is relative to the start of the struct.
+/// ```
+/// use bitendian::io::{Decode, DecodeExt as _, Encode, EncodeExt as _};
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Entry {
+///     kind: u8,
+///     #[bitendian(align = 4)]
+///     offset: u32,
+///     #[bitendian(pad_before = 2)]
+///     len: u16,
+/// }
+///
+/// let entry = Entry { kind: 1, offset: 2, len: 3 };
+/// let mut buf = vec![];
+/// buf.encode_be(&entry).unwrap();
+/// assert_eq!(buf, [1, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 3]);
+/// assert_eq!(buf.as_slice().decode_be::<Entry>().unwrap(), entry);
+/// ```
+///
+/// `Vec<T>` and `String` fields are supported with
+/// `#[bitendian(len_prefix = P)]`, which precedes them with their length (in
+/// items or bytes, respectively) as a `P`, e.g `u16`.
+/// With the `smallvec` feature, so are `SmallVec<[T; M]>` fields, which only
+/// allocate for more than `M` items.
+/// ```
+/// use bitendian::io::{Decode, DecodeExt as _, Encode, EncodeExt as _};
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Message {
+///     #[bitendian(len_prefix = u8)]
+///     name: String,
+///     #[bitendian(len_prefix = u16)]
+///     values: Vec<u16>,
+/// }
+///
+/// let message = Message { name: "hi".into(), values: vec![1] };
+/// let mut buf = vec![];
+/// buf.encode_be(&message).unwrap();
+/// assert_eq!(buf, [2, b'h', b'i', 0, 1, 0, 1]);
+/// assert_eq!(buf.as_slice().decode_be::<Message>().unwrap(), message);
+/// ```
+///
+/// Structs can start with a constant with `#[bitendian(magic = ...)]`, which
+/// is written when encoding, and checked when decoding.
+/// It may be a byte string, or an integer with a type suffix.
+/// ```
+/// use bitendian::io::{Decode, DecodeExt as _, Encode, EncodeExt as _};
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// #[bitendian(magic = b"RIFF")]
+/// struct Riff {
+///     len: u32,
+/// }
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// #[bitendian(magic = 0xCAFEu16)]
+/// struct Versioned(u8);
+///
+/// let mut buf = vec![];
+/// buf.encode_le(&Riff { len: 1 }).unwrap();
+/// buf.encode_le(&Versioned(2)).unwrap();
+/// assert_eq!(buf, *b"RIFF\x01\0\0\0\xfe\xca\x02");
+///
+/// let mut r = buf.as_slice();
+/// assert_eq!(r.decode_le::<Riff>().unwrap(), Riff { len: 1 });
+/// assert_eq!(r.decode_le::<Versioned>().unwrap(), Versioned(2));
+///
+/// let err = b"RIFX\x01\0\0\0".as_slice().decode_le::<Riff>().unwrap_err();
+/// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+/// ```
+///
+/// Fields can be left off the wire with `#[bitendian(skip)]`, and are then
+/// decoded as [`Default::default`].
+/// Fields can be computed when encoding with `#[bitendian(calc = EXPR)]`,
+/// where `EXPR` may refer to `self`.
+/// ```
+/// use bitendian::io::{Decode, DecodeExt as _, Encode, EncodeExt as _};
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Packet {
+///     #[bitendian(calc = self.payload.iter().fold(0, |acc, it| acc ^ it))]
+///     checksum: u8,
+///     #[bitendian(len_prefix = u8)]
+///     payload: Vec<u8>,
+///     #[bitendian(skip)]
+///     cached: Option<String>,
+/// }
+///
+/// let packet = Packet {
+///     checksum: 0, // ignored
+///     payload: vec![0b01, 0b10],
+///     cached: Some(String::from("not encoded")),
+/// };
+/// let mut buf = vec![];
+/// buf.encode_be(&packet).unwrap();
+/// assert_eq!(buf, [0b11, 2, 0b01, 0b10]);
+///
+/// let decoded = buf.as_slice().decode_be::<Packet>().unwrap();
+/// assert_eq!(decoded.checksum, 0b11);
+/// assert_eq!(decoded.cached, None);
+/// ```
+///
+/// Fieldless enums with an integer `#[repr(..)]` are encoded as their
+/// discriminant.
+/// Unknown discriminants are reported as [`InvalidData`](std::io::ErrorKind::InvalidData).
+/// ```
+/// use bitendian::io::{Decode, DecodeExt as _, Encode, EncodeExt as _};
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// #[repr(u16)]
+/// enum Kind {
+///     Request = 1,
+///     Response = 0x0100,
+/// }
+///
+/// let mut buf = vec![];
+/// buf.encode_be(&Kind::Response).unwrap();
+/// assert_eq!(buf, [1, 0]);
+/// assert_eq!(buf.as_slice().decode_be::<Kind>().unwrap(), Kind::Response);
+///
+/// let err = [0, 2].as_slice().decode_be::<Kind>().unwrap_err();
+/// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+/// assert_eq!(err.to_string(), "unknown discriminant 2 for Kind");
+/// ```
+#[proc_macro_derive(Decode, attributes(bitendian))]
+pub fn derive_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_decode(parse_macro_input!(input))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_encode(input: DeriveInput) -> syn::Result<TokenStream> {
+    if let Data::Enum(data) = &input.data {
+        return expand_encode_enum(&input, data);
+    }
+    let fields = fields(&input)?;
+    let generics = bounded(
+        input.generics.clone(),
+        parse_quote!(::bitendian::io::Encode),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = &input.ident;
+    let counted = counted(&fields, quote!(writer));
+    let magic = ContainerAttrs::parse(&input.attrs)?
+        .magic
+        .map(|magic| quote!(::bitendian::io::Encode::encode(&#magic, writer, endian)?;));
+    let writes = fields.iter().filter(|it| it.attrs.skip.is_none()).map(|field| {
+        let Field {
+            member, ty, attrs, ..
+        } = field;
+        let endian = attrs.endian();
+        let value = match &attrs.calc {
+            Some(calc) => quote!(&{ let it: #ty = #calc; it }),
+            None => quote!(&self.#member),
+        };
+        let encode = match &attrs.len_prefix {
+            Some(prefix) => quote! {
+                ::bitendian::io::__derive::encode_prefixed::<#prefix, _, _>(#value, writer, #endian)?;
+            },
+            None => quote!(::bitendian::io::Encode::encode(#value, writer, #endian)?;),
+        };
+        let pad = attrs
+            .padding(&quote!(writer))
+            .into_iter()
+            .map(|n| quote!(::bitendian::io::__derive::zeroes(writer, #n)?;));
+        quote! {
+            #(#pad)*
+            #encode
+        }
+    });
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::bitendian::io::Encode for #ident #ty_generics #where_clause {
+            fn encode<W: ::std::io::Write + ?::core::marker::Sized>(
+                &self,
+                writer: &mut W,
+                endian: ::bitendian::Endian,
+            ) -> ::std::io::Result<()> {
+                #counted
+                #magic
+                #(#writes)*
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+fn expand_decode(input: DeriveInput) -> syn::Result<TokenStream> {
+    if let Data::Enum(data) = &input.data {
+        return expand_decode_enum(&input, data);
+    }
+    let fields = fields(&input)?;
+    let generics = bounded(
+        input.generics.clone(),
+        parse_quote!(::bitendian::io::Decode),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = &input.ident;
+    let counted = counted(&fields, quote!(reader));
+    let magic = ContainerAttrs::parse(&input.attrs)?.magic.map(|magic| {
+        quote! {
+            ::bitendian::io::__derive::check_magic(
+                ::bitendian::io::Decode::decode(reader, endian)?,
+                #magic,
+            )?;
+        }
+    });
+    let reads = fields.iter().map(|Field { binding, attrs, .. }| {
+        if attrs.skip.is_some() {
+            return quote!(let #binding = ::core::default::Default::default(););
+        }
+        let endian = attrs.endian();
+        let decode = match &attrs.len_prefix {
+            Some(prefix) => quote! {
+                ::bitendian::io::__derive::decode_prefixed::<#prefix, _, _>(reader, #endian)?
+            },
+            None => quote!(::bitendian::io::Decode::decode(reader, #endian)?),
+        };
+        let pad = attrs
+            .padding(&quote!(reader))
+            .into_iter()
+            .map(|n| quote!(::bitendian::io::__derive::skip(reader, #n)?;));
+        quote! {
+            #(#pad)*
+            let #binding = #decode;
+        }
+    });
+    let bindings = fields.iter().map(|it| &it.binding);
+    let construct = match &input.data {
+        Data::Struct(it) => match &it.fields {
+            Fields::Named(_) => {
+                let members = fields.iter().map(|it| &it.member);
+                quote!(Self { #(#members: #bindings),* })
+            }
+            Fields::Unnamed(_) => quote!(Self(#(#bindings),*)),
+            Fields::Unit => quote!(Self),
+        },
+        _ => unreachable!("checked by `fields`"),
+    };
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::bitendian::io::Decode for #ident #ty_generics #where_clause {
+            fn decode<R: ::std::io::Read + ?::core::marker::Sized>(
+                reader: &mut R,
+                endian: ::bitendian::Endian,
+            ) -> ::std::io::Result<Self> {
+                #counted
+                #magic
+                #(#reads)*
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+    })
+}
+
+/// Fieldless enums are encoded as their discriminant.
+fn expand_encode_enum(input: &DeriveInput, data: &syn::DataEnum) -> syn::Result<TokenStream> {
+    let repr = repr(input)?;
+    let variants = variants(data)?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let ident = &input.ident;
+    let magic = ContainerAttrs::parse(&input.attrs)?
+        .magic
+        .map(|magic| quote!(::bitendian::io::Encode::encode(&#magic, writer, endian)?;));
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::bitendian::io::Encode for #ident #ty_generics #where_clause {
+            fn encode<W: ::std::io::Write + ?::core::marker::Sized>(
+                &self,
+                writer: &mut W,
+                endian: ::bitendian::Endian,
+            ) -> ::std::io::Result<()> {
+                #magic
+                let discriminant = match self {
+                    #(Self::#variants => Self::#variants as #repr,)*
+                };
+                ::bitendian::io::Encode::encode(&discriminant, writer, endian)
+            }
+        }
+    })
+}
+
+fn expand_decode_enum(input: &DeriveInput, data: &syn::DataEnum) -> syn::Result<TokenStream> {
+    let repr = repr(input)?;
+    let variants = variants(data)?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let magic = ContainerAttrs::parse(&input.attrs)?.magic.map(|magic| {
+        quote! {
+            ::bitendian::io::__derive::check_magic(
+                ::bitendian::io::Decode::decode(reader, endian)?,
+                #magic,
+            )?;
+        }
+    });
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::bitendian::io::Decode for #ident #ty_generics #where_clause {
+            fn decode<R: ::std::io::Read + ?::core::marker::Sized>(
+                reader: &mut R,
+                endian: ::bitendian::Endian,
+            ) -> ::std::io::Result<Self> {
+                #magic
+                let discriminant: #repr = ::bitendian::io::Decode::decode(reader, endian)?;
+                #(
+                    if discriminant == Self::#variants as #repr {
+                        return ::core::result::Result::Ok(Self::#variants);
+                    }
+                )*
+                ::core::result::Result::Err(
+                    ::bitendian::io::__derive::unknown_discriminant(#name, discriminant)
+                )
+            }
+        }
+    })
+}
+
+/// The integer type from `#[repr(..)]`.
+fn repr(input: &DeriveInput) -> syn::Result<Ident> {
+    let mut repr = None;
+    for attr in input.attrs.iter().filter(|it| it.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            const INTEGERS: &[&str] = &[
+                "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128",
+                "isize",
+            ];
+            if let Some(ident) = meta.path.get_ident() {
+                if INTEGERS.iter().any(|it| ident == it) {
+                    repr = Some(ident.clone());
+                }
+            }
+            Ok(())
+        })?;
+    }
+    repr.ok_or_else(|| {
+        syn::Error::new(
+            input.ident.span(),
+            "enums must have an integer `#[repr(..)]`, e.g `#[repr(u16)]`",
+        )
+    })
+}
+
+fn variants(data: &syn::DataEnum) -> syn::Result<Vec<&Ident>> {
+    data.variants
+        .iter()
+        .map(|variant| match &variant.fields {
+            Fields::Unit => Ok(&variant.ident),
+            fields => Err(syn::Error::new(
+                fields.span(),
+                "only fieldless enums are supported",
+            )),
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct ContainerAttrs {
+    /// An expression for the value of the magic.
+    magic: Option<TokenStream>,
+}
+
+impl ContainerAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut this = Self::default();
+        for attr in attrs.iter().filter(|it| it.path().is_ident("bitendian")) {
+            attr.parse_nested_meta(|meta| {
+                if !meta.path.is_ident("magic") {
+                    return Err(meta.error("unrecognised `bitendian` attribute"));
+                }
+                let magic = match meta.value()?.parse::<syn::Lit>()? {
+                    syn::Lit::ByteStr(it) => quote!(*#it),
+                    syn::Lit::Int(it) if !it.suffix().is_empty() => quote!(#it),
+                    syn::Lit::Int(it) => {
+                        return Err(syn::Error::new(
+                            it.span(),
+                            "integer magics need a type suffix, e.g `0xCAFEu16`",
+                        ))
+                    }
+                    other => {
+                        return Err(syn::Error::new(
+                            other.span(),
+                            "expected a byte string or integer",
+                        ))
+                    }
+                };
+                set(&mut this.magic, magic, &meta)
+            })?;
+        }
+        Ok(this)
+    }
+}
+
+/// A field, in declaration order.
+struct Field {
+    /// How to access the field on `self`.
+    member: Member,
+    /// The local variable the field is decoded into.
+    binding: Ident,
+    ty: syn::Type,
+    attrs: FieldAttrs,
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    endian: Option<TokenStream>,
+    pad_before: Option<u64>,
+    align: Option<u64>,
+    len_prefix: Option<syn::Type>,
+    /// Where `skip` was specified.
+    skip: Option<Span>,
+    calc: Option<syn::Expr>,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut this = Self::default();
+        for attr in attrs.iter().filter(|it| it.path().is_ident("bitendian")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    return set(&mut this.skip, meta.path.span(), &meta);
+                }
+                if meta.path.is_ident("calc") {
+                    let calc = meta.value()?.parse()?;
+                    return set(&mut this.calc, calc, &meta);
+                }
+                if meta.path.is_ident("pad_before") {
+                    let n = meta.value()?.parse::<syn::LitInt>()?.base10_parse()?;
+                    return set(&mut this.pad_before, n, &meta);
+                }
+                if meta.path.is_ident("len_prefix") {
+                    let prefix = meta.value()?.parse()?;
+                    return set(&mut this.len_prefix, prefix, &meta);
+                }
+                if meta.path.is_ident("align") {
+                    let lit = meta.value()?.parse::<syn::LitInt>()?;
+                    return match lit.base10_parse()? {
+                        0 => Err(syn::Error::new(lit.span(), "alignment must be non-zero")),
+                        n => set(&mut this.align, n, &meta),
+                    };
+                }
+                let endian = match () {
+                    _ if meta.path.is_ident("big") => quote!(::bitendian::Endian::Big),
+                    _ if meta.path.is_ident("little") => quote!(::bitendian::Endian::Little),
+                    _ => return Err(meta.error("unrecognised `bitendian` attribute")),
+                };
+                set(&mut this.endian, endian, &meta)
+            })?;
+        }
+        if let Some(span) = this.skip {
+            let FieldAttrs {
+                endian,
+                pad_before,
+                align,
+                len_prefix,
+                skip: _,
+                calc,
+            } = &this;
+            if endian.is_some()
+                || pad_before.is_some()
+                || align.is_some()
+                || len_prefix.is_some()
+                || calc.is_some()
+            {
+                return Err(syn::Error::new(
+                    span,
+                    "`skip` can't be combined with other attributes",
+                ));
+            }
+        }
+        Ok(this)
+    }
+    /// The lengths of padding before the field, in order, given the reader or
+    /// writer `io`.
+    fn padding(&self, io: &TokenStream) -> Vec<TokenStream> {
+        let mut it = vec![];
+        if let Some(n) = self.pad_before {
+            it.push(quote!(#n));
+        }
+        if let Some(align) = self.align {
+            it.push(quote!(::bitendian::io::__derive::padding(#io.offset, #align)));
+        }
+        it
+    }
+    /// The endianness to pass to the field's implementation.
+    fn endian(&self) -> TokenStream {
+        self.endian.clone().unwrap_or_else(|| quote!(endian))
+    }
+}
+
+fn set<T>(slot: &mut Option<T>, value: T, meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    match slot.replace(value) {
+        Some(_) => Err(meta.error("duplicate `bitendian` attribute")),
+        None => Ok(()),
+    }
+}
+
+/// If any field is aligned, shadow `io` with a wrapper which tracks the
+/// offset from the start of the struct.
+fn counted(fields: &[Field], io: TokenStream) -> Option<TokenStream> {
+    fields
+        .iter()
+        .any(|it| it.attrs.align.is_some())
+        .then(|| quote!(let #io = &mut ::bitendian::io::__derive::Counted::new(#io);))
+}
+
+fn fields(input: &DeriveInput) -> syn::Result<Vec<Field>> {
+    let fields = match &input.data {
+        Data::Struct(it) => &it.fields,
+        Data::Enum(_) => unreachable!("enums are handled separately"),
+        Data::Union(it) => {
+            return Err(syn::Error::new(
+                it.union_token.span(),
+                "only structs and enums are supported",
+            ))
+        }
+    };
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            Ok(Field {
+                member: match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(Index::from(i)),
+                },
+                binding: format_ident!("__field{}", i),
+                ty: field.ty.clone(),
+                attrs: FieldAttrs::parse(&field.attrs)?,
+            })
+        })
+        .collect()
+}
+
+/// Require every type parameter to implement `bound`.
+fn bounded(mut generics: Generics, bound: syn::TypeParamBound) -> Generics {
+    for param in generics.type_params_mut() {
+        param.bounds.push(bound.clone());
+    }
+    generics
+}
@@ -1,12 +1,19 @@
 #!/usr/bin/env bash
 set -euxo pipefail
 
-cargo test --all-features
+# every feature but `fixed` and `rkyv`, whose dependencies break type
+# inference in tests
+cargo +nightly test --features full,derive,cli,ffi,half,heapless,memmap2,miette,portable-atomic,portable-simd,serde,smallvec,rust_decimal,bitflags,bitvec,bytemuck,bytes,uuid,ux,zerocopy
+cargo test --manifest-path bitendian-derive/Cargo.toml
+cargo test --manifest-path isolated-tests/Cargo.toml
 cargo build --no-default-features
+cargo build --no-default-features --features alloc
 cargo build
 cargo build --features futures
 cargo build --features tokio
-cargo build --all-features
+cargo +nightly build --all-features
+cargo build --features cli
+cargo build --features derive
 
 RUSTDOCFLAGS="--cfg do_doc_cfg" cargo +nightly doc --all-features
 lychee target/doc/bitendian/index.html
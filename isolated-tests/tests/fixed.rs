@@ -0,0 +1,9 @@
+use bitendian::io::ReadExt as _;
+use fixed::types::{I8F8, U16F16};
+
+#[test]
+fn q_format() {
+    let mut r = [0x01, 0x80, 0x00, 0x80, 0x02, 0x00].as_slice();
+    assert_eq!(r.read_be::<I8F8>().unwrap(), I8F8::from_num(1.5));
+    assert_eq!(r.read_le::<U16F16>().unwrap(), U16F16::from_num(2.5));
+}
@@ -0,0 +1,21 @@
+use bitendian::{Be, Le};
+use rkyv::{rancor::Error, Archive, Deserialize, Serialize};
+
+#[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+struct Header {
+    magic: Be<u32, 4>,
+    count: Le<u16, 2>,
+}
+
+#[test]
+fn round_trip() {
+    let header = Header {
+        magic: Be::new(0xcafebabe),
+        count: Le::new(2),
+    };
+    let bytes = rkyv::to_bytes::<Error>(&header).unwrap();
+    assert_eq!(bytes.as_slice(), [0xca, 0xfe, 0xba, 0xbe, 2, 0]);
+    let archived = rkyv::access::<ArchivedHeader, Error>(&bytes).unwrap();
+    assert_eq!(archived.magic.get(), 0xcafebabe);
+    assert_eq!(header, rkyv::deserialize::<_, Error>(archived).unwrap());
+}
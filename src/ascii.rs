@@ -0,0 +1,343 @@
+//! Fixed-width ASCII numeric fields, as found in `tar`, `cpio` and `ar` headers.
+//!
+//! These formats store numbers as text in fixed-size fields, padded with
+//! `NUL`s or spaces, often right next to ordinary binary fields.
+//!
+//! ```
+//! use bitendian::ascii;
+//!
+//! // a `tar` size field: eleven octal digits and a `NUL` terminator
+//! let size: u64 = ascii::parse_octal(b"00000001750\0").unwrap();
+//! assert_eq!(size, 1000);
+//!
+//! let mut field = [0u8; 12];
+//! ascii::format_octal(1000u64, &mut field[..11]).unwrap();
+//! assert_eq!(&field, b"00000001750\0");
+//!
+//! // an `ar` size field: left-justified decimal, padded with spaces
+//! let size: u32 = ascii::parse_decimal(b"1000      ").unwrap();
+//! assert_eq!(size, 1000);
+//! ```
+
+use core::fmt;
+
+/// Parse an octal field, ignoring leading and trailing spaces and `NUL`s.
+///
+/// A field containing only padding is parsed as zero.
+///
+/// Fields with the high bit of the first byte set are interpreted as the
+/// GNU `tar` base-256 extension: the low seven bits of the first byte and the
+/// following bytes are a big-endian integer.
+/// Negative numbers, which have the `0x40` bit of the first byte set, are
+/// rejected.
+pub fn parse_octal<T: TryFrom<u64>>(field: &[u8]) -> Result<T, ParseError> {
+    match field.first() {
+        Some(first) if first & 0x80 != 0 => parse_base256(field),
+        _ => parse_radix(field, 8),
+    }
+}
+
+/// Parse a decimal field, ignoring leading and trailing spaces and `NUL`s.
+///
+/// A field containing only padding is parsed as zero.
+pub fn parse_decimal<T: TryFrom<u64>>(field: &[u8]) -> Result<T, ParseError> {
+    parse_radix(field, 10)
+}
+
+/// Fill `field` with the octal digits of `value`, padded on the left with `0`s.
+///
+/// No terminator is written - for `NUL`-terminated fields, pass all but the
+/// last byte.
+pub fn format_octal(value: impl Into<u64>, field: &mut [u8]) -> Result<(), FieldOverflow> {
+    let mut value = value.into();
+    for byte in field.iter_mut().rev() {
+        *byte = b'0' + (value % 8) as u8;
+        value /= 8;
+    }
+    match value {
+        0 => Ok(()),
+        _ => Err(FieldOverflow { width: field.len() }),
+    }
+}
+
+/// Fill `field` with the decimal digits of `value`, left-justified and padded
+/// on the right with spaces.
+pub fn format_decimal(value: impl Into<u64>, field: &mut [u8]) -> Result<(), FieldOverflow> {
+    let value = value.into();
+    let mut digits = [0u8; 20]; // u64::MAX has 20 decimal digits
+    let mut len = 0;
+    let mut rest = value;
+    loop {
+        digits[len] = b'0' + (rest % 10) as u8;
+        len += 1;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+    if len > field.len() {
+        return Err(FieldOverflow { width: field.len() });
+    }
+    let (head, tail) = field.split_at_mut(len);
+    for (dst, src) in head.iter_mut().zip(digits[..len].iter().rev()) {
+        *dst = *src
+    }
+    tail.fill(b' ');
+    Ok(())
+}
+
+fn is_padding(byte: &u8) -> bool {
+    matches!(byte, b' ' | b'\0')
+}
+
+fn parse_radix<T: TryFrom<u64>>(field: &[u8], radix: u8) -> Result<T, ParseError> {
+    let start = field
+        .iter()
+        .position(|it| !is_padding(it))
+        .unwrap_or(field.len());
+    let end = field
+        .iter()
+        .rposition(|it| !is_padding(it))
+        .map_or(start, |it| it + 1);
+    let mut acc = 0u64;
+    for (offset, byte) in field.iter().enumerate().take(end).skip(start) {
+        let digit = match byte {
+            b'0'..=b'9' if byte - b'0' < radix => byte - b'0',
+            _ => {
+                return Err(ParseError {
+                    offset,
+                    kind: ParseErrorKind::InvalidDigit,
+                })
+            }
+        };
+        acc = acc
+            .checked_mul(u64::from(radix))
+            .and_then(|it| it.checked_add(u64::from(digit)))
+            .ok_or(ParseError {
+                offset,
+                kind: ParseErrorKind::Overflow,
+            })?;
+    }
+    T::try_from(acc).map_err(|_| ParseError {
+        offset: start,
+        kind: ParseErrorKind::Overflow,
+    })
+}
+
+fn parse_base256<T: TryFrom<u64>>(field: &[u8]) -> Result<T, ParseError> {
+    let mut acc = 0u64;
+    for (offset, byte) in field.iter().enumerate() {
+        let byte = match offset {
+            0 => match byte & 0x40 {
+                0 => byte & 0x7f,
+                _ => {
+                    // the sign bit - negative numbers aren't supported
+                    return Err(ParseError {
+                        offset,
+                        kind: ParseErrorKind::InvalidDigit,
+                    });
+                }
+            },
+            _ => *byte,
+        };
+        acc = acc
+            .checked_mul(256)
+            .map(|it| it | u64::from(byte))
+            .ok_or(ParseError {
+                offset,
+                kind: ParseErrorKind::Overflow,
+            })?;
+    }
+    T::try_from(acc).map_err(|_| ParseError {
+        offset: 0,
+        kind: ParseErrorKind::Overflow,
+    })
+}
+
+/// Error returned by [`parse_octal`] and [`parse_decimal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    offset: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// The offset into the field at which the error was detected.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+    /// Whether this error was caused by a number too large for the output type.
+    pub fn is_overflow(&self) -> bool {
+        matches!(self.kind, ParseErrorKind::Overflow)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ParseErrorKind {
+    InvalidDigit,
+    Overflow,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::InvalidDigit => {
+                f.write_fmt(format_args!("invalid digit at offset {}", self.offset))
+            }
+            ParseErrorKind::Overflow => f.write_fmt(format_args!(
+                "number too large for output type (at offset {})",
+                self.offset
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Error returned by [`format_octal`] and [`format_decimal`] when a number
+/// doesn't fit in the field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldOverflow {
+    width: usize,
+}
+
+impl fmt::Display for FieldOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "number does not fit in a field of width {}",
+            self.width
+        ))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FieldOverflow {}
+
+/// Read an `N`-byte octal field, as in [`parse_octal`].
+///
+/// Parse failures are reported as [`std::io::ErrorKind::InvalidData`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn read_octal<const N: usize, T: TryFrom<u64>>(
+    reader: &mut (impl std::io::Read + ?Sized),
+) -> std::io::Result<T> {
+    let mut field = [0u8; N];
+    reader.read_exact(&mut field)?;
+    parse_octal(&field).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Read an `N`-byte decimal field, as in [`parse_decimal`].
+///
+/// Parse failures are reported as [`std::io::ErrorKind::InvalidData`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn read_decimal<const N: usize, T: TryFrom<u64>>(
+    reader: &mut (impl std::io::Read + ?Sized),
+) -> std::io::Result<T> {
+    let mut field = [0u8; N];
+    reader.read_exact(&mut field)?;
+    parse_decimal(&field).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Write an `N`-byte, `NUL`-terminated octal field, as in [`format_octal`].
+///
+/// Numbers that don't fit are reported as [`std::io::ErrorKind::InvalidInput`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn write_octal<const N: usize>(
+    writer: &mut (impl std::io::Write + ?Sized),
+    value: impl Into<u64>,
+) -> std::io::Result<()> {
+    let mut field = [0u8; N];
+    if let Some((_nul, digits)) = field.split_last_mut() {
+        format_octal(value, digits)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    }
+    writer.write_all(&field)
+}
+
+/// Write an `N`-byte decimal field, as in [`format_decimal`].
+///
+/// Numbers that don't fit are reported as [`std::io::ErrorKind::InvalidInput`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn write_decimal<const N: usize>(
+    writer: &mut (impl std::io::Write + ?Sized),
+    value: impl Into<u64>,
+) -> std::io::Result<()> {
+    let mut field = [0u8; N];
+    format_decimal(value, &mut field)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    writer.write_all(&field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn octal() {
+        assert_eq!(Ok(0o644u32), parse_octal(b"0000644\0"));
+        assert_eq!(Ok(0o644u32), parse_octal(b"   644 \0"));
+        assert_eq!(Ok(0u32), parse_octal(b"\0\0\0\0\0\0\0\0"));
+        assert_eq!(Ok(0u32), parse_octal(b""));
+        assert_eq!(
+            Some(3),
+            parse_octal::<u32>(b"0008\0").err().map(|e| e.offset())
+        );
+        assert_eq!(
+            Some(2),
+            parse_octal::<u32>(b"06 4\0").err().map(|e| e.offset())
+        );
+        assert!(parse_octal::<u8>(b"777").unwrap_err().is_overflow());
+
+        let mut field = [0u8; 8];
+        format_octal(0o644u32, &mut field[..7]).unwrap();
+        assert_eq!(&field, b"0000644\0");
+        assert!(format_octal(0o1000u32, &mut field[..3]).is_err());
+    }
+
+    #[test]
+    fn base256() {
+        let mut field = [0u8; 12];
+        field[0] = 0x80;
+        field[4..].copy_from_slice(&(1u64 << 40).to_be_bytes());
+        assert_eq!(Ok(1u64 << 40), parse_octal(&field));
+        assert_eq!(
+            Ok(0x3f00_0000_0000_0001u64),
+            parse_octal(b"\xbf\0\0\0\0\0\0\x01")
+        );
+        field[0] = 0x81;
+        assert!(parse_octal::<u64>(&field).unwrap_err().is_overflow());
+        field[0] = 0xff;
+        assert!(parse_octal::<u64>(&field).is_err());
+    }
+
+    #[test]
+    fn decimal() {
+        assert_eq!(Ok(1000u32), parse_decimal(b"1000      "));
+        assert_eq!(Ok(u64::MAX), parse_decimal(b"18446744073709551615"));
+        assert!(parse_decimal::<u64>(b"18446744073709551616")
+            .unwrap_err()
+            .is_overflow());
+
+        let mut field = [0u8; 10];
+        format_decimal(1000u32, &mut field).unwrap();
+        assert_eq!(&field, b"1000      ");
+        format_decimal(0u32, &mut field).unwrap();
+        assert_eq!(&field, b"0         ");
+        assert!(format_decimal(u64::MAX, &mut field).is_err());
+    }
+
+    #[test]
+    fn io() {
+        let mut buf = vec![];
+        write_octal::<12>(&mut buf, 1000u64).unwrap();
+        write_decimal::<10>(&mut buf, 1000u64).unwrap();
+        assert_eq!(&buf, b"00000001750\x001000      ");
+        let mut buf = buf.as_slice();
+        assert_eq!(1000u64, read_octal::<12, _>(&mut buf).unwrap());
+        assert_eq!(1000u16, read_decimal::<10, _>(&mut buf).unwrap());
+    }
+}
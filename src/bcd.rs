@@ -0,0 +1,142 @@
+//! Packed [binary-coded decimal](https://en.wikipedia.org/wiki/Binary-coded_decimal),
+//! two decimal digits per byte.
+//!
+//! Encodings are fixed-width, padded with leading zeros.
+//! The [`DigitOrder`] selects where each digit lives.
+//!
+//! ```
+//! use bitendian::bcd::{self, DigitOrder};
+//!
+//! assert_eq!(bcd::encode::<3>(1234, DigitOrder::Big), Some([0x00, 0x12, 0x34]));
+//! assert_eq!(bcd::encode::<3>(1234, DigitOrder::Little), Some([0x34, 0x12, 0x00]));
+//! // "Telephony BCD", as used for IMSIs and dialled numbers.
+//! assert_eq!(bcd::encode::<3>(1234, DigitOrder::Swapped), Some([0x00, 0x21, 0x43]));
+//!
+//! assert_eq!(bcd::decode([0x12, 0x34], DigitOrder::Big), Ok(1234));
+//! assert_eq!(bcd::decode([0x1a], DigitOrder::Big), Err(bcd::Error::InvalidDigit(0xa)));
+//! ```
+
+use core::fmt;
+
+/// Enough bytes to encode any [`u64`].
+pub const MAX_LEN: usize = 10;
+
+/// Where digits are placed in a packed BCD encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigitOrder {
+    /// Most significant byte first, and the more significant digit in each
+    /// byte's high nibble.
+    ///
+    /// `1234` is `[0x12, 0x34]`.
+    Big,
+    /// Least significant byte first, and the more significant digit in each
+    /// byte's high nibble.
+    ///
+    /// `1234` is `[0x34, 0x12]`.
+    Little,
+    /// Most significant byte first, and the more significant digit in each
+    /// byte's _low_ nibble, as in telecom (SS7, SIM) "TBCD".
+    ///
+    /// `1234` is `[0x21, 0x43]`.
+    Swapped,
+}
+
+impl DigitOrder {
+    /// The byte index and bit shift of the `i`th least significant digit in
+    /// an `n` byte encoding.
+    fn locate(self, n: usize, i: usize) -> (usize, u32) {
+        let byte = match self {
+            DigitOrder::Big | DigitOrder::Swapped => n - 1 - i / 2,
+            DigitOrder::Little => i / 2,
+        };
+        let shift = (i % 2) as u32 * 4;
+        match self {
+            DigitOrder::Swapped => (byte, shift ^ 4),
+            _ => (byte, shift),
+        }
+    }
+}
+
+/// Encode `value` into `N` bytes, or [`None`] if it has more than `2 * N`
+/// digits.
+pub fn encode<const N: usize>(mut value: u64, order: DigitOrder) -> Option<[u8; N]> {
+    let mut bytes = [0; N];
+    for i in 0..N * 2 {
+        if value == 0 {
+            break;
+        }
+        let (byte, shift) = order.locate(N, i);
+        bytes[byte] |= ((value % 10) as u8) << shift;
+        value /= 10;
+    }
+    match value {
+        0 => Some(bytes),
+        _ => None,
+    }
+}
+
+/// Decode `N` bytes of packed BCD.
+pub fn decode<const N: usize>(bytes: [u8; N], order: DigitOrder) -> Result<u64, Error> {
+    let mut value = 0u64;
+    for i in (0..N * 2).rev() {
+        let (byte, shift) = order.locate(N, i);
+        let digit = (bytes[byte] >> shift) & 0xf;
+        if digit > 9 {
+            return Err(Error::InvalidDigit(digit));
+        }
+        value = value
+            .checked_mul(10)
+            .and_then(|it| it.checked_add(u64::from(digit)))
+            .ok_or(Error::Overflow)?;
+    }
+    Ok(value)
+}
+
+/// Error returned when decoding invalid BCD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// A nibble was not in `0..=9`.
+    InvalidDigit(u8),
+    /// The value doesn't fit in a [`u64`].
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidDigit(it) => f.write_fmt(format_args!("invalid BCD digit {:#x}", it)),
+            Error::Overflow => f.write_str("BCD value overflows a u64"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for order in [DigitOrder::Big, DigitOrder::Little, DigitOrder::Swapped] {
+            for value in [0, 1, 9, 10, 99, 1234, 98_765_432_109, u64::MAX] {
+                let bytes = encode::<MAX_LEN>(value, order).unwrap();
+                assert_eq!(decode(bytes, order), Ok(value));
+            }
+        }
+    }
+
+    #[test]
+    fn limits() {
+        assert_eq!(encode::<2>(9999, DigitOrder::Big), Some([0x99, 0x99]));
+        assert_eq!(encode::<2>(10_000, DigitOrder::Big), None);
+        assert_eq!(encode::<0>(0, DigitOrder::Big), Some([]));
+        assert_eq!(decode([0x99; 11], DigitOrder::Little), Err(Error::Overflow));
+        assert_eq!(
+            decode([0x0f, 0x00], DigitOrder::Swapped),
+            Err(Error::InvalidDigit(0xf))
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Inspect and convert binary files from the command line.
+
+use bitendian::{slice, BitEndian, Endian};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Write as _},
+    path::PathBuf,
+};
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the contents of a file as columns of numbers.
+    Dump {
+        file: PathBuf,
+        #[arg(short, long, default_value = "u8")]
+        r#type: Type,
+        #[arg(short, long, default_value = "big")]
+        endian: Order,
+        /// Number of values per line.
+        #[arg(short, long, default_value_t = 8)]
+        columns: usize,
+    },
+    /// Reverse the byte order of every element in a file.
+    Swap {
+        input: PathBuf,
+        output: PathBuf,
+        /// Element width in bytes.
+        #[arg(short, long)]
+        width: usize,
+    },
+    /// Detect byte order marks and TIFF headers.
+    Detect { file: PathBuf },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Type {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    U128,
+    I128,
+    F32,
+    F64,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Order {
+    Big,
+    Little,
+    Native,
+}
+
+impl From<Order> for Endian {
+    fn from(value: Order) -> Self {
+        match value {
+            Order::Big => Endian::Big,
+            Order::Little => Endian::Little,
+            Order::Native => Endian::Native,
+        }
+    }
+}
+
+fn main() -> io::Result<()> {
+    match Args::parse().command {
+        Command::Dump {
+            file,
+            r#type,
+            endian,
+            columns,
+        } => {
+            let bytes = fs::read(file)?;
+            let endian = Endian::from(endian);
+            let columns = columns.max(1);
+            match r#type {
+                Type::U8 => dump::<1, u8>(&bytes, endian, columns),
+                Type::I8 => dump::<1, i8>(&bytes, endian, columns),
+                Type::U16 => dump::<2, u16>(&bytes, endian, columns),
+                Type::I16 => dump::<2, i16>(&bytes, endian, columns),
+                Type::U32 => dump::<4, u32>(&bytes, endian, columns),
+                Type::I32 => dump::<4, i32>(&bytes, endian, columns),
+                Type::U64 => dump::<8, u64>(&bytes, endian, columns),
+                Type::I64 => dump::<8, i64>(&bytes, endian, columns),
+                Type::U128 => dump::<16, u128>(&bytes, endian, columns),
+                Type::I128 => dump::<16, i128>(&bytes, endian, columns),
+                Type::F32 => dump::<4, f32>(&bytes, endian, columns),
+                Type::F64 => dump::<8, f64>(&bytes, endian, columns),
+            }
+        }
+        Command::Swap {
+            input,
+            output,
+            width,
+        } => {
+            if width == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "width must be nonzero",
+                ));
+            }
+            let mut bytes = fs::read(input)?;
+            if bytes.len() % width != 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("file length {} is not a multiple of {width}", bytes.len()),
+                ));
+            }
+            for chunk in bytes.chunks_exact_mut(width) {
+                chunk.reverse()
+            }
+            fs::write(output, bytes)
+        }
+        Command::Detect { file } => {
+            let bytes = fs::read(file)?;
+            println!("{}", detect(&bytes).unwrap_or("unknown"));
+            Ok(())
+        }
+    }
+}
+
+fn dump<const N: usize, T: BitEndian<N> + Display>(
+    bytes: &[u8],
+    endian: Endian,
+    columns: usize,
+) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    let values = slice::iter_endian::<N, T>(bytes, endian);
+    let remainder = values.remainder();
+    for (ix, value) in values.enumerate() {
+        match ix % columns {
+            0 if ix == 0 => write!(stdout, "{:08x}:", 0)?,
+            0 => write!(stdout, "\n{:08x}:", ix * N)?,
+            _ => {}
+        }
+        write!(stdout, " {value:>width$}", width = N * 3)?;
+    }
+    if bytes.len() >= N {
+        writeln!(stdout)?;
+    }
+    if !remainder.is_empty() {
+        write!(stdout, "{:08x}: trailing", bytes.len() - remainder.len())?;
+        for byte in remainder {
+            write!(stdout, " {byte:02x}")?;
+        }
+        writeln!(stdout)?;
+    }
+    Ok(())
+}
+
+fn detect(bytes: &[u8]) -> Option<&'static str> {
+    // longest prefixes first, so UTF-32LE isn't mistaken for UTF-16LE
+    const MARKERS: &[(&[u8], &str)] = &[
+        (
+            &[0x00, 0x00, 0xfe, 0xff],
+            "UTF-32 byte order mark (big-endian)",
+        ),
+        (
+            &[0xff, 0xfe, 0x00, 0x00],
+            "UTF-32 byte order mark (little-endian)",
+        ),
+        (b"MM\x00\x2a", "TIFF (big-endian)"),
+        (b"II\x2a\x00", "TIFF (little-endian)"),
+        (b"MM\x00\x2b", "BigTIFF (big-endian)"),
+        (b"II\x2b\x00", "BigTIFF (little-endian)"),
+        (&[0xef, 0xbb, 0xbf], "UTF-8 byte order mark"),
+        (&[0xfe, 0xff], "UTF-16 byte order mark (big-endian)"),
+        (&[0xff, 0xfe], "UTF-16 byte order mark (little-endian)"),
+    ];
+    MARKERS
+        .iter()
+        .find(|(marker, _)| bytes.starts_with(marker))
+        .map(|(_, description)| *description)
+}
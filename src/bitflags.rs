@@ -0,0 +1,197 @@
+//! Interoperability with [`bitflags`](https://docs.rs/bitflags/2/bitflags/).
+//!
+//! Flags types are encoded as their underlying bits.
+//! Wrap them to choose what happens to bits which don't correspond to a
+//! defined flag when decoding:
+//! - [`Strict`] rejects them, through [`TryBitEndian`].
+//! - [`Retain`] keeps them.
+//! - [`Truncate`] discards them.
+//!
+//! ```
+//! use bitendian::{bitflags::{Retain, Strict, UnknownBits}, BitEndian as _, TryBitEndian as _};
+//!
+//! bitflags::bitflags! {
+//!     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//!     struct Mode: u16 {
+//!         const READ = 0x0100;
+//!         const WRITE = 0x0200;
+//!     }
+//! }
+//!
+//! assert_eq!(Retain(Mode::READ | Mode::WRITE).to_be_bytes(), [0x03, 0x00]);
+//! assert_eq!(Strict::<Mode>::try_from_be_bytes([0x01, 0x00]), Ok(Strict(Mode::READ)));
+//! assert_eq!(Strict::<Mode>::try_from_be_bytes([0x01, 0x01]), Err(UnknownBits(0x0001)));
+//! assert_eq!(Retain::<Mode>::from_be_bytes([0x01, 0x01]).0.bits(), 0x0101);
+//! ```
+
+use crate::{BitEndian, TryBitEndian};
+use ::bitflags::{Bits, Flags};
+use core::fmt;
+
+/// Decoding fails if any unknown bits are set.
+///
+/// Encode with [`Retain`], or by encoding [`Flags::bits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Strict<F>(pub F);
+
+/// Decoding keeps any unknown bits, see [`Flags::from_bits_retain`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Retain<F>(pub F);
+
+/// Decoding discards any unknown bits, see [`Flags::from_bits_truncate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Truncate<F>(pub F);
+
+impl<const N: usize, F: Flags> TryBitEndian<N> for Strict<F>
+where
+    F::Bits: BitEndian<N>,
+{
+    type Error = UnknownBits<F::Bits>;
+
+    fn try_from_le_bytes(bytes: [u8; N]) -> Result<Self, Self::Error> {
+        strict(F::Bits::from_le_bytes(bytes))
+    }
+    fn try_from_be_bytes(bytes: [u8; N]) -> Result<Self, Self::Error> {
+        strict(F::Bits::from_be_bytes(bytes))
+    }
+    fn try_from_ne_bytes(bytes: [u8; N]) -> Result<Self, Self::Error> {
+        strict(F::Bits::from_ne_bytes(bytes))
+    }
+}
+
+fn strict<F: Flags>(bits: F::Bits) -> Result<Strict<F>, UnknownBits<F::Bits>> {
+    match bits & !F::all().bits() {
+        unknown if unknown == F::Bits::EMPTY => Ok(Strict(F::from_bits_retain(bits))),
+        unknown => Err(UnknownBits(unknown)),
+    }
+}
+
+macro_rules! bit_endian_wrapper {
+    ($($ty:ident => $from_bits:ident),* $(,)?) => {
+        $(
+            impl<const N: usize, F: Flags> BitEndian<N> for $ty<F>
+            where
+                F::Bits: BitEndian<N>,
+            {
+                fn to_le_bytes(self) -> [u8; N] {
+                    self.0.bits().to_le_bytes()
+                }
+                fn to_be_bytes(self) -> [u8; N] {
+                    self.0.bits().to_be_bytes()
+                }
+                fn to_ne_bytes(self) -> [u8; N] {
+                    self.0.bits().to_ne_bytes()
+                }
+
+                fn from_le_bytes(bytes: [u8; N]) -> Self {
+                    Self(F::$from_bits(F::Bits::from_le_bytes(bytes)))
+                }
+                fn from_be_bytes(bytes: [u8; N]) -> Self {
+                    Self(F::$from_bits(F::Bits::from_be_bytes(bytes)))
+                }
+                fn from_ne_bytes(bytes: [u8; N]) -> Self {
+                    Self(F::$from_bits(F::Bits::from_ne_bytes(bytes)))
+                }
+            }
+
+            #[cfg(feature = "std")]
+            impl<F: Flags> crate::io::Decode for $ty<F>
+            where
+                F::Bits: crate::io::Decode,
+            {
+                fn decode<R: std::io::Read + ?Sized>(
+                    reader: &mut R,
+                    endian: crate::Endian,
+                ) -> std::io::Result<Self> {
+                    crate::io::Decode::decode(reader, endian).map(|it| Self(F::$from_bits(it)))
+                }
+            }
+        )*
+    };
+}
+bit_endian_wrapper!(Retain => from_bits_retain, Truncate => from_bits_truncate);
+
+#[cfg(feature = "std")]
+impl<F: Flags> crate::io::Decode for Strict<F>
+where
+    F::Bits: crate::io::Decode + fmt::Debug + fmt::LowerHex + Send + Sync,
+{
+    fn decode<R: std::io::Read + ?Sized>(
+        reader: &mut R,
+        endian: crate::Endian,
+    ) -> std::io::Result<Self> {
+        strict(crate::io::Decode::decode(reader, endian)?)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+macro_rules! encode {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            #[cfg(feature = "std")]
+            impl<F: Flags> crate::io::Encode for $ty<F>
+            where
+                F::Bits: crate::io::Encode,
+            {
+                fn encode<W: std::io::Write + ?Sized>(
+                    &self,
+                    writer: &mut W,
+                    endian: crate::Endian,
+                ) -> std::io::Result<()> {
+                    self.0.bits().encode(writer, endian)
+                }
+            }
+        )*
+    };
+}
+encode!(Strict, Retain, Truncate);
+
+/// Error returned when decoding a [`Strict`] with bits which don't correspond
+/// to any flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnknownBits<B>(pub B);
+
+impl<B: fmt::LowerHex> fmt::Display for UnknownBits<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("unknown flag bits {:#x}", self.0))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<B: fmt::Debug + fmt::LowerHex> std::error::Error for UnknownBits<B> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{DecodeExt as _, EncodeExt as _, ReadExt as _};
+    use std::io;
+
+    ::bitflags::bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        struct Flags32: u32 {
+            const A = 1;
+            const B = 1 << 31;
+        }
+    }
+
+    #[test]
+    fn io() {
+        let mut buf = vec![];
+        buf.encode_le(&Strict(Flags32::A | Flags32::B)).unwrap();
+        buf.encode_le(&Retain(Flags32::from_bits_retain(0b11)))
+            .unwrap();
+        assert_eq!(buf, [1, 0, 0, 0x80, 0b11, 0, 0, 0]);
+
+        let mut r = buf.as_slice();
+        assert_eq!(
+            r.try_read_le::<Strict<Flags32>>().unwrap().0,
+            Flags32::all()
+        );
+        let mut peek = r;
+        assert_eq!(
+            peek.decode_le::<Strict<Flags32>>().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(r.decode_le::<Truncate<Flags32>>().unwrap().0, Flags32::A);
+    }
+}
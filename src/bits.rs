@@ -0,0 +1,121 @@
+//! Random access to bit ranges within byte buffers, using network bit numbering.
+//!
+//! Bit `0` is the most significant bit of the first byte, so fields are laid
+//! out exactly as they're drawn in RFC header diagrams.
+//!
+//! ```
+//! use bitendian::bits::{get_bits_be, set_bits_be};
+//!
+//! // the first byte of an IPv4 header: version 4, IHL 5
+//! let mut header = [0x45, 0x00];
+//! assert_eq!(get_bits_be(&header, 0, 4), 4);
+//! assert_eq!(get_bits_be(&header, 4, 4), 5);
+//!
+//! set_bits_be(&mut header, 4, 4, 6);
+//! assert_eq!(header, [0x46, 0x00]);
+//! ```
+
+/// Read `bit_len` bits starting at `bit_offset`, returning them in the least
+/// significant bits of a [`u64`].
+///
+/// # Panics
+/// - If `bit_len` is greater than `64`.
+/// - If the range is out of bounds for `buf`.
+pub fn get_bits_be(buf: &[u8], bit_offset: usize, bit_len: usize) -> u64 {
+    let span = Span::new(buf.len(), bit_offset, bit_len);
+    let acc = buf[span.bytes()]
+        .iter()
+        .fold(0u128, |acc, byte| acc << 8 | u128::from(*byte));
+    ((acc >> span.shift()) & span.mask()) as u64
+}
+
+/// Write the least significant `bit_len` bits of `value` starting at
+/// `bit_offset`, leaving the surrounding bits untouched.
+///
+/// Any higher bits of `value` are ignored.
+///
+/// # Panics
+/// - If `bit_len` is greater than `64`.
+/// - If the range is out of bounds for `buf`.
+pub fn set_bits_be(buf: &mut [u8], bit_offset: usize, bit_len: usize, value: u64) {
+    let span = Span::new(buf.len(), bit_offset, bit_len);
+    let bytes = &mut buf[span.bytes()];
+    let mut acc = bytes
+        .iter()
+        .fold(0u128, |acc, byte| acc << 8 | u128::from(*byte));
+    acc &= !(span.mask() << span.shift());
+    acc |= (u128::from(value) & span.mask()) << span.shift();
+    for byte in bytes.iter_mut().rev() {
+        *byte = acc as u8;
+        acc >>= 8;
+    }
+}
+
+/// A bit range, validated against a buffer.
+struct Span {
+    start: usize,
+    end: usize,
+}
+
+impl Span {
+    fn new(buf_len: usize, bit_offset: usize, bit_len: usize) -> Self {
+        assert!(bit_len <= 64, "bit_len must be at most 64, not {bit_len}");
+        let end = bit_offset
+            .checked_add(bit_len)
+            .filter(|end| end.div_ceil(8) <= buf_len)
+            .unwrap_or_else(|| {
+                panic!(
+                    "bit range {bit_offset}+{bit_len} out of bounds for buffer of length {buf_len}"
+                )
+            });
+        Self {
+            start: bit_offset,
+            end,
+        }
+    }
+    /// The bytes which contain the range, at most 9.
+    fn bytes(&self) -> core::ops::Range<usize> {
+        self.start / 8..self.end.div_ceil(8)
+    }
+    /// How far the range is from the least significant bit of the last byte.
+    fn shift(&self) -> usize {
+        self.end.div_ceil(8) * 8 - self.end
+    }
+    fn mask(&self) -> u128 {
+        (1u128 << (self.end - self.start)) - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tcp_flags() {
+        // data offset 5, flags SYN + ACK
+        let mut buf = [0x50, 0x12];
+        assert_eq!(get_bits_be(&buf, 0, 4), 5);
+        assert_eq!(get_bits_be(&buf, 10, 6), 0b01_0010);
+        set_bits_be(&mut buf, 10, 6, 0b00_0001);
+        assert_eq!(buf, [0x50, 0x01]);
+    }
+
+    #[test]
+    fn unaligned_wide() {
+        let mut buf = [0u8; 10];
+        set_bits_be(&mut buf, 3, 64, u64::MAX);
+        assert_eq!(
+            buf,
+            [0x1f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xe0, 0x00]
+        );
+        assert_eq!(get_bits_be(&buf, 3, 64), u64::MAX);
+        assert_eq!(get_bits_be(&buf, 2, 64), u64::MAX >> 1);
+        assert_eq!(get_bits_be(&buf, 80, 0), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        get_bits_be(&[0], 4, 5);
+    }
+}
@@ -0,0 +1,109 @@
+//! Interoperability with [`bitvec`](https://docs.rs/bitvec/1/bitvec/).
+//!
+//! ```
+//! use bitendian::{bitvec::{load_endian, read_bits}, Endian};
+//! use bitvec::prelude::*;
+//!
+//! # fn doit() -> std::io::Result<()> {
+//! let mut reader = [0b1010_0000, 0x01, 0x02].as_slice();
+//! let flags = read_bits::<Msb0>(&mut reader, 4)?;
+//! assert_eq!(flags, bits![1, 0, 1, 0]);
+//!
+//! let bits = [0x01, 0x02].view_bits::<Lsb0>();
+//! assert_eq!(load_endian::<u16, _>(bits, Endian::Big), 0x0102);
+//! assert_eq!(load_endian::<u16, _>(bits, Endian::Little), 0x0201);
+//! # Ok(())
+//! # }
+//! # doit().unwrap()
+//! ```
+
+use crate::Endian;
+use ::bitvec::{field::BitField, order::BitOrder, slice::BitSlice};
+use funty::Integral;
+
+/// Load an integer from `bits`, where bytes are combined according to a
+/// run-time endianness, and bits within each byte according to `O`.
+///
+/// # Panics
+/// - If `bits` is empty, or wider than `T`.
+pub fn load_endian<T: Integral, O: BitOrder>(bits: &BitSlice<u8, O>, endian: Endian) -> T
+where
+    BitSlice<u8, O>: BitField,
+{
+    match endian.is_big() {
+        true => bits.load_be(),
+        false => bits.load_le(),
+    }
+}
+
+/// Store an integer into `bits`, as in [`load_endian`].
+///
+/// Any bits of `value` which don't fit in `bits` are discarded.
+///
+/// # Panics
+/// - If `bits` is empty, or wider than `T`.
+pub fn store_endian<T: Integral, O: BitOrder>(bits: &mut BitSlice<u8, O>, value: T, endian: Endian)
+where
+    BitSlice<u8, O>: BitField,
+{
+    match endian.is_big() {
+        true => bits.store_be(value),
+        false => bits.store_le(value),
+    }
+}
+
+/// Read the whole bytes containing `bit_len` bits, returning the first
+/// `bit_len` bits according to `O`.
+///
+/// Any remaining bits in the last byte are discarded.
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn read_bits<O: BitOrder>(
+    reader: &mut (impl std::io::Read + ?Sized),
+    bit_len: usize,
+) -> std::io::Result<::bitvec::vec::BitVec<u8, O>> {
+    let mut bytes = vec![0u8; bit_len.div_ceil(8)];
+    reader.read_exact(&mut bytes)?;
+    let mut bits = ::bitvec::vec::BitVec::from_vec(bytes);
+    bits.truncate(bit_len);
+    Ok(bits)
+}
+
+/// Write `bits`, filling any partial last byte with zeroes.
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn write_bits<O: BitOrder>(
+    writer: &mut (impl std::io::Write + ?Sized),
+    bits: &BitSlice<u8, O>,
+) -> std::io::Result<()> {
+    let mut bits = ::bitvec::vec::BitVec::<u8, O>::from_bitslice(bits);
+    bits.set_uninitialized(false);
+    writer.write_all(bits.as_raw_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::bitvec::prelude::*;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = vec![];
+        write_bits(&mut buf, bits![u8, Lsb0; 1, 1, 0, 1]).unwrap();
+        assert_eq!(buf, [0b1011]);
+        let bits = read_bits::<Lsb0>(&mut buf.as_slice(), 4).unwrap();
+        assert_eq!(bits, bits![1, 1, 0, 1]);
+
+        let mut bytes = [0u8; 3];
+        store_endian(
+            &mut bytes.view_bits_mut::<Msb0>()[4..20],
+            0xabcdu16,
+            Endian::Big,
+        );
+        assert_eq!(bytes, [0x0a, 0xbc, 0xd0]);
+        assert_eq!(
+            load_endian::<u16, _>(&bytes.view_bits::<Msb0>()[4..20], Endian::Big),
+            0xabcd
+        );
+    }
+}
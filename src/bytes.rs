@@ -0,0 +1,144 @@
+//! Extension methods for [`bytes`](https://docs.rs/bytes/1/bytes/)'s
+//! [`Buf`] and [`BufMut`].
+//!
+//! ```
+//! use bitendian::bytes::{BufExt as _, BufMutExt as _};
+//! use bytes::BytesMut;
+//!
+//! let mut buf = BytesMut::new();
+//! buf.put_be(1u16);
+//! buf.put_le(1u16);
+//! let mut buf = buf.freeze();
+//! let be: u16 = buf.get_be();
+//! let le: u16 = buf.get_le();
+//! assert_eq!((be, le), (1, 1));
+//! assert!(buf.try_get_be::<u16>().is_err());
+//! ```
+
+use crate::{cursor::UnexpectedEnd, BitEndian, Endian};
+use ::bytes::{Buf, BufMut};
+
+/// Extends [`Buf`] with methods for reading in an endian-dependent way.
+///
+/// Like [`Buf::get_u32`] etc., the `get_*` methods panic if there aren't
+/// enough bytes remaining.
+pub trait BufExt<const N: usize>: Buf {
+    /// Get according to a run-time endianness.
+    ///
+    /// # Panics
+    /// - If fewer than `N` bytes remain.
+    fn get_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> T {
+        let mut bytes = [0u8; N];
+        self.copy_to_slice(&mut bytes);
+        T::from_bytes_endian(bytes, endian)
+    }
+    /// Get with [`Endian::Big`].
+    ///
+    /// # Panics
+    /// - If fewer than `N` bytes remain.
+    fn get_be<T: BitEndian<N>>(&mut self) -> T {
+        self.get_endian(Endian::Big)
+    }
+    /// Get with [`Endian::Little`].
+    ///
+    /// # Panics
+    /// - If fewer than `N` bytes remain.
+    fn get_le<T: BitEndian<N>>(&mut self) -> T {
+        self.get_endian(Endian::Little)
+    }
+    /// Get with [`Endian::Native`].
+    ///
+    /// # Panics
+    /// - If fewer than `N` bytes remain.
+    fn get_ne<T: BitEndian<N>>(&mut self) -> T {
+        self.get_endian(Endian::Native)
+    }
+    /// Get according to a run-time endianness, or consume nothing if fewer
+    /// than `N` bytes remain.
+    fn try_get_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> Result<T, UnexpectedEnd> {
+        match self.remaining() {
+            remaining if remaining < N => Err(UnexpectedEnd {
+                offset: 0,
+                needed: N,
+                remaining,
+            }),
+            _ => Ok(self.get_endian(endian)),
+        }
+    }
+    /// Try to get with [`Endian::Big`].
+    fn try_get_be<T: BitEndian<N>>(&mut self) -> Result<T, UnexpectedEnd> {
+        self.try_get_endian(Endian::Big)
+    }
+    /// Try to get with [`Endian::Little`].
+    fn try_get_le<T: BitEndian<N>>(&mut self) -> Result<T, UnexpectedEnd> {
+        self.try_get_endian(Endian::Little)
+    }
+    /// Try to get with [`Endian::Native`].
+    fn try_get_ne<T: BitEndian<N>>(&mut self) -> Result<T, UnexpectedEnd> {
+        self.try_get_endian(Endian::Native)
+    }
+}
+impl<const N: usize, B: Buf + ?Sized> BufExt<N> for B {}
+
+/// Extends [`BufMut`] with methods for writing in an endian-dependent way.
+///
+/// Like [`BufMut::put_u32`] etc., these methods panic if there isn't enough
+/// capacity.
+pub trait BufMutExt<const N: usize>: BufMut {
+    /// Put according to a run-time endianness.
+    ///
+    /// # Panics
+    /// - If there isn't room for `N` bytes.
+    fn put_endian<T: BitEndian<N>>(&mut self, it: T, endian: Endian) {
+        self.put_slice(&it.to_bytes_endian(endian))
+    }
+    /// Put with [`Endian::Big`].
+    ///
+    /// # Panics
+    /// - If there isn't room for `N` bytes.
+    fn put_be<T: BitEndian<N>>(&mut self, it: T) {
+        self.put_endian(it, Endian::Big)
+    }
+    /// Put with [`Endian::Little`].
+    ///
+    /// # Panics
+    /// - If there isn't room for `N` bytes.
+    fn put_le<T: BitEndian<N>>(&mut self, it: T) {
+        self.put_endian(it, Endian::Little)
+    }
+    /// Put with [`Endian::Native`].
+    ///
+    /// # Panics
+    /// - If there isn't room for `N` bytes.
+    fn put_ne<T: BitEndian<N>>(&mut self, it: T) {
+        self.put_endian(it, Endian::Native)
+    }
+}
+impl<const N: usize, B: BufMut + ?Sized> BufMutExt<N> for B {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chained() {
+        let mut buf = [0u8; 3];
+        let mut w = &mut buf[..];
+        w.put_le(0x0102u16);
+        w.put_ne(3u8);
+        assert_eq!(buf, [2, 1, 3]);
+
+        // reads span both halves of the chain
+        let mut r = Buf::chain(&buf[..1], &buf[1..]);
+        assert_eq!(r.get_be::<u16>(), 0x0201);
+        assert_eq!(
+            r.try_get_le::<u16>(),
+            Err(UnexpectedEnd {
+                offset: 0,
+                needed: 2,
+                remaining: 1
+            })
+        );
+        assert_eq!(r.try_get_endian::<u8>(Endian::Big), Ok(3));
+    }
+}
@@ -0,0 +1,329 @@
+//! The [CBOR](https://www.rfc-editor.org/rfc/rfc8949#section-3) data item
+//! head, for streaming encoders and decoders which don't need a full CBOR
+//! implementation.
+//!
+//! A head is a major type, and an argument which is either packed into the
+//! initial byte, or follows it as a big-endian integer of 1, 2, 4 or 8 bytes.
+//!
+//! ```
+//! use bitendian::cbor::{encode_head, decode_head, Head, Major, MAX_LEN};
+//!
+//! let mut buf = [0; MAX_LEN];
+//! let head = Head::new(Major::Bytes, 500);
+//! let encoded = encode_head(head, &mut buf);
+//! assert_eq!(encoded, [0x59, 0x01, 0xf4]);
+//! assert_eq!(decode_head(encoded), Ok((head, &[][..])));
+//! ```
+
+use core::fmt;
+
+/// The longest possible head, an initial byte and an eight byte argument.
+pub const MAX_LEN: usize = 9;
+
+/// The additional information for an indefinite length, or a break.
+const INDEFINITE: u8 = 31;
+
+/// The major type of a data item, the top three bits of the initial byte.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Major {
+    /// An unsigned integer.
+    Unsigned = 0,
+    /// A negative integer, `-1 - argument`.
+    Negative = 1,
+    /// A byte string of `argument` bytes.
+    Bytes = 2,
+    /// A UTF-8 string of `argument` bytes.
+    Text = 3,
+    /// An array of `argument` items.
+    Array = 4,
+    /// A map of `argument` pairs.
+    Map = 5,
+    /// A tag, followed by the tagged item.
+    Tag = 6,
+    /// A simple value, float, or break.
+    Simple = 7,
+}
+
+impl Major {
+    /// Whether items of this type may have an indefinite length.
+    pub const fn allows_indefinite(self) -> bool {
+        matches!(
+            self,
+            Major::Bytes | Major::Text | Major::Array | Major::Map | Major::Simple
+        )
+    }
+    const fn from_bits(bits: u8) -> Self {
+        match bits & 0b111 {
+            0 => Major::Unsigned,
+            1 => Major::Negative,
+            2 => Major::Bytes,
+            3 => Major::Text,
+            4 => Major::Array,
+            5 => Major::Map,
+            6 => Major::Tag,
+            _ => Major::Simple,
+        }
+    }
+}
+
+/// A data item head.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Head {
+    /// The major type.
+    pub major: Major,
+    /// The argument, or [`None`] for an indefinite length (or a break, for
+    /// [`Major::Simple`]).
+    pub argument: Option<u64>,
+}
+
+impl Head {
+    /// A head with a definite argument.
+    pub const fn new(major: Major, argument: u64) -> Self {
+        Self {
+            major,
+            argument: Some(argument),
+        }
+    }
+    /// A head with an indefinite length.
+    pub const fn indefinite(major: Major) -> Self {
+        Self {
+            major,
+            argument: None,
+        }
+    }
+}
+
+/// Encode `head` into `buf` in the shortest form, returning the used portion.
+///
+/// Floats are not simple values - use [`encode_float16`], [`encode_float32`]
+/// or [`encode_float64`] for them.
+///
+/// # Panics
+/// - If `head` is indefinite, but its major type doesn't
+///   [allow it](Major::allows_indefinite).
+/// - If `head` is a [`Major::Simple`] value outside `0..=23` or `32..=255`,
+///   which have no well-formed encoding.
+pub fn encode_head(head: Head, buf: &mut [u8; MAX_LEN]) -> &[u8] {
+    let Some(argument) = head.argument else {
+        assert!(
+            head.major.allows_indefinite(),
+            "{:?} may not have an indefinite length",
+            head.major
+        );
+        buf[0] = (head.major as u8) << 5 | INDEFINITE;
+        return &buf[..1];
+    };
+    if head.major == Major::Simple {
+        assert!(
+            matches!(argument, 0..=23 | 32..=255),
+            "simple value {} has no well-formed encoding",
+            argument
+        );
+    }
+    let bytes = argument.to_be_bytes();
+    match argument {
+        0..=23 => encode(head.major, argument as u8, &[], buf),
+        24..=0xff => encode(head.major, 24, &bytes[7..], buf),
+        0x100..=0xffff => encode(head.major, 25, &bytes[6..], buf),
+        0x1_0000..=0xffff_ffff => encode(head.major, 26, &bytes[4..], buf),
+        _ => encode(head.major, 27, &bytes, buf),
+    }
+}
+
+/// Encode the head of a half-precision float, given as its bits, returning
+/// the used portion of `buf`.
+pub fn encode_float16(bits: u16, buf: &mut [u8; MAX_LEN]) -> &[u8] {
+    encode(Major::Simple, 25, &bits.to_be_bytes(), buf)
+}
+
+/// Encode the head of a single-precision float, returning the used portion
+/// of `buf`.
+pub fn encode_float32(value: f32, buf: &mut [u8; MAX_LEN]) -> &[u8] {
+    encode(Major::Simple, 26, &value.to_bits().to_be_bytes(), buf)
+}
+
+/// Encode the head of a double-precision float, returning the used portion
+/// of `buf`.
+pub fn encode_float64(value: f64, buf: &mut [u8; MAX_LEN]) -> &[u8] {
+    encode(Major::Simple, 27, &value.to_bits().to_be_bytes(), buf)
+}
+
+fn encode<'a>(major: Major, info: u8, argument: &[u8], buf: &'a mut [u8; MAX_LEN]) -> &'a [u8] {
+    let len = argument.len();
+    buf[0] = (major as u8) << 5 | info;
+    buf[1..=len].copy_from_slice(argument);
+    &buf[..=len]
+}
+
+/// Decode a head from the start of `bytes`, returning the rest of the input.
+///
+/// Arguments which are not in the shortest form are accepted.
+///
+/// For [`Major::Simple`], a two, four or eight byte argument holds the bits
+/// of a half, single or double precision float respectively - the length of
+/// the head tells them apart.
+pub fn decode_head(bytes: &[u8]) -> Result<(Head, &[u8]), DecodeError> {
+    let (initial, rest) = bytes.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+    let major = Major::from_bits(initial >> 5);
+    let len = match initial & 0b1_1111 {
+        info @ 0..=23 => return Ok((Head::new(major, info.into()), rest)),
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        INDEFINITE => match major.allows_indefinite() {
+            true => return Ok((Head::indefinite(major), rest)),
+            false => return Err(DecodeError::InvalidIndefinite(*initial)),
+        },
+        _ => return Err(DecodeError::Reserved(*initial)),
+    };
+    if rest.len() < len {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    let (argument, rest) = rest.split_at(len);
+    let mut buf = [0u8; 8];
+    buf[8 - len..].copy_from_slice(argument);
+    Ok((Head::new(major, u64::from_be_bytes(buf)), rest))
+}
+
+/// Error returned when decoding a malformed head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input ended part-way through a head.
+    UnexpectedEnd,
+    /// The initial byte uses reserved additional information.
+    Reserved(u8),
+    /// The initial byte has an indefinite length, but its major type doesn't
+    /// allow one.
+    InvalidIndefinite(u8),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => f.write_str("unexpected end of head"),
+            DecodeError::Reserved(it) => {
+                f.write_fmt(format_args!("reserved initial byte {:#04x}", it))
+            }
+            DecodeError::InvalidIndefinite(it) => f.write_fmt(format_args!(
+                "initial byte {:#04x} has an invalid indefinite length",
+                it
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// Write `head`, as in [`encode_head`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn write_head(writer: &mut (impl std::io::Write + ?Sized), head: Head) -> std::io::Result<()> {
+    writer.write_all(encode_head(head, &mut [0; MAX_LEN]))
+}
+
+/// Read a head, as in [`decode_head`].
+///
+/// Malformed heads are reported as [`std::io::ErrorKind::InvalidData`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn read_head(reader: &mut (impl std::io::Read + ?Sized)) -> std::io::Result<Head> {
+    let mut buf = [0; MAX_LEN];
+    reader.read_exact(&mut buf[..1])?;
+    let len = match buf[0] & 0b1_1111 {
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        _ => 0,
+    };
+    reader.read_exact(&mut buf[1..=len])?;
+    decode_head(&buf[..=len])
+        .map(|(head, _)| head)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal() {
+        for (head, expected) in [
+            (Head::new(Major::Unsigned, 0), &[0x00][..]),
+            (Head::new(Major::Unsigned, 23), &[0x17]),
+            (Head::new(Major::Unsigned, 24), &[0x18, 0x18]),
+            (Head::new(Major::Negative, 0x100), &[0x39, 0x01, 0x00]),
+            (
+                Head::new(Major::Array, 0x1_0000),
+                &[0x9a, 0x00, 0x01, 0x00, 0x00],
+            ),
+            (
+                Head::new(Major::Tag, u64::MAX),
+                &[0xdb, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            ),
+            (Head::indefinite(Major::Map), &[0xbf]),
+            (Head::indefinite(Major::Simple), &[0xff]),
+        ] {
+            let mut buf = [0; MAX_LEN];
+            assert_eq!(encode_head(head, &mut buf), expected);
+            assert_eq!(decode_head(expected), Ok((head, &[][..])));
+            assert_eq!(read_head(&mut &*expected).unwrap(), head);
+        }
+    }
+
+    #[test]
+    fn simple() {
+        let mut buf = [0; MAX_LEN];
+        assert_eq!(encode_head(Head::new(Major::Simple, 20), &mut buf), [0xf4]);
+        assert_eq!(
+            encode_head(Head::new(Major::Simple, 32), &mut buf),
+            [0xf8, 0x20]
+        );
+        assert_eq!(encode_float16(0x3c00, &mut buf), [0xf9, 0x3c, 0x00]);
+        assert_eq!(
+            encode_float32(100000.0, &mut buf),
+            [0xfa, 0x47, 0xc3, 0x50, 0x00]
+        );
+        // not simple value 0 (`0xe0`)
+        assert_eq!(
+            encode_float64(0.0, &mut buf),
+            [0xfb, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(
+            decode_head(&[0xfb, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Ok((Head::new(Major::Simple, 0), &[][..]))
+        );
+    }
+
+    #[test]
+    #[should_panic = "simple value 24 has no well-formed encoding"]
+    fn simple_24() {
+        encode_head(Head::new(Major::Simple, 24), &mut [0; MAX_LEN]);
+    }
+
+    #[test]
+    #[should_panic = "simple value 256 has no well-formed encoding"]
+    fn simple_256() {
+        encode_head(Head::new(Major::Simple, 256), &mut [0; MAX_LEN]);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(decode_head(&[]), Err(DecodeError::UnexpectedEnd));
+        assert_eq!(decode_head(&[0x19, 0x01]), Err(DecodeError::UnexpectedEnd));
+        assert_eq!(decode_head(&[0x1c]), Err(DecodeError::Reserved(0x1c)));
+        assert_eq!(
+            decode_head(&[0x1f]),
+            Err(DecodeError::InvalidIndefinite(0x1f))
+        );
+        // non-minimal arguments are accepted
+        assert_eq!(
+            decode_head(&[0x18, 0x01]),
+            Ok((Head::new(Major::Unsigned, 1), &[][..]))
+        );
+    }
+}
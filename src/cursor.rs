@@ -0,0 +1,531 @@
+//! Cursors over byte slices, for parsing and building packets without
+//! [`std::io`].
+//!
+//! ```
+//! use bitendian::cursor::{ByteReader, ReadBytesExt as _, UnexpectedEnd};
+//!
+//! let mut reader = ByteReader::new(&[0x12, 0x34, 0x01, 0xff]);
+//! let id: u16 = reader.read_be()?;
+//! let flags: u8 = reader.read_le()?;
+//! assert_eq!((id, flags), (0x1234, 1));
+//! assert_eq!(reader.remaining(), [0xff]);
+//! assert_eq!(
+//!     reader.read_be::<u16>(),
+//!     Err(UnexpectedEnd { offset: 3, needed: 2, remaining: 1 })
+//! );
+//! # Ok::<_, UnexpectedEnd>(())
+//! ```
+//!
+//! ```
+//! use bitendian::cursor::{BufferFull, ByteWriter, WriteBytesExt as _};
+//!
+//! let mut buf = [0; 3];
+//! let mut writer = ByteWriter::new(&mut buf);
+//! writer.write_be(0x1234u16)?;
+//! assert_eq!(writer.write_le(1u16), Err(BufferFull { needed: 2, remaining: 1 }));
+//! writer.write_le(1u8)?;
+//! assert_eq!(writer.written(), [0x12, 0x34, 0x01]);
+//! # Ok::<_, BufferFull>(())
+//! ```
+
+use crate::{BitEndian, Endian};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// Reads values from the front of a byte slice.
+///
+/// Failed reads consume nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    /// Read from the start of `bytes`.
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+    /// Read the next `len` bytes, borrowed from the underlying slice.
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], UnexpectedEnd> {
+        let (read, rest) = self.bytes.split_at_checked(len).ok_or(UnexpectedEnd {
+            offset: self.position,
+            needed: len,
+            remaining: self.bytes.len(),
+        })?;
+        self.bytes = rest;
+        self.position += len;
+        Ok(read)
+    }
+    /// Skip the next `len` bytes.
+    pub fn advance(&mut self, len: usize) -> Result<(), UnexpectedEnd> {
+        self.read_bytes(len).map(drop)
+    }
+    /// The bytes which haven't been read yet.
+    pub const fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+    /// Returns true if every byte has been read.
+    pub const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+    /// The number of bytes read so far.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Extends [`ByteReader`] with methods for reading in an endian-dependent way.
+///
+/// See the [module documentation](mod@self) for usage examples.
+pub trait ReadBytesExt<const N: usize> {
+    /// Read according to a run-time endianness.
+    fn read_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> Result<T, UnexpectedEnd>;
+    /// Read with [`Endian::Big`].
+    fn read_be<T: BitEndian<N>>(&mut self) -> Result<T, UnexpectedEnd> {
+        self.read_endian(Endian::Big)
+    }
+    /// Read with [`Endian::Little`].
+    fn read_le<T: BitEndian<N>>(&mut self) -> Result<T, UnexpectedEnd> {
+        self.read_endian(Endian::Little)
+    }
+    /// Read with [`Endian::Native`].
+    fn read_ne<T: BitEndian<N>>(&mut self) -> Result<T, UnexpectedEnd> {
+        self.read_endian(Endian::Native)
+    }
+}
+
+impl<const N: usize> ReadBytesExt<N> for ByteReader<'_> {
+    fn read_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> Result<T, UnexpectedEnd> {
+        let bytes = self.read_bytes(N)?;
+        let bytes = <[u8; N]>::try_from(bytes).expect("slice is of length N");
+        Ok(T::from_bytes_endian(bytes, endian))
+    }
+}
+
+/// Error returned when reading past the end of a [`ByteReader`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnexpectedEnd {
+    /// The position in the input at which the read started.
+    ///
+    /// This is relative to the start of whatever was being read from, e.g the
+    /// slice a [`ByteReader`] was created with.
+    pub offset: usize,
+    /// The number of bytes the read needed.
+    pub needed: usize,
+    /// The number of bytes which were left.
+    pub remaining: usize,
+}
+
+impl fmt::Display for UnexpectedEnd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "unexpected end of input at offset {}: needed {} bytes, but only {} remain",
+            self.offset, self.needed, self.remaining
+        ))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnexpectedEnd {}
+
+/// As [`std::io::ErrorKind::UnexpectedEof`].
+#[cfg(feature = "std")]
+impl From<UnexpectedEnd> for std::io::Error {
+    fn from(value: UnexpectedEnd) -> Self {
+        std::io::Error::new(std::io::ErrorKind::UnexpectedEof, value)
+    }
+}
+
+/// Extends [`ByteReader`] with length-prefixed reads into [`Vec`]s and
+/// [`String`]s.
+///
+/// The length prefix is a `P`, which is `N` bytes wide.
+/// Failed reads consume nothing.
+///
+/// ```
+/// use bitendian::cursor::{ByteReader, ReadAllocExt as _, LengthPrefixedError};
+///
+/// let mut reader = ByteReader::new(&[2, 0x00, 0x01, 0x00, 0x02, 2, b'o', b'k']);
+/// let ports: Vec<u16> = reader.read_prefixed_vec_be::<u8, _, _>()?;
+/// assert_eq!(ports, [1, 2]);
+/// assert_eq!(reader.read_prefixed_string_be::<u8>()?, "ok");
+/// # Ok::<_, LengthPrefixedError>(())
+/// ```
+///
+/// [`Vec`]: alloc::vec::Vec
+/// [`String`]: alloc::string::String
+#[cfg(feature = "alloc")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "alloc")))]
+pub trait ReadAllocExt<const N: usize> {
+    /// Read a `P` length, followed by that many `T`s, according to a
+    /// run-time endianness.
+    fn read_prefixed_vec_endian<P, const S: usize, T>(
+        &mut self,
+        endian: Endian,
+    ) -> Result<Vec<T>, LengthPrefixedError>
+    where
+        P: BitEndian<N> + Into<u64>,
+        T: BitEndian<S>;
+    /// Read a `P` length, followed by that many `T`s, with [`Endian::Big`].
+    fn read_prefixed_vec_be<P, const S: usize, T>(&mut self) -> Result<Vec<T>, LengthPrefixedError>
+    where
+        P: BitEndian<N> + Into<u64>,
+        T: BitEndian<S>,
+    {
+        self.read_prefixed_vec_endian::<P, S, T>(Endian::Big)
+    }
+    /// Read a `P` length, followed by that many `T`s, with [`Endian::Little`].
+    fn read_prefixed_vec_le<P, const S: usize, T>(&mut self) -> Result<Vec<T>, LengthPrefixedError>
+    where
+        P: BitEndian<N> + Into<u64>,
+        T: BitEndian<S>,
+    {
+        self.read_prefixed_vec_endian::<P, S, T>(Endian::Little)
+    }
+    /// Read a `P` length, followed by that many `T`s, with [`Endian::Native`].
+    fn read_prefixed_vec_ne<P, const S: usize, T>(&mut self) -> Result<Vec<T>, LengthPrefixedError>
+    where
+        P: BitEndian<N> + Into<u64>,
+        T: BitEndian<S>,
+    {
+        self.read_prefixed_vec_endian::<P, S, T>(Endian::Native)
+    }
+
+    /// Read a `P` length, followed by that many bytes of UTF-8, according to
+    /// a run-time endianness.
+    fn read_prefixed_string_endian<P>(
+        &mut self,
+        endian: Endian,
+    ) -> Result<String, LengthPrefixedError>
+    where
+        P: BitEndian<N> + Into<u64>;
+    /// Read a `P` length, followed by that many bytes of UTF-8, with
+    /// [`Endian::Big`].
+    fn read_prefixed_string_be<P>(&mut self) -> Result<String, LengthPrefixedError>
+    where
+        P: BitEndian<N> + Into<u64>,
+    {
+        self.read_prefixed_string_endian::<P>(Endian::Big)
+    }
+    /// Read a `P` length, followed by that many bytes of UTF-8, with
+    /// [`Endian::Little`].
+    fn read_prefixed_string_le<P>(&mut self) -> Result<String, LengthPrefixedError>
+    where
+        P: BitEndian<N> + Into<u64>,
+    {
+        self.read_prefixed_string_endian::<P>(Endian::Little)
+    }
+    /// Read a `P` length, followed by that many bytes of UTF-8, with
+    /// [`Endian::Native`].
+    fn read_prefixed_string_ne<P>(&mut self) -> Result<String, LengthPrefixedError>
+    where
+        P: BitEndian<N> + Into<u64>,
+    {
+        self.read_prefixed_string_endian::<P>(Endian::Native)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> ReadAllocExt<N> for ByteReader<'_> {
+    fn read_prefixed_vec_endian<P, const S: usize, T>(
+        &mut self,
+        endian: Endian,
+    ) -> Result<Vec<T>, LengthPrefixedError>
+    where
+        P: BitEndian<N> + Into<u64>,
+        T: BitEndian<S>,
+    {
+        let mut reader = *self;
+        let len = reader.read_endian::<P>(endian)?.into();
+        let count = prefixed_len(len, 1, &reader)?;
+        let bytes = reader.read_bytes(prefixed_len(len, S, &reader)?)?;
+        let items = match S {
+            // zero-width items take up no bytes, so only the length is read
+            0 => {
+                let item = || T::from_bytes_endian([0; S], endian);
+                core::iter::repeat_with(item).take(count).collect()
+            }
+            _ => {
+                let (items, _) = bytes.as_chunks::<S>();
+                items
+                    .iter()
+                    .map(|it| T::from_bytes_endian(*it, endian))
+                    .collect()
+            }
+        };
+        *self = reader;
+        Ok(items)
+    }
+
+    fn read_prefixed_string_endian<P>(
+        &mut self,
+        endian: Endian,
+    ) -> Result<String, LengthPrefixedError>
+    where
+        P: BitEndian<N> + Into<u64>,
+    {
+        let mut reader = *self;
+        let len = reader.read_endian::<P>(endian)?.into();
+        let bytes = reader.read_bytes(prefixed_len(len, 1, &reader)?)?;
+        let s = core::str::from_utf8(bytes).map_err(LengthPrefixedError::InvalidUtf8)?;
+        *self = reader;
+        Ok(s.into())
+    }
+}
+
+/// The number of bytes in `len` items of `size` bytes each, or an error if
+/// that can't be addressed.
+#[cfg(feature = "alloc")]
+fn prefixed_len(len: u64, size: usize, reader: &ByteReader<'_>) -> Result<usize, UnexpectedEnd> {
+    usize::try_from(len)
+        .ok()
+        .and_then(|it| it.checked_mul(size))
+        .ok_or(UnexpectedEnd {
+            offset: reader.position(),
+            needed: usize::MAX,
+            remaining: reader.remaining().len(),
+        })
+}
+
+/// Error returned by [`ReadAllocExt`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthPrefixedError {
+    /// The input ended before the prefix or the items.
+    ///
+    /// Lengths too large to address report [`usize::MAX`] bytes needed.
+    UnexpectedEnd(UnexpectedEnd),
+    /// A string wasn't valid UTF-8.
+    InvalidUtf8(core::str::Utf8Error),
+}
+
+#[cfg(feature = "alloc")]
+impl From<UnexpectedEnd> for LengthPrefixedError {
+    fn from(value: UnexpectedEnd) -> Self {
+        Self::UnexpectedEnd(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for LengthPrefixedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LengthPrefixedError::UnexpectedEnd(e) => e.fmt(f),
+            LengthPrefixedError::InvalidUtf8(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthPrefixedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LengthPrefixedError::UnexpectedEnd(e) => Some(e),
+            LengthPrefixedError::InvalidUtf8(e) => Some(e),
+        }
+    }
+}
+
+/// As [`std::io::ErrorKind::UnexpectedEof`] or
+/// [`std::io::ErrorKind::InvalidData`].
+#[cfg(feature = "std")]
+impl From<LengthPrefixedError> for std::io::Error {
+    fn from(value: LengthPrefixedError) -> Self {
+        match value {
+            LengthPrefixedError::UnexpectedEnd(e) => e.into(),
+            e => std::io::Error::new(std::io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+/// Writes values to the front of a byte slice.
+///
+/// Failed writes write nothing.
+#[derive(Debug, PartialEq, Eq, Hash, Default)]
+pub struct ByteWriter<'a> {
+    buf: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> ByteWriter<'a> {
+    /// Write from the start of `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, position: 0 }
+    }
+    /// Write all of `bytes`.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), BufferFull> {
+        let remaining = self.remaining();
+        self.buf
+            .get_mut(self.position..)
+            .and_then(|it| it.get_mut(..bytes.len()))
+            .ok_or(BufferFull {
+                needed: bytes.len(),
+                remaining,
+            })?
+            .copy_from_slice(bytes);
+        self.position += bytes.len();
+        Ok(())
+    }
+    /// The number of bytes written so far.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+    /// The number of bytes which can still be written.
+    pub const fn remaining(&self) -> usize {
+        self.buf.len() - self.position
+    }
+    /// The bytes written so far.
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.position]
+    }
+    /// Return the bytes written so far, borrowed from the underlying slice.
+    pub fn into_written(self) -> &'a mut [u8] {
+        &mut self.buf[..self.position]
+    }
+}
+
+/// Extends [`ByteWriter`] with methods for writing in an endian-dependent way.
+///
+/// See the [module documentation](mod@self) for usage examples.
+pub trait WriteBytesExt<const N: usize> {
+    /// Write according to a run-time endianness.
+    fn write_endian<T: BitEndian<N>>(&mut self, it: T, endian: Endian) -> Result<(), BufferFull>;
+    /// Write with [`Endian::Big`].
+    fn write_be<T: BitEndian<N>>(&mut self, it: T) -> Result<(), BufferFull> {
+        self.write_endian(it, Endian::Big)
+    }
+    /// Write with [`Endian::Little`].
+    fn write_le<T: BitEndian<N>>(&mut self, it: T) -> Result<(), BufferFull> {
+        self.write_endian(it, Endian::Little)
+    }
+    /// Write with [`Endian::Native`].
+    fn write_ne<T: BitEndian<N>>(&mut self, it: T) -> Result<(), BufferFull> {
+        self.write_endian(it, Endian::Native)
+    }
+}
+
+impl<const N: usize> WriteBytesExt<N> for ByteWriter<'_> {
+    fn write_endian<T: BitEndian<N>>(&mut self, it: T, endian: Endian) -> Result<(), BufferFull> {
+        self.write_bytes(&it.to_bytes_endian(endian))
+    }
+}
+
+/// Error returned when writing past the end of a [`ByteWriter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BufferFull {
+    /// The number of bytes the write needed.
+    pub needed: usize,
+    /// The number of bytes which were left.
+    pub remaining: usize,
+}
+
+impl fmt::Display for BufferFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "buffer full: needed {} bytes, but only {} remain",
+            self.needed, self.remaining
+        ))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BufferFull {}
+
+/// As [`std::io::ErrorKind::WriteZero`].
+#[cfg(feature = "std")]
+impl From<BufferFull> for std::io::Error {
+    fn from(value: BufferFull) -> Self {
+        std::io::Error::new(std::io::ErrorKind::WriteZero, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reader() {
+        let mut reader = ByteReader::new(b"\x00\x01GIF\x02\x00");
+        assert_eq!(reader.read_be::<u16>(), Ok(1));
+        assert_eq!(reader.read_bytes(3), Ok(&b"GIF"[..]));
+        assert_eq!(reader.position(), 5);
+        assert_eq!(
+            reader.advance(3),
+            Err(UnexpectedEnd {
+                offset: 5,
+                needed: 3,
+                remaining: 2
+            })
+        );
+        assert_eq!(reader.position(), 5);
+        assert_eq!(reader.read_endian::<i16>(Endian::Little), Ok(2));
+        assert!(reader.is_empty());
+        assert_eq!(
+            std::io::Error::from(reader.read_ne::<u8>().unwrap_err()).kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn length_prefixed() {
+        let mut reader = ByteReader::new(&[0, 2, 0xff, 0xfe, 0x01, 0x00, 9]);
+        let v: Vec<i16> = reader
+            .read_prefixed_vec_endian::<u16, _, _>(Endian::Big)
+            .unwrap();
+        assert_eq!(v, [-2, 0x100]);
+        assert_eq!(
+            reader.read_prefixed_vec_le::<u8, _, u32>(),
+            Err(LengthPrefixedError::UnexpectedEnd(UnexpectedEnd {
+                offset: 7,
+                needed: 36,
+                remaining: 0
+            }))
+        );
+        // failures consume nothing
+        assert_eq!(reader.remaining(), [9]);
+        let v: Vec<[u8; 0]> = reader.read_prefixed_vec_be::<u8, _, _>().unwrap();
+        assert_eq!((v.len(), reader.position()), (9, 7));
+        assert!(
+            ReadAllocExt::<8>::read_prefixed_vec_be::<u64, _, u64>(&mut ByteReader::new(
+                &[0xff; 8]
+            ))
+            .is_err()
+        );
+
+        let mut reader = ByteReader::new(&[3, 0, b'a', b'b', b'c', 1, 0xff]);
+        assert_eq!(reader.read_prefixed_string_le::<u16>().unwrap(), "abc");
+        let e = reader.read_prefixed_string_ne::<u8>().unwrap_err();
+        assert!(matches!(e, LengthPrefixedError::InvalidUtf8(_)));
+        assert_eq!(
+            std::io::Error::from(e).kind(),
+            std::io::ErrorKind::InvalidData
+        );
+        assert_eq!(reader.position(), 5);
+    }
+
+    #[test]
+    fn writer() {
+        let mut buf = [0xff; 5];
+        let mut writer = ByteWriter::new(&mut buf);
+        writer.write_le(-2i16).unwrap();
+        writer.write_bytes(b"ok").unwrap();
+        assert_eq!(writer.position(), 4);
+        assert_eq!(
+            writer.write_endian(1u32, Endian::Big),
+            Err(BufferFull {
+                needed: 4,
+                remaining: 1
+            })
+        );
+        assert_eq!(writer.remaining(), 1);
+        writer.write_ne(0u8).unwrap();
+        assert_eq!(
+            std::io::Error::from(writer.write_be(0u8).unwrap_err()).kind(),
+            std::io::ErrorKind::WriteZero
+        );
+        assert_eq!(writer.into_written(), [0xfe, 0xff, b'o', b'k', 0]);
+    }
+}
@@ -0,0 +1,170 @@
+//! An accessor mirroring JavaScript's [`DataView`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/DataView),
+//! for porting binary parsing code line-for-line.
+//!
+//! ```
+//! use bitendian::data_view::DataView;
+//!
+//! let mut buf = [0u8; 8];
+//! let mut view = DataView::new(&mut buf[..]);
+//! view.set_uint16(0, 0x0102, false);
+//! view.set_float32(4, 1.5, true);
+//! assert_eq!(view.get_uint16(0, true), 0x0201);
+//! assert_eq!(buf, [1, 2, 0, 0, 0, 0, 0xc0, 0x3f]);
+//! ```
+//!
+//! Like JavaScript, multi-byte accessors default to big-endian, i.e
+//! `little_endian` is `false`.
+//! Where JavaScript would throw a `RangeError`, these methods panic.
+
+use crate::BitEndian;
+
+/// See the [module documentation](mod@self).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct DataView<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> DataView<B> {
+    /// View `buffer`, e.g a `&[u8]` or `&mut [u8]`.
+    pub fn new(buffer: B) -> Self {
+        Self { buffer }
+    }
+    /// `byteLength`.
+    pub fn byte_length(&self) -> usize {
+        self.buffer.as_ref().len()
+    }
+    /// Return the viewed buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+    /// `getInt8`.
+    ///
+    /// # Panics
+    /// - If `offset` is out of bounds.
+    pub fn get_int8(&self, offset: usize) -> i8 {
+        self.get(offset, false)
+    }
+    /// `getUint8`.
+    ///
+    /// # Panics
+    /// - If `offset` is out of bounds.
+    pub fn get_uint8(&self, offset: usize) -> u8 {
+        self.get(offset, false)
+    }
+    fn get<const N: usize, T: BitEndian<N>>(&self, offset: usize, little_endian: bool) -> T {
+        let bytes = offset
+            .checked_add(N)
+            .and_then(|end| self.buffer.as_ref().get(offset..end))
+            .unwrap_or_else(|| out_of_bounds(offset, N, self.byte_length()));
+        let bytes = <[u8; N]>::try_from(bytes).expect("slice is of length N");
+        match little_endian {
+            true => T::from_le_bytes(bytes),
+            false => T::from_be_bytes(bytes),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> DataView<B> {
+    /// `setInt8`.
+    ///
+    /// # Panics
+    /// - If `offset` is out of bounds.
+    pub fn set_int8(&mut self, offset: usize, value: i8) {
+        self.set(offset, value, false)
+    }
+    /// `setUint8`.
+    ///
+    /// # Panics
+    /// - If `offset` is out of bounds.
+    pub fn set_uint8(&mut self, offset: usize, value: u8) {
+        self.set(offset, value, false)
+    }
+    fn set<const N: usize, T: BitEndian<N>>(
+        &mut self,
+        offset: usize,
+        value: T,
+        little_endian: bool,
+    ) {
+        let len = self.byte_length();
+        let bytes = offset
+            .checked_add(N)
+            .and_then(|end| self.buffer.as_mut().get_mut(offset..end))
+            .unwrap_or_else(|| out_of_bounds(offset, N, len));
+        bytes.copy_from_slice(&match little_endian {
+            true => value.to_le_bytes(),
+            false => value.to_be_bytes(),
+        })
+    }
+}
+
+macro_rules! accessors {
+    ($($js:literal $ty:ty => $get:ident, $set:ident;)*) => {
+        impl<B: AsRef<[u8]>> DataView<B> {
+            $(
+                #[doc = concat!("`get", $js, "`.")]
+                ///
+                /// # Panics
+                /// - If `offset` is out of bounds.
+                pub fn $get(&self, offset: usize, little_endian: bool) -> $ty {
+                    self.get(offset, little_endian)
+                }
+            )*
+        }
+        impl<B: AsRef<[u8]> + AsMut<[u8]>> DataView<B> {
+            $(
+                #[doc = concat!("`set", $js, "`.")]
+                ///
+                /// # Panics
+                /// - If `offset` is out of bounds.
+                pub fn $set(&mut self, offset: usize, value: $ty, little_endian: bool) {
+                    self.set(offset, value, little_endian)
+                }
+            )*
+        }
+    };
+}
+accessors! {
+    "Int16" i16 => get_int16, set_int16;
+    "Uint16" u16 => get_uint16, set_uint16;
+    "Int32" i32 => get_int32, set_int32;
+    "Uint32" u32 => get_uint32, set_uint32;
+    "BigInt64" i64 => get_big_int64, set_big_int64;
+    "BigUint64" u64 => get_big_uint64, set_big_uint64;
+    "Float32" f32 => get_float32, set_float32;
+    "Float64" f64 => get_float64, set_float64;
+}
+
+#[cold]
+#[track_caller]
+fn out_of_bounds(offset: usize, width: usize, len: usize) -> ! {
+    panic!(
+        "offset {} is out of bounds for a {} byte access of a view of {} bytes",
+        offset, width, len
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accessors() {
+        let view = DataView::new([0xff, 0xfe, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        assert_eq!(view.get_int8(0), -1);
+        assert_eq!(view.get_int16(0, false), -2);
+        assert_eq!(view.get_uint32(2, false), 0x00010203);
+        assert_eq!(view.get_big_uint64(1, true), 0x0605_0403_0201_00fe);
+        assert_eq!(view.byte_length(), 9);
+
+        let mut view = DataView::new(vec![0; 8]);
+        view.set_float64(0, -2.0, false);
+        assert_eq!(view.get_float64(0, false), -2.0);
+        assert_eq!(view.into_inner()[0], 0xc0);
+    }
+
+    #[test]
+    #[should_panic = "offset 7 is out of bounds for a 2 byte access of a view of 8 bytes"]
+    fn out_of_bounds() {
+        DataView::new([0; 8]).get_uint16(7, false);
+    }
+}
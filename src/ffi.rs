@@ -0,0 +1,140 @@
+//! A C ABI for the core conversions, so that C and C++ code can share an
+//! implementation with Rust.
+//!
+//! Every function is `#[no_mangle]`, and named
+//! `bitendian_{load,store}_{type}_{be,le}[_slice]`, for `type` in `u16`,
+//! `i16`, `u32`, `i32`, `u64`, `i64`, `f32` and `f64`.
+//! For example:
+//! ```c
+//! uint32_t bitendian_load_u32_be(const uint8_t *src);
+//! void bitendian_store_u32_be(uint8_t *dst, uint32_t value);
+//! void bitendian_load_u32_be_slice(const uint8_t *src, uint32_t *dst, size_t count);
+//! void bitendian_store_u32_be_slice(uint8_t *dst, const uint32_t *src, size_t count);
+//! ```
+//!
+//! To build a library for linking, use e.g
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or `staticlib`).
+
+use crate::BitEndian;
+use core::slice;
+
+macro_rules! ffi {
+    ($(
+        $width:literal $ty:ty, $from:ident, $to:ident
+            => $load:ident, $store:ident, $load_slice:ident, $store_slice:ident;
+    )*) => {
+        $(
+            #[doc = concat!("Load a `", stringify!($ty), "` from `src`, as in [`BitEndian::", stringify!($from), "`].")]
+            ///
+            /// # Safety
+            #[doc = concat!("- `src` must be valid for reads of ", stringify!($width), " bytes.")]
+            #[no_mangle]
+            pub unsafe extern "C" fn $load(src: *const u8) -> $ty {
+                // SAFETY: upheld by caller, and arrays of bytes have no alignment
+                let bytes = unsafe { src.cast::<[u8; $width]>().read_unaligned() };
+                <$ty as BitEndian<$width>>::$from(bytes)
+            }
+
+            #[doc = concat!("Store a `", stringify!($ty), "` to `dst`, as in [`BitEndian::", stringify!($to), "`].")]
+            ///
+            /// # Safety
+            #[doc = concat!("- `dst` must be valid for writes of ", stringify!($width), " bytes.")]
+            #[no_mangle]
+            pub unsafe extern "C" fn $store(dst: *mut u8, value: $ty) {
+                let bytes = <$ty as BitEndian<$width>>::$to(value);
+                // SAFETY: upheld by caller, and arrays of bytes have no alignment
+                unsafe { dst.cast::<[u8; $width]>().write_unaligned(bytes) }
+            }
+
+            #[doc = concat!("Load `count` `", stringify!($ty), "`s from `src` into `dst`.")]
+            ///
+            /// # Safety
+            /// If `count` is non-zero:
+            #[doc = concat!("- `src` must be valid for reads of `count * ", stringify!($width), "` bytes.")]
+            #[doc = concat!("- `dst` must be aligned, and valid for writes of `count` `", stringify!($ty), "`s.")]
+            /// - The two must not overlap.
+            #[no_mangle]
+            pub unsafe extern "C" fn $load_slice(src: *const u8, dst: *mut $ty, count: usize) {
+                if count == 0 {
+                    return;
+                }
+                // SAFETY: upheld by caller
+                let (src, dst) = unsafe {
+                    (
+                        slice::from_raw_parts(src, count * $width),
+                        slice::from_raw_parts_mut(dst, count),
+                    )
+                };
+                for (dst, src) in dst.iter_mut().zip(src.as_chunks().0) {
+                    *dst = <$ty as BitEndian<$width>>::$from(*src);
+                }
+            }
+
+            #[doc = concat!("Store `count` `", stringify!($ty), "`s from `src` to `dst`.")]
+            ///
+            /// # Safety
+            /// If `count` is non-zero:
+            #[doc = concat!("- `dst` must be valid for writes of `count * ", stringify!($width), "` bytes.")]
+            #[doc = concat!("- `src` must be aligned, and valid for reads of `count` `", stringify!($ty), "`s.")]
+            /// - The two must not overlap.
+            #[no_mangle]
+            pub unsafe extern "C" fn $store_slice(dst: *mut u8, src: *const $ty, count: usize) {
+                if count == 0 {
+                    return;
+                }
+                // SAFETY: upheld by caller
+                let (dst, src) = unsafe {
+                    (
+                        slice::from_raw_parts_mut(dst, count * $width),
+                        slice::from_raw_parts(src, count),
+                    )
+                };
+                for (dst, src) in dst.as_chunks_mut().0.iter_mut().zip(src) {
+                    *dst = <$ty as BitEndian<$width>>::$to(*src);
+                }
+            }
+        )*
+    };
+}
+
+ffi! {
+    2 u16, from_be_bytes, to_be_bytes => bitendian_load_u16_be, bitendian_store_u16_be, bitendian_load_u16_be_slice, bitendian_store_u16_be_slice;
+    2 u16, from_le_bytes, to_le_bytes => bitendian_load_u16_le, bitendian_store_u16_le, bitendian_load_u16_le_slice, bitendian_store_u16_le_slice;
+    2 i16, from_be_bytes, to_be_bytes => bitendian_load_i16_be, bitendian_store_i16_be, bitendian_load_i16_be_slice, bitendian_store_i16_be_slice;
+    2 i16, from_le_bytes, to_le_bytes => bitendian_load_i16_le, bitendian_store_i16_le, bitendian_load_i16_le_slice, bitendian_store_i16_le_slice;
+    4 u32, from_be_bytes, to_be_bytes => bitendian_load_u32_be, bitendian_store_u32_be, bitendian_load_u32_be_slice, bitendian_store_u32_be_slice;
+    4 u32, from_le_bytes, to_le_bytes => bitendian_load_u32_le, bitendian_store_u32_le, bitendian_load_u32_le_slice, bitendian_store_u32_le_slice;
+    4 i32, from_be_bytes, to_be_bytes => bitendian_load_i32_be, bitendian_store_i32_be, bitendian_load_i32_be_slice, bitendian_store_i32_be_slice;
+    4 i32, from_le_bytes, to_le_bytes => bitendian_load_i32_le, bitendian_store_i32_le, bitendian_load_i32_le_slice, bitendian_store_i32_le_slice;
+    8 u64, from_be_bytes, to_be_bytes => bitendian_load_u64_be, bitendian_store_u64_be, bitendian_load_u64_be_slice, bitendian_store_u64_be_slice;
+    8 u64, from_le_bytes, to_le_bytes => bitendian_load_u64_le, bitendian_store_u64_le, bitendian_load_u64_le_slice, bitendian_store_u64_le_slice;
+    8 i64, from_be_bytes, to_be_bytes => bitendian_load_i64_be, bitendian_store_i64_be, bitendian_load_i64_be_slice, bitendian_store_i64_be_slice;
+    8 i64, from_le_bytes, to_le_bytes => bitendian_load_i64_le, bitendian_store_i64_le, bitendian_load_i64_le_slice, bitendian_store_i64_le_slice;
+    4 f32, from_be_bytes, to_be_bytes => bitendian_load_f32_be, bitendian_store_f32_be, bitendian_load_f32_be_slice, bitendian_store_f32_be_slice;
+    4 f32, from_le_bytes, to_le_bytes => bitendian_load_f32_le, bitendian_store_f32_le, bitendian_load_f32_le_slice, bitendian_store_f32_le_slice;
+    8 f64, from_be_bytes, to_be_bytes => bitendian_load_f64_be, bitendian_store_f64_be, bitendian_load_f64_be_slice, bitendian_store_f64_be_slice;
+    8 f64, from_le_bytes, to_le_bytes => bitendian_load_f64_le, bitendian_store_f64_le, bitendian_load_f64_le_slice, bitendian_store_f64_le_slice;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = [0u8; 9];
+        unsafe {
+            bitendian_store_u32_be(buf[1..].as_mut_ptr(), 0x01020304);
+            assert_eq!(buf[..5], [0, 1, 2, 3, 4]);
+            assert_eq!(bitendian_load_u32_le(buf[1..].as_ptr()), 0x04030201);
+
+            bitendian_store_u16_le_slice(buf[1..].as_mut_ptr(), [1u16, 2, 3, 4].as_ptr(), 4);
+            assert_eq!(buf, [0, 1, 0, 2, 0, 3, 0, 4, 0]);
+            let mut values = [0i16; 4];
+            bitendian_load_i16_be_slice(buf.as_ptr(), values.as_mut_ptr(), 4);
+            assert_eq!(values, [1, 2, 3, 4]);
+
+            bitendian_load_f64_be_slice(core::ptr::null(), core::ptr::null_mut(), 0);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::BitEndian;
+use fixed::types::extra::{LeEqU128, LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+
+macro_rules! fixed {
+    ($($width:literal { $($ty:ident),* $(,)? } $bound:ident),* $(,)?) => {
+        $($(
+            /// As the underlying integer.
+            impl<Frac: $bound> BitEndian<$width> for fixed::$ty<Frac> {
+                fn to_le_bytes(self) -> [u8; $width] {
+                    fixed::$ty::to_le_bytes(self)
+                }
+                fn to_be_bytes(self) -> [u8; $width] {
+                    fixed::$ty::to_be_bytes(self)
+                }
+                fn to_ne_bytes(self) -> [u8; $width] {
+                    fixed::$ty::to_ne_bytes(self)
+                }
+
+                fn from_le_bytes(bytes: [u8; $width]) -> Self {
+                    fixed::$ty::from_le_bytes(bytes)
+                }
+                fn from_be_bytes(bytes: [u8; $width]) -> Self {
+                    fixed::$ty::from_be_bytes(bytes)
+                }
+                fn from_ne_bytes(bytes: [u8; $width]) -> Self {
+                    fixed::$ty::from_ne_bytes(bytes)
+                }
+            }
+
+            #[cfg(feature = "std")]
+            impl<Frac: $bound> crate::io::Decode for fixed::$ty<Frac> {
+                fn decode<R: std::io::Read + ?Sized>(
+                    reader: &mut R,
+                    endian: crate::Endian,
+                ) -> std::io::Result<Self> {
+                    let mut bytes = [0; $width];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(Self::from_bytes_endian(bytes, endian))
+                }
+            }
+
+            #[cfg(feature = "std")]
+            impl<Frac: $bound> crate::io::Encode for fixed::$ty<Frac> {
+                fn encode<W: std::io::Write + ?Sized>(
+                    &self,
+                    writer: &mut W,
+                    endian: crate::Endian,
+                ) -> std::io::Result<()> {
+                    writer.write_all(&BitEndian::to_bytes_endian(*self, endian))
+                }
+            }
+        )*)*
+    };
+}
+fixed!(
+    1 { FixedU8, FixedI8 } LeEqU8,
+    2 { FixedU16, FixedI16 } LeEqU16,
+    4 { FixedU32, FixedI32 } LeEqU32,
+    8 { FixedU64, FixedI64 } LeEqU64,
+    16 { FixedU128, FixedI128 } LeEqU128,
+);
@@ -0,0 +1,169 @@
+//! Fortran sequential unformatted files.
+//!
+//! Each record is framed by a length marker before and after it.
+//! The width of the markers (usually four bytes, sometimes eight) and their
+//! endianness depend on the compiler and platform which wrote the file.
+//!
+//! ```
+//! use bitendian::{fortran::{self, Marker}, Endian};
+//!
+//! # fn doit() -> std::io::Result<()> {
+//! let mut file = vec![];
+//! fortran::write_record(&mut file, b"hello", Marker::U32, Endian::Little)?;
+//! assert_eq!(file, b"\x05\0\0\0hello\x05\0\0\0");
+//!
+//! let mut file = file.as_slice();
+//! let record = fortran::read_record(&mut file, Marker::U32, Endian::Little)?;
+//! assert_eq!(record.as_deref(), Some(&b"hello"[..]));
+//! assert_eq!(fortran::read_record(&mut file, Marker::U32, Endian::Little)?, None);
+//! # Ok(())
+//! # }
+//! # doit().unwrap()
+//! ```
+//!
+//! Records split into sub-records (as `gfortran` does for records of 2GiB
+//! or more) are not supported.
+
+use crate::{
+    io::{ReadExt as _, WriteExt as _},
+    Endian,
+};
+use std::io::{self, Read as _};
+
+/// The width of record length markers.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Marker {
+    /// Four byte markers, used by most compilers.
+    #[default]
+    U32,
+    /// Eight byte markers.
+    U64,
+}
+
+/// Read a single record.
+///
+/// Returns [`None`] if the reader is at end-of-file before the record starts.
+///
+/// A trailing marker which doesn't match the leading marker is reported as
+/// [`io::ErrorKind::InvalidData`].
+pub fn read_record(
+    reader: &mut (impl io::Read + ?Sized),
+    marker: Marker,
+    endian: Endian,
+) -> io::Result<Option<Vec<u8>>> {
+    let Some(len) = read_leading_marker(reader, marker, endian)? else {
+        return Ok(None);
+    };
+    // don't trust the marker for the allocation size
+    let mut record = Vec::new();
+    (&mut *reader).take(len).read_to_end(&mut record)?;
+    if record.len() as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    match read_marker(&mut *reader, marker, endian)? == len {
+        true => Ok(Some(record)),
+        false => Err(invalid_data(
+            "trailing record marker does not match leading marker",
+        )),
+    }
+}
+
+/// Write a single record.
+///
+/// Records too large for the marker are reported as
+/// [`io::ErrorKind::InvalidInput`].
+pub fn write_record(
+    writer: &mut (impl io::Write + ?Sized),
+    record: &[u8],
+    marker: Marker,
+    endian: Endian,
+) -> io::Result<()> {
+    write_marker(&mut *writer, record.len(), marker, endian)?;
+    writer.write_all(record)?;
+    write_marker(&mut *writer, record.len(), marker, endian)
+}
+
+fn read_leading_marker(
+    reader: &mut (impl io::Read + ?Sized),
+    marker: Marker,
+    endian: Endian,
+) -> io::Result<Option<u64>> {
+    let mut buf = [0u8; 8];
+    let buf = match marker {
+        Marker::U32 => &mut buf[..4],
+        Marker::U64 => &mut buf[..],
+    };
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    read_marker(&*buf, marker, endian).map(Some)
+}
+
+fn read_marker(mut reader: impl io::Read, marker: Marker, endian: Endian) -> io::Result<u64> {
+    match marker {
+        Marker::U32 => reader.read_endian::<u32>(endian).map(u64::from),
+        Marker::U64 => reader.read_endian(endian),
+    }
+}
+
+fn write_marker(
+    mut writer: impl io::Write,
+    len: usize,
+    marker: Marker,
+    endian: Endian,
+) -> io::Result<()> {
+    let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "record too large for marker");
+    match marker {
+        Marker::U32 => writer.write_endian(u32::try_from(len).map_err(|_| too_large())?, endian),
+        Marker::U64 => writer.write_endian(u64::try_from(len).map_err(|_| too_large())?, endian),
+    }
+}
+
+fn invalid_data(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for marker in [Marker::U32, Marker::U64] {
+            for endian in [Endian::Big, Endian::Little] {
+                let mut file = vec![];
+                write_record(&mut file, b"", marker, endian).unwrap();
+                write_record(&mut file, &[1, 2, 3], marker, endian).unwrap();
+                let mut file = file.as_slice();
+                assert_eq!(
+                    read_record(&mut file, marker, endian).unwrap(),
+                    Some(vec![])
+                );
+                assert_eq!(
+                    read_record(&mut file, marker, endian).unwrap(),
+                    Some(vec![1, 2, 3])
+                );
+                assert_eq!(read_record(&mut file, marker, endian).unwrap(), None);
+            }
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        let kind = |mut file: &[u8]| {
+            read_record(&mut file, Marker::U32, Endian::Big)
+                .unwrap_err()
+                .kind()
+        };
+        assert_eq!(kind(b"\0\0\0\x01a\0\0\0\x02"), io::ErrorKind::InvalidData);
+        assert_eq!(kind(b"\0\0\0\x01a"), io::ErrorKind::UnexpectedEof);
+        assert_eq!(kind(b"\0\0"), io::ErrorKind::UnexpectedEof);
+    }
+}
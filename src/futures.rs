@@ -13,7 +13,8 @@
 //! # futures::executor::block_on(doit()).unwrap()
 //! ```
 
-use crate::{BitEndian, Endian};
+use crate::{BitEndian, Endian, TryBitEndian};
+use futures_core::Stream;
 use futures_io::{AsyncRead, AsyncWrite};
 use pin_project::pin_project;
 use std::{
@@ -30,9 +31,9 @@ pub struct ReadEndian<const N: usize, R, T> {
     #[pin]
     reader: R,
     buffer: [u8; N],
-    progress: usize,
+    progress: u32,
     endian: Endian,
-    _out: PhantomData<T>,
+    _out: PhantomData<fn() -> T>,
 }
 
 impl<const N: usize, R, T> Future for ReadEndian<N, R, T>
@@ -42,24 +43,58 @@ where
 {
     type Output = io::Result<T>;
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let mut this = self.as_mut().project();
-        loop {
-            let buf = &mut this.buffer[*this.progress..];
-            let progress = ready!(this.reader.as_mut().poll_read(cx, buf))?;
-            if progress == 0 {
-                return Poll::Ready(Err(io::Error::from(io::ErrorKind::UnexpectedEof)));
-            }
-            *this.progress += progress;
-            if *this.progress >= N {
-                return Poll::Ready(Ok(T::from_bytes_endian(self.buffer, self.endian)));
-            }
-        }
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        ready!(poll_read_array(this.reader, cx, this.buffer, this.progress))?;
+        Poll::Ready(Ok(T::from_bytes_endian(*this.buffer, *this.endian)))
     }
 }
 
 impl<const N: usize, R, T> ReadEndian<N, R, T> {
     fn new(reader: R, endian: Endian) -> Self {
+        assert_fits::<N>();
+        Self {
+            reader,
+            buffer: [0u8; N],
+            progress: 0,
+            endian,
+            _out: PhantomData,
+        }
+    }
+}
+
+/// Future for [`AsyncReadExt`]'s `try_` methods, see that trait for more.
+#[pin_project]
+pub struct TryReadEndian<const N: usize, R, T> {
+    #[pin]
+    reader: R,
+    buffer: [u8; N],
+    progress: u32,
+    endian: Endian,
+    _out: PhantomData<fn() -> T>,
+}
+
+impl<const N: usize, R, T> Future for TryReadEndian<N, R, T>
+where
+    R: AsyncRead,
+    T: TryBitEndian<N>,
+    T::Error: std::error::Error + Send + Sync + 'static,
+{
+    type Output = io::Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        ready!(poll_read_array(this.reader, cx, this.buffer, this.progress))?;
+        Poll::Ready(
+            T::try_from_bytes_endian(*this.buffer, *this.endian)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        )
+    }
+}
+
+impl<const N: usize, R, T> TryReadEndian<N, R, T> {
+    fn new(reader: R, endian: Endian) -> Self {
+        assert_fits::<N>();
         Self {
             reader,
             buffer: [0u8; N],
@@ -70,6 +105,95 @@ impl<const N: usize, R, T> ReadEndian<N, R, T> {
     }
 }
 
+/// Future for [`AsyncReadExt`]'s `_owned` methods, see that trait for more.
+pub struct ReadEndianOwned<const N: usize, R, T> {
+    reader: Option<R>,
+    buffer: [u8; N],
+    progress: u32,
+    endian: Endian,
+    _out: PhantomData<fn() -> T>,
+}
+
+impl<const N: usize, R, T> Future for ReadEndianOwned<N, R, T>
+where
+    R: AsyncRead + Unpin,
+    T: BitEndian<N>,
+{
+    type Output = (R, io::Result<T>);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let reader = this.reader.as_mut().expect("polled after completion");
+        let res = ready!(poll_read_array(
+            Pin::new(reader),
+            cx,
+            &mut this.buffer,
+            &mut this.progress
+        ));
+        let reader = this.reader.take().expect("polled after completion");
+        let res = res.map(|()| T::from_bytes_endian(this.buffer, this.endian));
+        Poll::Ready((reader, res))
+    }
+}
+
+impl<const N: usize, R, T> ReadEndianOwned<N, R, T> {
+    fn new(reader: R, endian: Endian) -> Self {
+        assert_fits::<N>();
+        Self {
+            reader: Some(reader),
+            buffer: [0u8; N],
+            progress: 0,
+            endian,
+            _out: PhantomData,
+        }
+    }
+}
+
+/// Future for [`AsyncReadExt`]'s `read_array` methods, see that trait for
+/// more.
+#[pin_project]
+pub struct ReadArray<const N: usize, R, T, const M: usize> {
+    #[pin]
+    reader: R,
+    buffer: [[u8; N]; M],
+    progress: u32,
+    endian: Endian,
+    _out: PhantomData<fn() -> T>,
+}
+
+impl<const N: usize, R, T, const M: usize> Future for ReadArray<N, R, T, M>
+where
+    R: AsyncRead,
+    T: BitEndian<N>,
+{
+    type Output = io::Result<[T; M]>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        ready!(poll_read_array(
+            this.reader,
+            cx,
+            this.buffer.as_flattened_mut(),
+            this.progress
+        ))?;
+        let endian = *this.endian;
+        Poll::Ready(Ok(this.buffer.map(|it| T::from_bytes_endian(it, endian))))
+    }
+}
+
+impl<const N: usize, R, T, const M: usize> ReadArray<N, R, T, M> {
+    fn new(reader: R, endian: Endian) -> Self {
+        const { assert!(N * M <= u32::MAX as usize, "buffer too large for future") }
+        Self {
+            reader,
+            buffer: [[0u8; N]; M],
+            progress: 0,
+            endian,
+            _out: PhantomData,
+        }
+    }
+}
+
 /// Extends [`futures::io::AsyncRead`](https://docs.rs/futures/0.3/futures/io/trait.AsyncRead.html)
 /// with methods for reading in an endian-dependant way.
 ///
@@ -91,6 +215,90 @@ pub trait AsyncReadExt<const N: usize>: AsyncRead + Unpin {
     fn read_ne<T: BitEndian<N>>(&mut self) -> ReadEndian<N, &mut Self, T> {
         self.read_endian(Endian::Native)
     }
+    /// Read `M` consecutive values according to a run-time endianness.
+    fn read_array_endian<T: BitEndian<N>, const M: usize>(
+        &mut self,
+        endian: Endian,
+    ) -> ReadArray<N, &mut Self, T, M> {
+        assert_future::<io::Result<[T; M]>, _>(ReadArray::new(self, endian))
+    }
+    /// Read `M` values with [`Endian::Big`].
+    fn read_array_be<T: BitEndian<N>, const M: usize>(&mut self) -> ReadArray<N, &mut Self, T, M> {
+        self.read_array_endian(Endian::Big)
+    }
+    /// Read `M` values with [`Endian::Little`].
+    fn read_array_le<T: BitEndian<N>, const M: usize>(&mut self) -> ReadArray<N, &mut Self, T, M> {
+        self.read_array_endian(Endian::Little)
+    }
+    /// Read `M` values with [`Endian::Native`].
+    fn read_array_ne<T: BitEndian<N>, const M: usize>(&mut self) -> ReadArray<N, &mut Self, T, M> {
+        self.read_array_endian(Endian::Native)
+    }
+    /// Read a [`TryBitEndian`] type according to a run-time endianness.
+    ///
+    /// Invalid values are reported as [`io::ErrorKind::InvalidData`].
+    fn try_read_endian<T>(&mut self, endian: Endian) -> TryReadEndian<N, &mut Self, T>
+    where
+        T: TryBitEndian<N>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        assert_future::<io::Result<T>, _>(TryReadEndian::new(self, endian))
+    }
+    /// Try to read with [`Endian::Big`].
+    fn try_read_be<T>(&mut self) -> TryReadEndian<N, &mut Self, T>
+    where
+        T: TryBitEndian<N>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        self.try_read_endian(Endian::Big)
+    }
+    /// Try to read with [`Endian::Little`].
+    fn try_read_le<T>(&mut self) -> TryReadEndian<N, &mut Self, T>
+    where
+        T: TryBitEndian<N>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        self.try_read_endian(Endian::Little)
+    }
+    /// Try to read with [`Endian::Native`].
+    fn try_read_ne<T>(&mut self) -> TryReadEndian<N, &mut Self, T>
+    where
+        T: TryBitEndian<N>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        self.try_read_endian(Endian::Native)
+    }
+    /// Read according to a run-time endianness, taking the reader by value,
+    /// and returning it along with the result.
+    ///
+    /// The returned future borrows nothing, so may be spawned or stored.
+    fn read_endian_owned<T: BitEndian<N>>(self, endian: Endian) -> ReadEndianOwned<N, Self, T>
+    where
+        Self: Sized,
+    {
+        assert_future::<(Self, io::Result<T>), _>(ReadEndianOwned::new(self, endian))
+    }
+    /// Read with [`Endian::Big`], as in [`Self::read_endian_owned`].
+    fn read_be_owned<T: BitEndian<N>>(self) -> ReadEndianOwned<N, Self, T>
+    where
+        Self: Sized,
+    {
+        self.read_endian_owned(Endian::Big)
+    }
+    /// Read with [`Endian::Little`], as in [`Self::read_endian_owned`].
+    fn read_le_owned<T: BitEndian<N>>(self) -> ReadEndianOwned<N, Self, T>
+    where
+        Self: Sized,
+    {
+        self.read_endian_owned(Endian::Little)
+    }
+    /// Read with [`Endian::Native`], as in [`Self::read_endian_owned`].
+    fn read_ne_owned<T: BitEndian<N>>(self) -> ReadEndianOwned<N, Self, T>
+    where
+        Self: Sized,
+    {
+        self.read_endian_owned(Endian::Native)
+    }
 }
 impl<const N: usize, R> AsyncReadExt<N> for R where R: AsyncRead + Unpin {}
 
@@ -100,7 +308,7 @@ pub struct WriteArray<const N: usize, W> {
     #[pin]
     writer: W,
     buffer: [u8; N],
-    progress: usize,
+    progress: u32,
 }
 
 impl<const N: usize, W> Future for WriteArray<N, W>
@@ -109,22 +317,15 @@ where
 {
     type Output = io::Result<()>;
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let mut this = self.as_mut().project();
-        loop {
-            *this.progress += ready!(this
-                .writer
-                .as_mut()
-                .poll_write(cx, &this.buffer[*this.progress..]))?;
-            if *this.progress >= N {
-                return Poll::Ready(Ok(()));
-            }
-        }
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        poll_write_array(this.writer, cx, this.buffer, this.progress)
     }
 }
 
 impl<const N: usize, W> WriteArray<N, W> {
     fn new(writer: W, it: impl BitEndian<N>, endian: Endian) -> Self {
+        assert_fits::<N>();
         Self {
             writer,
             buffer: it.to_bytes_endian(endian),
@@ -133,6 +334,44 @@ impl<const N: usize, W> WriteArray<N, W> {
     }
 }
 
+/// Future for [`AsyncWriteExt`]'s `_owned` methods, see that trait for more.
+pub struct WriteArrayOwned<const N: usize, W> {
+    writer: Option<W>,
+    buffer: [u8; N],
+    progress: u32,
+}
+
+impl<const N: usize, W> Future for WriteArrayOwned<N, W>
+where
+    W: AsyncWrite + Unpin,
+{
+    type Output = (W, io::Result<()>);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let writer = this.writer.as_mut().expect("polled after completion");
+        let res = ready!(poll_write_array(
+            Pin::new(writer),
+            cx,
+            &this.buffer,
+            &mut this.progress
+        ));
+        let writer = this.writer.take().expect("polled after completion");
+        Poll::Ready((writer, res))
+    }
+}
+
+impl<const N: usize, W> WriteArrayOwned<N, W> {
+    fn new(writer: W, it: impl BitEndian<N>, endian: Endian) -> Self {
+        assert_fits::<N>();
+        Self {
+            writer: Some(writer),
+            buffer: it.to_bytes_endian(endian),
+            progress: 0,
+        }
+    }
+}
+
 /// Extends [`futures::io::AsyncWrite`](https://docs.rs/futures/0.3/futures/io/trait.AsyncWrite.html)
 /// with methods for writing in an endian-dependent way.
 ///
@@ -154,9 +393,228 @@ pub trait AsyncWriteExt<const N: usize>: AsyncWrite + Unpin {
     fn write_ne<T: BitEndian<N>>(&mut self, it: T) -> WriteArray<N, &mut Self> {
         self.write_endian(it, Endian::Native)
     }
+    /// Write according to a run-time endianness, taking the writer by value,
+    /// and returning it along with the result.
+    ///
+    /// The returned future borrows nothing, so may be spawned or stored.
+    fn write_endian_owned<T: BitEndian<N>>(self, it: T, endian: Endian) -> WriteArrayOwned<N, Self>
+    where
+        Self: Sized,
+    {
+        assert_future::<(Self, io::Result<()>), _>(WriteArrayOwned::new(self, it, endian))
+    }
+    /// Write with [`Endian::Big`], as in [`Self::write_endian_owned`].
+    fn write_be_owned<T: BitEndian<N>>(self, it: T) -> WriteArrayOwned<N, Self>
+    where
+        Self: Sized,
+    {
+        self.write_endian_owned(it, Endian::Big)
+    }
+    /// Write with [`Endian::Little`], as in [`Self::write_endian_owned`].
+    fn write_le_owned<T: BitEndian<N>>(self, it: T) -> WriteArrayOwned<N, Self>
+    where
+        Self: Sized,
+    {
+        self.write_endian_owned(it, Endian::Little)
+    }
+    /// Write with [`Endian::Native`], as in [`Self::write_endian_owned`].
+    fn write_ne_owned<T: BitEndian<N>>(self, it: T) -> WriteArrayOwned<N, Self>
+    where
+        Self: Sized,
+    {
+        self.write_endian_owned(it, Endian::Native)
+    }
 }
 impl<const N: usize, W> AsyncWriteExt<N> for W where W: AsyncWrite + Unpin {}
 
+/// Write [`NEGOTIATION_WORD`](crate::io::NEGOTIATION_WORD) in native byte
+/// order, as in [`crate::io::write_negotiation`].
+pub async fn write_negotiation<W: AsyncWrite + Unpin>(writer: &mut W) -> io::Result<()> {
+    writer.write_ne(crate::io::NEGOTIATION_WORD).await
+}
+
+/// Read [`NEGOTIATION_WORD`](crate::io::NEGOTIATION_WORD), returning the byte
+/// order it was written in, as in [`crate::io::detect_negotiation`].
+pub async fn detect_negotiation<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Endian> {
+    let word: [u8; 4] = reader.read_ne::<u32>().await?.to_ne_bytes();
+    crate::io::negotiated(word)
+}
+
+/// Extends [`AsyncRead`] with methods for reading
+/// [`Decode`](crate::io::Decode) types.
+///
+/// This is the asynchronous counterpart of
+/// [`DecodeExt::decode_stream_endian`](crate::io::DecodeExt::decode_stream_endian).
+pub trait AsyncDecodeExt: AsyncRead + Unpin {
+    /// Lazily decode values until the end of the reader, according to a
+    /// run-time endianness.
+    ///
+    /// The stream ends cleanly if the reader ends between values.
+    /// If it ends partway through a value, the stream yields
+    /// [`io::ErrorKind::UnexpectedEof`], and then ends.
+    /// Other errors are yielded in the same way.
+    ///
+    /// Input is read in blocks, so the reader may be read past the last
+    /// value yielded.
+    ///
+    /// ```
+    /// use bitendian::futures::AsyncDecodeExt as _;
+    /// use futures::TryStreamExt as _;
+    ///
+    /// # async fn doit() -> std::io::Result<()> {
+    /// let mut r = [0, 1, 2, 0, 3, 4].as_slice();
+    /// let records: Vec<_> = r.decode_stream_be::<(u16, u8)>().try_collect().await?;
+    /// assert_eq!(records, [(1, 2), (3, 4)]);
+    /// # Ok(())
+    /// # }
+    /// # futures::executor::block_on(doit()).unwrap()
+    /// ```
+    fn decode_stream_endian<T: crate::io::Decode>(
+        &mut self,
+        endian: Endian,
+    ) -> DecodeStream<&mut Self, T> {
+        DecodeStream {
+            reader: self,
+            buffer: Vec::new(),
+            start: 0,
+            endian,
+            eof: false,
+            done: false,
+            _out: PhantomData,
+        }
+    }
+    /// Decode values with [`Endian::Big`], as in
+    /// [`Self::decode_stream_endian`].
+    fn decode_stream_be<T: crate::io::Decode>(&mut self) -> DecodeStream<&mut Self, T> {
+        self.decode_stream_endian(Endian::Big)
+    }
+    /// Decode values with [`Endian::Little`], as in
+    /// [`Self::decode_stream_endian`].
+    fn decode_stream_le<T: crate::io::Decode>(&mut self) -> DecodeStream<&mut Self, T> {
+        self.decode_stream_endian(Endian::Little)
+    }
+    /// Decode values with [`Endian::Native`], as in
+    /// [`Self::decode_stream_endian`].
+    fn decode_stream_ne<T: crate::io::Decode>(&mut self) -> DecodeStream<&mut Self, T> {
+        self.decode_stream_endian(Endian::Native)
+    }
+}
+impl<R> AsyncDecodeExt for R where R: AsyncRead + Unpin {}
+
+/// How much to read at once when decoding a stream.
+const DECODE_BLOCK: usize = 4096;
+
+/// Stream for [`AsyncDecodeExt`], see that trait for more.
+#[pin_project]
+pub struct DecodeStream<R, T> {
+    #[pin]
+    reader: R,
+    buffer: Vec<u8>,
+    /// Where the next value starts in `buffer`.
+    start: usize,
+    endian: Endian,
+    eof: bool,
+    done: bool,
+    _out: PhantomData<fn() -> T>,
+}
+
+impl<R, T> Stream for DecodeStream<R, T>
+where
+    R: AsyncRead,
+    T: crate::io::Decode,
+{
+    type Item = io::Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if *this.done {
+                return Poll::Ready(None);
+            }
+            if *this.start < this.buffer.len() || *this.eof {
+                let mut rest = &this.buffer[*this.start..];
+                match T::decode(&mut rest, *this.endian) {
+                    Ok(it) => {
+                        *this.start = this.buffer.len() - rest.len();
+                        return Poll::Ready(Some(Ok(it)));
+                    }
+                    // wait for the rest of the value
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof && !*this.eof => {}
+                    Err(e) => {
+                        *this.done = true;
+                        return Poll::Ready(match e.kind() {
+                            // the reader ended between values
+                            io::ErrorKind::UnexpectedEof if *this.start == this.buffer.len() => {
+                                None
+                            }
+                            _ => Some(Err(e)),
+                        });
+                    }
+                }
+            }
+            // only the partial value is kept, and at least as much again is
+            // read, so retrying a large value is amortized
+            this.buffer.drain(..*this.start);
+            *this.start = 0;
+            let len = this.buffer.len();
+            this.buffer.resize(len + DECODE_BLOCK.max(len), 0);
+            let polled = this.reader.as_mut().poll_read(cx, &mut this.buffer[len..]);
+            let read = match polled {
+                Poll::Ready(Ok(read)) => read,
+                _ => 0,
+            };
+            this.buffer.truncate(len + read);
+            match ready!(polled) {
+                Ok(0) => *this.eof = true,
+                Ok(_) => {}
+                Err(e) => {
+                    *this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
+}
+
+fn poll_read_array<R: AsyncRead + ?Sized>(
+    mut reader: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    buffer: &mut [u8],
+    progress: &mut u32,
+) -> Poll<io::Result<()>> {
+    while (*progress as usize) < buffer.len() {
+        let buf = &mut buffer[*progress as usize..];
+        let read = ready!(reader.as_mut().poll_read(cx, buf))?;
+        if read == 0 {
+            return Poll::Ready(Err(io::Error::from(io::ErrorKind::UnexpectedEof)));
+        }
+        // can't overflow, since `read` is at most `buffer.len()`
+        *progress += read as u32;
+    }
+    Poll::Ready(Ok(()))
+}
+
+fn poll_write_array<W: AsyncWrite + ?Sized>(
+    mut writer: Pin<&mut W>,
+    cx: &mut Context<'_>,
+    buffer: &[u8],
+    progress: &mut u32,
+) -> Poll<io::Result<()>> {
+    while (*progress as usize) < buffer.len() {
+        let written = ready!(writer
+            .as_mut()
+            .poll_write(cx, &buffer[*progress as usize..]))?;
+        *progress += written as u32;
+    }
+    Poll::Ready(Ok(()))
+}
+
+/// Progress through the buffer is tracked with a `u32` rather than a `usize`,
+/// which keeps the futures small, e.g 16 rather than 24 bytes for a `u16`.
+fn assert_fits<const N: usize>() {
+    const { assert!(N <= u32::MAX as usize, "buffer too large for future") }
+}
+
 fn assert_future<T, F: Future<Output = T>>(f: F) -> F {
     f
 }
@@ -176,6 +634,16 @@ mod tests {
     const LOWER: i64 = -500_000;
     const UPPER: i64 = 500_000;
 
+    // keep per-value futures small, since they are created in tight loops
+    #[cfg(target_pointer_width = "64")]
+    const _: () = {
+        use std::mem::size_of;
+        assert!(size_of::<super::ReadEndian<2, &mut &[u8], u16>>() == 16);
+        assert!(size_of::<super::ReadEndian<8, &mut &[u8], u64>>() == 24);
+        assert!(size_of::<super::WriteArray<4, &mut Vec<u8>>>() == 16);
+        assert!(size_of::<super::WriteArray<8, &mut Vec<u8>>>() == 24);
+    };
+
     #[test]
     fn read() {
         for endian in [Endian::Big, Endian::Little] {
@@ -215,4 +683,82 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn negotiation() {
+        block_on(async {
+            let mut wire = vec![];
+            super::write_negotiation(&mut wire).await.unwrap();
+            let endian = super::detect_negotiation(&mut wire.as_slice()).await;
+            assert_eq!(endian.unwrap(), Endian::Native.canonical());
+        })
+    }
+
+    #[test]
+    fn decode_stream() {
+        use super::AsyncDecodeExt as _;
+        use ::futures::StreamExt as _;
+
+        // records straddle the blocks the stream reads in
+        let mut bytes = (0..2000u16)
+            .flat_map(|it| [it.to_be_bytes()[0], it.to_be_bytes()[1], 0xff])
+            .collect::<Vec<_>>();
+        block_on(async {
+            let mut r = bytes.as_slice();
+            let mut stream = r.decode_stream_be::<(u16, u8)>();
+            for expected in 0..2000 {
+                assert_eq!(stream.next().await.unwrap().unwrap(), (expected, 0xff));
+            }
+            assert!(stream.next().await.is_none());
+        });
+
+        bytes.push(1);
+        block_on(async {
+            let mut r = bytes.as_slice();
+            let mut stream = r.decode_stream_be::<(u16, u8)>().skip(2000);
+            assert_eq!(
+                stream.next().await.unwrap().unwrap_err().kind(),
+                std::io::ErrorKind::UnexpectedEof
+            );
+            assert!(stream.next().await.is_none());
+        });
+
+        // values larger than a block
+        let bytes = vec![7; 3 * 5000];
+        block_on(async {
+            let mut r = bytes.as_slice();
+            let mut stream = r.decode_stream_be::<[u8; 5000]>();
+            for _ in 0..3 {
+                assert_eq!(stream.next().await.unwrap().unwrap(), [7; 5000]);
+            }
+            assert!(stream.next().await.is_none());
+        });
+    }
+
+    #[test]
+    fn owned() {
+        block_on(async {
+            let (writer, res) = Vec::new().write_le_owned(0x0102u16).await;
+            res.unwrap();
+            assert_eq!(writer, [2, 1]);
+
+            let (reader, res) = writer.as_slice().read_le_owned::<u16>().await;
+            assert_eq!(res.unwrap(), 0x0102);
+            let (_, res) = reader.read_be_owned::<u8>().await;
+            assert_eq!(res.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+        })
+    }
+
+    #[test]
+    fn array() {
+        block_on(async {
+            let mut r = ::futures::io::Cursor::new([1, 0, 2, 0, 3]);
+            let it = r.read_array_endian::<u16, 2>(Endian::Little).await;
+            assert_eq!(it.unwrap(), [1, 2]);
+            assert_eq!(
+                r.read_array_be::<i8, 2>().await.unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+        })
+    }
 }
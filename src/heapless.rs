@@ -0,0 +1,243 @@
+//! Length-prefixed reads into [`heapless`](https://docs.rs/heapless/0.8/heapless/)
+//! containers, for variable-length fields without an allocator.
+//!
+//! ```
+//! use bitendian::{cursor::ByteReader, heapless::{Error, ReadHeaplessExt as _}};
+//!
+//! let mut reader = ByteReader::new(&[2, 0x00, 0x01, 0x00, 0x02, 2, b'o', b'k']);
+//! let ports: heapless::Vec<u16, 4> = reader.read_vec_be::<u8, _, _, _>()?;
+//! assert_eq!(ports, [1, 2]);
+//! assert_eq!(
+//!     reader.read_string_be::<u8, 1>(),
+//!     Err(Error::CapacityExceeded { len: 2, capacity: 1 })
+//! );
+//! let name: heapless::String<2> = reader.read_string_be::<u8, _>()?;
+//! assert_eq!(name, "ok");
+//! # Ok::<_, Error>(())
+//! ```
+
+use crate::{
+    cursor::{ByteReader, ReadBytesExt as _, UnexpectedEnd},
+    BitEndian, Endian,
+};
+use core::fmt;
+use heapless::{String, Vec};
+
+/// Extends [`ByteReader`] with length-prefixed reads into [`heapless`]
+/// containers.
+///
+/// The length prefix is a `P`, which is `N` bytes wide.
+/// Failed reads consume nothing.
+pub trait ReadHeaplessExt<const N: usize> {
+    /// Read a `P` length, followed by that many `T`s, according to a
+    /// run-time endianness.
+    fn read_vec_endian<P, const S: usize, T, const C: usize>(
+        &mut self,
+        endian: Endian,
+    ) -> Result<Vec<T, C>, Error>
+    where
+        P: BitEndian<N> + Into<u64>,
+        T: BitEndian<S>;
+    /// Read a `P` length, followed by that many `T`s, with [`Endian::Big`].
+    fn read_vec_be<P, const S: usize, T, const C: usize>(&mut self) -> Result<Vec<T, C>, Error>
+    where
+        P: BitEndian<N> + Into<u64>,
+        T: BitEndian<S>,
+    {
+        self.read_vec_endian::<P, S, T, C>(Endian::Big)
+    }
+    /// Read a `P` length, followed by that many `T`s, with [`Endian::Little`].
+    fn read_vec_le<P, const S: usize, T, const C: usize>(&mut self) -> Result<Vec<T, C>, Error>
+    where
+        P: BitEndian<N> + Into<u64>,
+        T: BitEndian<S>,
+    {
+        self.read_vec_endian::<P, S, T, C>(Endian::Little)
+    }
+    /// Read a `P` length, followed by that many `T`s, with [`Endian::Native`].
+    fn read_vec_ne<P, const S: usize, T, const C: usize>(&mut self) -> Result<Vec<T, C>, Error>
+    where
+        P: BitEndian<N> + Into<u64>,
+        T: BitEndian<S>,
+    {
+        self.read_vec_endian::<P, S, T, C>(Endian::Native)
+    }
+
+    /// Read a `P` length, followed by that many bytes of UTF-8, according to
+    /// a run-time endianness.
+    fn read_string_endian<P, const C: usize>(&mut self, endian: Endian) -> Result<String<C>, Error>
+    where
+        P: BitEndian<N> + Into<u64>;
+    /// Read a `P` length, followed by that many bytes of UTF-8, with
+    /// [`Endian::Big`].
+    fn read_string_be<P, const C: usize>(&mut self) -> Result<String<C>, Error>
+    where
+        P: BitEndian<N> + Into<u64>,
+    {
+        self.read_string_endian::<P, C>(Endian::Big)
+    }
+    /// Read a `P` length, followed by that many bytes of UTF-8, with
+    /// [`Endian::Little`].
+    fn read_string_le<P, const C: usize>(&mut self) -> Result<String<C>, Error>
+    where
+        P: BitEndian<N> + Into<u64>,
+    {
+        self.read_string_endian::<P, C>(Endian::Little)
+    }
+    /// Read a `P` length, followed by that many bytes of UTF-8, with
+    /// [`Endian::Native`].
+    fn read_string_ne<P, const C: usize>(&mut self) -> Result<String<C>, Error>
+    where
+        P: BitEndian<N> + Into<u64>,
+    {
+        self.read_string_endian::<P, C>(Endian::Native)
+    }
+}
+
+impl<const N: usize> ReadHeaplessExt<N> for ByteReader<'_> {
+    fn read_vec_endian<P, const S: usize, T, const C: usize>(
+        &mut self,
+        endian: Endian,
+    ) -> Result<Vec<T, C>, Error>
+    where
+        P: BitEndian<N> + Into<u64>,
+        T: BitEndian<S>,
+    {
+        let mut reader = *self;
+        let len = read_len::<N, P, C>(&mut reader, endian)?;
+        let bytes = reader.read_bytes(len * S)?;
+        let items = bytes
+            .chunks_exact(S)
+            .map(|chunk| {
+                T::from_bytes_endian(chunk.try_into().expect("chunk is of length S"), endian)
+            })
+            .collect();
+        *self = reader;
+        Ok(items)
+    }
+
+    fn read_string_endian<P, const C: usize>(&mut self, endian: Endian) -> Result<String<C>, Error>
+    where
+        P: BitEndian<N> + Into<u64>,
+    {
+        let mut reader = *self;
+        let len = read_len::<N, P, C>(&mut reader, endian)?;
+        let s = core::str::from_utf8(reader.read_bytes(len)?).map_err(Error::InvalidUtf8)?;
+        let mut string = String::new();
+        string.push_str(s).expect("length is within capacity");
+        *self = reader;
+        Ok(string)
+    }
+}
+
+fn read_len<const N: usize, P: BitEndian<N> + Into<u64>, const C: usize>(
+    reader: &mut ByteReader<'_>,
+    endian: Endian,
+) -> Result<usize, Error> {
+    let len = reader.read_endian::<P>(endian)?.into();
+    match usize::try_from(len) {
+        Ok(len) if len <= C => Ok(len),
+        _ => Err(Error::CapacityExceeded { len, capacity: C }),
+    }
+}
+
+/// Error returned by [`ReadHeaplessExt`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The input ended before the prefix or the items.
+    UnexpectedEnd(UnexpectedEnd),
+    /// The length prefix was larger than the container's capacity.
+    CapacityExceeded {
+        /// The length from the prefix.
+        len: u64,
+        /// The capacity of the container.
+        capacity: usize,
+    },
+    /// A string wasn't valid UTF-8.
+    InvalidUtf8(core::str::Utf8Error),
+}
+
+impl From<UnexpectedEnd> for Error {
+    fn from(value: UnexpectedEnd) -> Self {
+        Self::UnexpectedEnd(value)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnexpectedEnd(e) => e.fmt(f),
+            Error::CapacityExceeded { len, capacity } => f.write_fmt(format_args!(
+                "length prefix {} exceeds capacity {}",
+                len, capacity
+            )),
+            Error::InvalidUtf8(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::UnexpectedEnd(e) => Some(e),
+            Error::CapacityExceeded { .. } => None,
+            Error::InvalidUtf8(e) => Some(e),
+        }
+    }
+}
+
+/// As [`std::io::ErrorKind::UnexpectedEof`] or
+/// [`std::io::ErrorKind::InvalidData`].
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::UnexpectedEnd(e) => e.into(),
+            e => std::io::Error::new(std::io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vec() {
+        let mut reader = ByteReader::new(&[0, 2, 0xff, 0xfe, 0x01, 0x00, 9]);
+        let v: Vec<i16, 2> = reader.read_vec_endian::<u16, _, _, _>(Endian::Big).unwrap();
+        assert_eq!(v, [-2, 0x100]);
+        assert_eq!(
+            reader.read_vec_le::<u8, _, u32, 16>(),
+            Err(Error::UnexpectedEnd(UnexpectedEnd {
+                offset: 7,
+                needed: 36,
+                remaining: 0
+            }))
+        );
+        // failures consume nothing
+        assert_eq!(reader.remaining(), [9]);
+        assert_eq!(
+            reader.read_vec_le::<u8, _, u8, 8>(),
+            Err(Error::CapacityExceeded {
+                len: 9,
+                capacity: 8
+            })
+        );
+        assert_eq!(reader.position(), 6);
+    }
+
+    #[test]
+    fn string() {
+        let mut reader = ByteReader::new(&[3, 0, b'a', b'b', b'c', 1, 0xff]);
+        let s: String<3> = reader.read_string_le::<u16, _>().unwrap();
+        assert_eq!(s, "abc");
+        let e = reader.read_string_ne::<u8, 4>().unwrap_err();
+        assert!(matches!(e, Error::InvalidUtf8(_)));
+        assert_eq!(
+            std::io::Error::from(e).kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Split [IEEE 754](https://en.wikipedia.org/wiki/IEEE_754) floats into
+//! their sign, exponent and mantissa fields, and put them back together.
+//!
+//! ```
+//! use bitendian::{ieee754::{self, Parts}, Endian};
+//!
+//! let parts = ieee754::parts_endian::<4, f32>([0x7f, 0xc0, 0x00, 0x01], Endian::Big);
+//! assert_eq!(parts, Parts { negative: false, exponent: 0xff, mantissa: 0x40_0001 });
+//! assert!(parts.is_nan::<4, f32>());
+//!
+//! let bytes = ieee754::from_parts_endian::<4, f32>(parts, Endian::Little);
+//! assert_eq!(bytes, [0x01, 0x00, 0xc0, 0x7f]);
+//! ```
+
+use crate::{BitEndian, Endian};
+
+/// The fields of a float, as they are stored.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub struct Parts {
+    /// The sign bit.
+    pub negative: bool,
+    /// The biased exponent, see [`Ieee754::EXPONENT_BIAS`].
+    pub exponent: u16,
+    /// The stored bits of the significand, without the implicit leading bit.
+    pub mantissa: u64,
+}
+
+impl Parts {
+    /// The exponent with the bias removed.
+    ///
+    /// Note that zeros and subnormals are stored with an exponent of `0`,
+    /// but scale like an exponent of `1`.
+    pub fn unbiased_exponent<const N: usize, T: Ieee754<N>>(&self) -> i32 {
+        i32::from(self.exponent) - T::EXPONENT_BIAS
+    }
+    /// Returns true if the exponent is all ones, and the mantissa is
+    /// non-zero.
+    /// The mantissa is then the NaN's payload.
+    pub fn is_nan<const N: usize, T: Ieee754<N>>(&self) -> bool {
+        self.exponent == max_exponent::<N, T>() && self.mantissa != 0
+    }
+    /// Returns true if the exponent is all ones, and the mantissa is zero.
+    pub fn is_infinite<const N: usize, T: Ieee754<N>>(&self) -> bool {
+        self.exponent == max_exponent::<N, T>() && self.mantissa == 0
+    }
+    /// Returns true if the exponent is zero, and the mantissa is non-zero.
+    pub fn is_subnormal(&self) -> bool {
+        self.exponent == 0 && self.mantissa != 0
+    }
+}
+
+fn max_exponent<const N: usize, T: Ieee754<N>>() -> u16 {
+    ((1u32 << T::EXPONENT_BITS) - 1) as u16
+}
+
+/// A binary float with a sign bit, followed by the exponent, followed by the
+/// mantissa.
+pub trait Ieee754<const N: usize>: BitEndian<N> {
+    /// The width of the exponent field.
+    const EXPONENT_BITS: u32;
+    /// The width of the mantissa field.
+    const MANTISSA_BITS: u32;
+    /// Subtracted from the stored exponent to give the real exponent.
+    const EXPONENT_BIAS: i32 = (1 << (Self::EXPONENT_BITS - 1)) - 1;
+
+    /// Split into fields.
+    fn to_parts(self) -> Parts;
+    /// Assemble from fields.
+    ///
+    /// Bits which don't fit in each field are ignored.
+    fn from_parts(parts: Parts) -> Self;
+}
+
+/// Split the encoding of a `T` into fields, according to a run-time
+/// endianness.
+pub fn parts_endian<const N: usize, T: Ieee754<N>>(bytes: [u8; N], endian: Endian) -> Parts {
+    T::from_bytes_endian(bytes, endian).to_parts()
+}
+
+/// Assemble the encoding of a `T` from fields, according to a run-time
+/// endianness.
+pub fn from_parts_endian<const N: usize, T: Ieee754<N>>(parts: Parts, endian: Endian) -> [u8; N] {
+    T::from_parts(parts).to_bytes_endian(endian)
+}
+
+macro_rules! ieee754 {
+    ($($width:literal { $ty:ty => $bits:ty, $exponent:literal, $mantissa:literal }),* $(,)?) => {
+        $(
+            impl Ieee754<$width> for $ty {
+                const EXPONENT_BITS: u32 = $exponent;
+                const MANTISSA_BITS: u32 = $mantissa;
+
+                fn to_parts(self) -> Parts {
+                    let bits = u64::from(self.to_bits());
+                    Parts {
+                        negative: bits >> ($exponent + $mantissa) == 1,
+                        exponent: ((bits >> $mantissa) & ((1 << $exponent) - 1)) as u16,
+                        mantissa: bits & ((1 << $mantissa) - 1),
+                    }
+                }
+                fn from_parts(parts: Parts) -> Self {
+                    let bits = u64::from(parts.negative) << ($exponent + $mantissa)
+                        | (u64::from(parts.exponent) & ((1 << $exponent) - 1)) << $mantissa
+                        | parts.mantissa & ((1 << $mantissa) - 1);
+                    Self::from_bits(bits as $bits)
+                }
+            }
+        )*
+    };
+}
+ieee754!(4 { f32 => u32, 8, 23 }, 8 { f64 => u64, 11, 52 });
+#[cfg(feature = "half")]
+ieee754!(2 { half::f16 => u16, 5, 10 }, 2 { half::bf16 => u16, 8, 7 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parts() {
+        let parts = (-1.5f64).to_parts();
+        assert_eq!(
+            parts,
+            Parts {
+                negative: true,
+                exponent: 1023,
+                mantissa: 1 << 51,
+            }
+        );
+        assert_eq!(parts.unbiased_exponent::<8, f64>(), 0);
+        assert_eq!(f64::from_parts(parts), -1.5);
+
+        assert!(f32::INFINITY.to_parts().is_infinite::<4, f32>());
+        assert!(!f32::INFINITY.to_parts().is_nan::<4, f32>());
+        assert!(f64::from_bits(1).to_parts().is_subnormal());
+        assert_eq!(
+            f32::from_parts(Parts {
+                negative: false,
+                exponent: 0xffff,
+                mantissa: u64::MAX,
+            })
+            .to_bits(),
+            0x7fff_ffff
+        );
+    }
+}
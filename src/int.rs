@@ -0,0 +1,201 @@
+//! Integers of widths which Rust has no primitive for, e.g [`U24`] or
+//! [`U48`].
+//!
+//! ```
+//! use bitendian::{io::ReadExt as _, U24};
+//!
+//! # fn doit() -> std::io::Result<()> {
+//! let sample: U24 = [0x01, 0x02, 0x03].as_slice().read_be()?;
+//! assert_eq!(u32::from(sample), 0x010203);
+//! # Ok(())
+//! # }
+//! # doit().unwrap()
+//! ```
+
+use crate::BitEndian;
+use core::fmt;
+
+macro_rules! int {
+    ($($(#[$meta:meta])* $name:ident($width:literal, $repr:ty);)*) => {
+        $(
+            $(#[$meta])*
+            ///
+            /// Values are stored in the primitive, and are always in range.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+            pub struct $name($repr);
+
+            impl $name {
+                /// The number of bits in this type.
+                pub const BITS: u32 = $width * 8;
+                /// The smallest value of this type.
+                pub const MIN: Self = Self(<$repr>::MIN >> Self::SHIFT);
+                /// The largest value of this type.
+                pub const MAX: Self = Self(<$repr>::MAX >> Self::SHIFT);
+                const SHIFT: u32 = <$repr>::BITS - Self::BITS;
+
+                /// Return [`None`] if `value` is out of range.
+                pub const fn new(value: $repr) -> Option<Self> {
+                    let it = Self::new_wrapping(value);
+                    match it.0 == value {
+                        true => Some(it),
+                        false => None,
+                    }
+                }
+                /// Discard the high bits of `value`.
+                pub const fn new_wrapping(value: $repr) -> Self {
+                    // shifting back down sign-extends signed types
+                    Self(value << Self::SHIFT >> Self::SHIFT)
+                }
+                /// Return the value as a primitive.
+                pub const fn get(self) -> $repr {
+                    self.0
+                }
+            }
+
+            impl From<$name> for $repr {
+                fn from(value: $name) -> Self {
+                    value.0
+                }
+            }
+
+            impl TryFrom<$repr> for $name {
+                type Error = OutOfRange;
+                fn try_from(value: $repr) -> Result<Self, Self::Error> {
+                    Self::new(value).ok_or(OutOfRange)
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&self.0, f)
+                }
+            }
+
+            impl BitEndian<$width> for $name {
+                fn to_le_bytes(self) -> [u8; $width] {
+                    let bytes = self.0.to_le_bytes();
+                    bytes[..$width].try_into().expect("slice is of length N")
+                }
+                fn to_be_bytes(self) -> [u8; $width] {
+                    let bytes = self.0.to_be_bytes();
+                    bytes[bytes.len() - $width..].try_into().expect("slice is of length N")
+                }
+                fn to_ne_bytes(self) -> [u8; $width] {
+                    match cfg!(target_endian = "big") {
+                        true => BitEndian::to_be_bytes(self),
+                        false => BitEndian::to_le_bytes(self),
+                    }
+                }
+                fn from_le_bytes(bytes: [u8; $width]) -> Self {
+                    let mut padded = [0; size_of::<$repr>()];
+                    padded[..$width].copy_from_slice(&bytes);
+                    Self::new_wrapping(<$repr>::from_le_bytes(padded))
+                }
+                fn from_be_bytes(bytes: [u8; $width]) -> Self {
+                    let mut padded = [0; size_of::<$repr>()];
+                    padded[size_of::<$repr>() - $width..].copy_from_slice(&bytes);
+                    Self::new_wrapping(<$repr>::from_be_bytes(padded))
+                }
+                fn from_ne_bytes(bytes: [u8; $width]) -> Self {
+                    match cfg!(target_endian = "big") {
+                        true => Self::from_be_bytes(bytes),
+                        false => Self::from_le_bytes(bytes),
+                    }
+                }
+            }
+
+            #[cfg(feature = "std")]
+            impl crate::io::Decode for $name {
+                fn decode<R: std::io::Read + ?Sized>(
+                    reader: &mut R,
+                    endian: crate::Endian,
+                ) -> std::io::Result<Self> {
+                    let mut bytes = [0; $width];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(Self::from_bytes_endian(bytes, endian))
+                }
+            }
+
+            #[cfg(feature = "std")]
+            impl crate::io::Encode for $name {
+                fn encode<W: std::io::Write + ?Sized>(
+                    &self,
+                    writer: &mut W,
+                    endian: crate::Endian,
+                ) -> std::io::Result<()> {
+                    writer.write_all(&self.to_bytes_endian(endian))
+                }
+            }
+        )*
+    };
+}
+
+int! {
+    /// A 24-bit unsigned integer, e.g for 24-bit PCM audio.
+    U24(3, u32);
+    /// A 24-bit signed integer, e.g for 24-bit PCM audio.
+    I24(3, i32);
+    /// A 40-bit unsigned integer.
+    U40(5, u64);
+    /// A 40-bit signed integer.
+    I40(5, i64);
+    /// A 48-bit unsigned integer, e.g for timestamps in capture formats.
+    U48(6, u64);
+    /// A 48-bit signed integer.
+    I48(6, i64);
+    /// A 56-bit unsigned integer.
+    U56(7, u64);
+    /// A 56-bit signed integer.
+    I56(7, i64);
+}
+
+/// Error returned when converting an out-of-range primitive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OutOfRange;
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value is out of range")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OutOfRange {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let it = U24::new(0x010203).unwrap();
+        assert_eq!(it.to_be_bytes(), [1, 2, 3]);
+        assert_eq!(it.to_le_bytes(), [3, 2, 1]);
+        assert_eq!(U24::from_le_bytes([3, 2, 1]), it);
+        assert_eq!(U24::MAX.get(), 0xff_ffff);
+        assert_eq!(U24::new(0x0100_0000), None);
+
+        let it = I24::new(-2).unwrap();
+        assert_eq!(it.to_be_bytes(), [0xff, 0xff, 0xfe]);
+        assert_eq!(I24::from_be_bytes([0xff, 0xff, 0xfe]), it);
+        assert_eq!(I24::from_le_bytes([0xff, 0xff, 0x7f]), I24::MAX);
+        assert_eq!(I24::MIN.get(), -0x80_0000);
+        assert_eq!(I24::try_from(0x80_0000), Err(OutOfRange));
+        assert_eq!(
+            I24::from_ne_bytes(I24::MIN.to_ne_bytes()).to_string(),
+            "-8388608"
+        );
+    }
+
+    #[test]
+    fn wider() {
+        let it = U48::new(0x0102_0304_0506).unwrap();
+        assert_eq!(it.to_be_bytes(), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(U48::from_le_bytes([6, 5, 4, 3, 2, 1]), it);
+        assert_eq!(U40::MAX.get(), 0xff_ffff_ffff);
+        assert_eq!(U56::BITS, 56);
+        assert_eq!(I40::from_be_bytes([0xff; 5]).get(), -1);
+        assert_eq!(I56::MIN.to_le_bytes(), [0, 0, 0, 0, 0, 0, 0x80]);
+        assert_eq!(I48::new(1 << 47), None);
+    }
+}
@@ -13,8 +13,12 @@
 //! # doit().unwrap()
 //! ```
 
-use crate::{BitEndian, Endian};
-use std::io;
+use crate::{BitEndian, Endian, TryBitEndian};
+use std::{io, marker::PhantomData, mem::size_of};
+
+#[cfg(feature = "derive")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "derive")))]
+pub use bitendian_derive::{Decode, Encode};
 
 /// Extends [`std::io::Read`] with methods for reading in an endian-dependant way.
 ///
@@ -38,9 +42,499 @@ pub trait ReadExt<const N: usize>: io::Read {
     fn read_ne<T: BitEndian<N>>(&mut self) -> io::Result<T> {
         self.read_endian(Endian::Native)
     }
+    /// Read a [`TryBitEndian`] type according to a run-time endianness.
+    ///
+    /// Invalid values are reported as [`io::ErrorKind::InvalidData`].
+    fn try_read_endian<T>(&mut self, endian: Endian) -> io::Result<T>
+    where
+        T: TryBitEndian<N>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let mut bytes = [0u8; N];
+        self.read_exact(bytes.as_mut())?;
+        T::try_from_bytes_endian(bytes, endian)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+    /// Try to read with [`Endian::Big`].
+    fn try_read_be<T>(&mut self) -> io::Result<T>
+    where
+        T: TryBitEndian<N>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        self.try_read_endian(Endian::Big)
+    }
+    /// Try to read with [`Endian::Little`].
+    fn try_read_le<T>(&mut self) -> io::Result<T>
+    where
+        T: TryBitEndian<N>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        self.try_read_endian(Endian::Little)
+    }
+    /// Try to read with [`Endian::Native`].
+    fn try_read_ne<T>(&mut self) -> io::Result<T>
+    where
+        T: TryBitEndian<N>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        self.try_read_endian(Endian::Native)
+    }
+    /// Read a `Wire` value according to a run-time endianness, then convert
+    /// it to a `T`.
+    ///
+    /// Values which don't fit in a `T` are reported as
+    /// [`io::ErrorKind::InvalidData`].
+    ///
+    /// ```
+    /// use bitendian::io::ReadExt as _;
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let mut r = [0, 3, 0xff, 0xff].as_slice();
+    /// let len: usize = r.read_be_as::<u16, _>()?;
+    /// assert_eq!(len, 3);
+    /// assert!(r.read_be_as::<i16, u8>().is_err());
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    fn read_endian_as<Wire, T>(&mut self, endian: Endian) -> io::Result<T>
+    where
+        Wire: BitEndian<N>,
+        T: TryFrom<Wire>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        T::try_from(self.read_endian::<Wire>(endian)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+    /// Read and convert with [`Endian::Big`], as in [`Self::read_endian_as`].
+    fn read_be_as<Wire, T>(&mut self) -> io::Result<T>
+    where
+        Wire: BitEndian<N>,
+        T: TryFrom<Wire>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        self.read_endian_as::<Wire, T>(Endian::Big)
+    }
+    /// Read and convert with [`Endian::Little`], as in
+    /// [`Self::read_endian_as`].
+    fn read_le_as<Wire, T>(&mut self) -> io::Result<T>
+    where
+        Wire: BitEndian<N>,
+        T: TryFrom<Wire>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        self.read_endian_as::<Wire, T>(Endian::Little)
+    }
+    /// Read and convert with [`Endian::Native`], as in
+    /// [`Self::read_endian_as`].
+    fn read_ne_as<Wire, T>(&mut self) -> io::Result<T>
+    where
+        Wire: BitEndian<N>,
+        T: TryFrom<Wire>,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        self.read_endian_as::<Wire, T>(Endian::Native)
+    }
+    /// Read a presence byte, then the value if it is present, according to a
+    /// run-time endianness.
+    ///
+    /// Presence bytes other than `0` or `1` are reported as
+    /// [`io::ErrorKind::InvalidData`].
+    ///
+    /// ```
+    /// use bitendian::io::{ReadExt as _, WriteExt as _};
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let mut buf = vec![];
+    /// buf.write_option_be(Some(1u16))?;
+    /// buf.write_option_be(None::<u16>)?;
+    /// assert_eq!(buf, [1, 0, 1, 0]);
+    ///
+    /// let mut r = buf.as_slice();
+    /// assert_eq!(r.read_option_be::<u16>()?, Some(1));
+    /// assert_eq!(r.read_option_be::<u16>()?, None);
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    fn read_option_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> io::Result<Option<T>> {
+        match bool::decode(self, endian)? {
+            true => self.read_endian(endian).map(Some),
+            false => Ok(None),
+        }
+    }
+    /// Read an optional value with [`Endian::Big`], as in
+    /// [`Self::read_option_endian`].
+    fn read_option_be<T: BitEndian<N>>(&mut self) -> io::Result<Option<T>> {
+        self.read_option_endian(Endian::Big)
+    }
+    /// Read an optional value with [`Endian::Little`], as in
+    /// [`Self::read_option_endian`].
+    fn read_option_le<T: BitEndian<N>>(&mut self) -> io::Result<Option<T>> {
+        self.read_option_endian(Endian::Little)
+    }
+    /// Read an optional value with [`Endian::Native`], as in
+    /// [`Self::read_option_endian`].
+    fn read_option_ne<T: BitEndian<N>>(&mut self) -> io::Result<Option<T>> {
+        self.read_option_endian(Endian::Native)
+    }
+    /// Read `M` consecutive values according to a run-time endianness.
+    ///
+    /// ```
+    /// use bitendian::io::ReadExt as _;
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let palette: [u16; 2] = [0x7c, 0x00, 0x03, 0xe0].as_slice().read_array_be()?;
+    /// assert_eq!(palette, [0x7c00, 0x03e0]);
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    fn read_array_endian<T: BitEndian<N>, const M: usize>(
+        &mut self,
+        endian: Endian,
+    ) -> io::Result<[T; M]> {
+        let mut bytes = [[0u8; N]; M];
+        self.read_exact(bytes.as_flattened_mut())?;
+        Ok(bytes.map(|it| T::from_bytes_endian(it, endian)))
+    }
+    /// Read `M` values with [`Endian::Big`], as in
+    /// [`Self::read_array_endian`].
+    fn read_array_be<T: BitEndian<N>, const M: usize>(&mut self) -> io::Result<[T; M]> {
+        self.read_array_endian(Endian::Big)
+    }
+    /// Read `M` values with [`Endian::Little`], as in
+    /// [`Self::read_array_endian`].
+    fn read_array_le<T: BitEndian<N>, const M: usize>(&mut self) -> io::Result<[T; M]> {
+        self.read_array_endian(Endian::Little)
+    }
+    /// Read `M` values with [`Endian::Native`], as in
+    /// [`Self::read_array_endian`].
+    fn read_array_ne<T: BitEndian<N>, const M: usize>(&mut self) -> io::Result<[T; M]> {
+        self.read_array_endian(Endian::Native)
+    }
+    /// Read `count` consecutive values into a new vector, according to a
+    /// run-time endianness.
+    ///
+    /// `count` is often read from untrusted input, so the vector grows as
+    /// values are read, rather than being allocated up front.
+    /// A `count` whose length in bytes overflows a [`usize`] is reported as
+    /// [`io::ErrorKind::InvalidData`].
+    ///
+    /// ```
+    /// use bitendian::io::ReadExt as _;
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let mut r = [2, 0, 1, 0, 2].as_slice();
+    /// let count: u8 = r.read_be()?;
+    /// let values: Vec<u16> = r.read_vec_be(count.into())?;
+    /// assert_eq!(values, [1, 2]);
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    fn read_vec_endian<T: BitEndian<N>>(
+        &mut self,
+        count: usize,
+        endian: Endian,
+    ) -> io::Result<Vec<T>> {
+        self.read_collect_endian(count, endian)
+    }
+    /// Read `count` values with [`Endian::Big`], as in
+    /// [`Self::read_vec_endian`].
+    fn read_vec_be<T: BitEndian<N>>(&mut self, count: usize) -> io::Result<Vec<T>> {
+        self.read_vec_endian(count, Endian::Big)
+    }
+    /// Read `count` values with [`Endian::Little`], as in
+    /// [`Self::read_vec_endian`].
+    fn read_vec_le<T: BitEndian<N>>(&mut self, count: usize) -> io::Result<Vec<T>> {
+        self.read_vec_endian(count, Endian::Little)
+    }
+    /// Read `count` values with [`Endian::Native`], as in
+    /// [`Self::read_vec_endian`].
+    fn read_vec_ne<T: BitEndian<N>>(&mut self, count: usize) -> io::Result<Vec<T>> {
+        self.read_vec_endian(count, Endian::Native)
+    }
+    /// Read `count` consecutive values into a new collection, according to a
+    /// run-time endianness.
+    ///
+    /// This is like [`Self::read_vec_endian`], but for any collection, e.g a
+    /// [`VecDeque`](std::collections::VecDeque), or a `SmallVec` which keeps
+    /// short runs inline.
+    ///
+    /// ```
+    /// use bitendian::io::ReadExt as _;
+    /// use std::collections::VecDeque;
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let mut r = [0, 1, 0, 2].as_slice();
+    /// let values = r.read_collect_be::<u16, VecDeque<_>>(2)?;
+    /// assert_eq!(values, [1, 2]);
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    fn read_collect_endian<T: BitEndian<N>, C: Default + Extend<T>>(
+        &mut self,
+        count: usize,
+        endian: Endian,
+    ) -> io::Result<C> {
+        if count.checked_mul(N).is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "count is too large for this platform",
+            ));
+        }
+        let mut values = C::default();
+        if N == 0 {
+            // zero-width values take up no bytes, so there's nothing to read
+            let value = || T::from_bytes_endian([0; N], endian);
+            values.extend(core::iter::repeat_with(value).take(count));
+            return Ok(values);
+        }
+        let per_block = block_len::<N>(count);
+        let mut buf = vec![0; per_block * N];
+        let mut remaining = count;
+        while remaining > 0 {
+            let block = remaining.min(per_block);
+            let buf = &mut buf[..block * N];
+            self.read_exact(buf)?;
+            values.extend(crate::slice::iter_endian(buf, endian));
+            remaining -= block;
+        }
+        Ok(values)
+    }
+    /// Read `count` values with [`Endian::Big`], as in
+    /// [`Self::read_collect_endian`].
+    fn read_collect_be<T: BitEndian<N>, C: Default + Extend<T>>(
+        &mut self,
+        count: usize,
+    ) -> io::Result<C> {
+        self.read_collect_endian(count, Endian::Big)
+    }
+    /// Read `count` values with [`Endian::Little`], as in
+    /// [`Self::read_collect_endian`].
+    fn read_collect_le<T: BitEndian<N>, C: Default + Extend<T>>(
+        &mut self,
+        count: usize,
+    ) -> io::Result<C> {
+        self.read_collect_endian(count, Endian::Little)
+    }
+    /// Read `count` values with [`Endian::Native`], as in
+    /// [`Self::read_collect_endian`].
+    fn read_collect_ne<T: BitEndian<N>, C: Default + Extend<T>>(
+        &mut self,
+        count: usize,
+    ) -> io::Result<C> {
+        self.read_collect_endian(count, Endian::Native)
+    }
+    /// Read values until the end of the reader, according to a run-time
+    /// endianness.
+    ///
+    /// If the reader ends partway through a value, this fails with
+    /// [`io::ErrorKind::UnexpectedEof`], wrapping a
+    /// [`TrailingBytes`](crate::slice::TrailingBytes) with the offset of
+    /// that value.
+    /// Zero-width values take up no bytes, so none are read.
+    ///
+    /// ```
+    /// use bitendian::io::ReadExt as _;
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let records: Vec<u16> = [0, 1, 0, 2].as_slice().read_to_end_be()?;
+    /// assert_eq!(records, [1, 2]);
+    ///
+    /// let e = [0, 1, 0].as_slice().read_to_end_be::<u16>().unwrap_err();
+    /// assert_eq!(e.to_string(), "1 trailing bytes at offset 2 don't make up a whole value");
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    fn read_to_end_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> io::Result<Vec<T>> {
+        if N == 0 {
+            // there are no zero-width values in a stream of bytes, as in
+            // `slice::iter_endian`
+            return Ok(Vec::new());
+        }
+        let mut buf = vec![0; block_len::<N>(usize::MAX) * N];
+        let (mut values, mut filled, mut offset) = (Vec::new(), 0, 0);
+        loop {
+            match self.read(&mut buf[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+            let whole = filled / N * N;
+            values.extend(crate::slice::iter_endian(&buf[..whole], endian));
+            buf.copy_within(whole..filled, 0);
+            filled -= whole;
+            offset += whole;
+        }
+        match filled {
+            0 => Ok(values),
+            len => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                crate::slice::TrailingBytes { offset, len },
+            )),
+        }
+    }
+    /// Read values until the end with [`Endian::Big`], as in
+    /// [`Self::read_to_end_endian`].
+    fn read_to_end_be<T: BitEndian<N>>(&mut self) -> io::Result<Vec<T>> {
+        self.read_to_end_endian(Endian::Big)
+    }
+    /// Read values until the end with [`Endian::Little`], as in
+    /// [`Self::read_to_end_endian`].
+    fn read_to_end_le<T: BitEndian<N>>(&mut self) -> io::Result<Vec<T>> {
+        self.read_to_end_endian(Endian::Little)
+    }
+    /// Read values until the end with [`Endian::Native`], as in
+    /// [`Self::read_to_end_endian`].
+    fn read_to_end_ne<T: BitEndian<N>>(&mut self) -> io::Result<Vec<T>> {
+        self.read_to_end_endian(Endian::Native)
+    }
+    /// Lazily read values until the end of the reader, according to a
+    /// run-time endianness.
+    ///
+    /// The iterator ends cleanly if the reader ends between values.
+    /// If it ends partway through a value, or fails, the iterator yields the
+    /// error, and then ends.
+    /// Zero-width values take up no bytes, so none are yielded.
+    ///
+    /// ```
+    /// use bitendian::io::ReadExt as _;
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let mut r = [0, 1, 0, 2, 0, 3].as_slice();
+    /// let total = r.iter_be::<u16>().sum::<std::io::Result<u16>>()?;
+    /// assert_eq!(total, 6);
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    fn iter_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> Iter<N, &mut Self, T> {
+        Iter {
+            reader: self,
+            endian,
+            done: false,
+            _out: PhantomData,
+        }
+    }
+    /// Iterate with [`Endian::Big`], as in [`Self::iter_endian`].
+    fn iter_be<T: BitEndian<N>>(&mut self) -> Iter<N, &mut Self, T> {
+        self.iter_endian(Endian::Big)
+    }
+    /// Iterate with [`Endian::Little`], as in [`Self::iter_endian`].
+    fn iter_le<T: BitEndian<N>>(&mut self) -> Iter<N, &mut Self, T> {
+        self.iter_endian(Endian::Little)
+    }
+    /// Iterate with [`Endian::Native`], as in [`Self::iter_endian`].
+    fn iter_ne<T: BitEndian<N>>(&mut self) -> Iter<N, &mut Self, T> {
+        self.iter_endian(Endian::Native)
+    }
+    /// Fill `out` with consecutive values, according to a run-time
+    /// endianness.
+    ///
+    /// The bytes are read in large blocks rather than value-by-value.
+    /// If this returns an error, `out` may have been partially filled.
+    ///
+    /// ```
+    /// use bitendian::io::ReadExt as _;
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let mut samples = [0i16; 3];
+    /// [0xff, 0xff, 0, 1, 0, 2].as_slice().read_be_into(&mut samples)?;
+    /// assert_eq!(samples, [-1, 1, 2]);
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    fn read_endian_into<T: BitEndian<N>>(
+        &mut self,
+        out: &mut [T],
+        endian: Endian,
+    ) -> io::Result<()> {
+        if N == 0 {
+            // zero-width values take up no bytes, so there's nothing to read
+            crate::slice::decode_slice(&[], endian, out);
+            return Ok(());
+        }
+        let per_block = block_len::<N>(out.len());
+        let mut buf = vec![0; per_block * N];
+        for out in out.chunks_mut(per_block) {
+            let buf = &mut buf[..out.len() * N];
+            self.read_exact(buf)?;
+            crate::slice::decode_slice(buf, endian, out);
+        }
+        Ok(())
+    }
+    /// Fill `out` with [`Endian::Big`], as in [`Self::read_endian_into`].
+    fn read_be_into<T: BitEndian<N>>(&mut self, out: &mut [T]) -> io::Result<()> {
+        self.read_endian_into(out, Endian::Big)
+    }
+    /// Fill `out` with [`Endian::Little`], as in [`Self::read_endian_into`].
+    fn read_le_into<T: BitEndian<N>>(&mut self, out: &mut [T]) -> io::Result<()> {
+        self.read_endian_into(out, Endian::Little)
+    }
+    /// Fill `out` with [`Endian::Native`], as in [`Self::read_endian_into`].
+    fn read_ne_into<T: BitEndian<N>>(&mut self, out: &mut [T]) -> io::Result<()> {
+        self.read_endian_into(out, Endian::Native)
+    }
 }
 impl<const N: usize, R> ReadExt<N> for R where R: io::Read {}
 
+/// Iterator for [`ReadExt::iter_endian`], see that method for more.
+#[derive(Debug)]
+pub struct Iter<const N: usize, R, T> {
+    reader: R,
+    endian: Endian,
+    done: bool,
+    _out: PhantomData<fn() -> T>,
+}
+
+impl<const N: usize, R: io::Read, T: BitEndian<N>> Iterator for Iter<N, R, T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // as in `read_to_end_endian`, a stream holds no zero-width values
+        if self.done || N == 0 {
+            return None;
+        }
+        let mut bytes = [0u8; N];
+        let mut filled = 0;
+        while filled < N {
+            match self.reader.read(&mut bytes[filled..]) {
+                Ok(0) if filled == 0 => {
+                    self.done = true;
+                    return None;
+                }
+                Ok(0) => {
+                    self.done = true;
+                    return Some(Err(io::ErrorKind::UnexpectedEof.into()));
+                }
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        Some(Ok(T::from_bytes_endian(bytes, self.endian)))
+    }
+}
+
+impl<const N: usize, R: io::Read, T: BitEndian<N>> std::iter::FusedIterator for Iter<N, R, T> {}
+
+/// The number of values of `N` bytes to stage at once, out of `count`.
+///
+/// This is at least one, and at most as many as fit in a few pages.
+fn block_len<const N: usize>(count: usize) -> usize {
+    const MAX: usize = 8 * 1024;
+    count.min(MAX.checked_div(N).unwrap_or(MAX)).max(1)
+}
+
 /// Extends [`std::io::Write`] with methods for writing in an endian-dependent way.
 ///
 /// See [module docs](mod@self) for usage examples.
@@ -61,5 +555,1794 @@ pub trait WriteExt<const N: usize>: io::Write {
     fn write_ne<T: BitEndian<N>>(&mut self, it: T) -> io::Result<()> {
         self.write_endian(it, Endian::Native)
     }
+    /// Write a presence byte, then the value if it is present, according to
+    /// a run-time endianness.
+    ///
+    /// See [`ReadExt::read_option_endian`] for the inverse.
+    fn write_option_endian<T: BitEndian<N>>(
+        &mut self,
+        it: Option<T>,
+        endian: Endian,
+    ) -> io::Result<()> {
+        match it {
+            Some(it) => {
+                self.write_all(&[1])?;
+                self.write_endian(it, endian)
+            }
+            None => self.write_all(&[0]),
+        }
+    }
+    /// Write an optional value with [`Endian::Big`], as in
+    /// [`Self::write_option_endian`].
+    fn write_option_be<T: BitEndian<N>>(&mut self, it: Option<T>) -> io::Result<()> {
+        self.write_option_endian(it, Endian::Big)
+    }
+    /// Write an optional value with [`Endian::Little`], as in
+    /// [`Self::write_option_endian`].
+    fn write_option_le<T: BitEndian<N>>(&mut self, it: Option<T>) -> io::Result<()> {
+        self.write_option_endian(it, Endian::Little)
+    }
+    /// Write an optional value with [`Endian::Native`], as in
+    /// [`Self::write_option_endian`].
+    fn write_option_ne<T: BitEndian<N>>(&mut self, it: Option<T>) -> io::Result<()> {
+        self.write_option_endian(it, Endian::Native)
+    }
+    /// Write consecutive values, according to a run-time endianness.
+    ///
+    /// The values are encoded into large blocks rather than written
+    /// value-by-value.
+    ///
+    /// ```
+    /// use bitendian::io::WriteExt as _;
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let mut buf = vec![];
+    /// buf.write_le_all(&[1u16, 2])?;
+    /// assert_eq!(buf, [1, 0, 2, 0]);
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    fn write_endian_all<T: BitEndian<N> + Copy>(
+        &mut self,
+        values: &[T],
+        endian: Endian,
+    ) -> io::Result<()> {
+        let per_block = block_len::<N>(values.len());
+        let mut buf = vec![0; per_block * N];
+        for values in values.chunks(per_block) {
+            let buf = &mut buf[..values.len() * N];
+            crate::slice::encode_slice(values, endian, buf);
+            self.write_all(buf)?;
+        }
+        Ok(())
+    }
+    /// Write all with [`Endian::Big`], as in [`Self::write_endian_all`].
+    fn write_be_all<T: BitEndian<N> + Copy>(&mut self, values: &[T]) -> io::Result<()> {
+        self.write_endian_all(values, Endian::Big)
+    }
+    /// Write all with [`Endian::Little`], as in [`Self::write_endian_all`].
+    fn write_le_all<T: BitEndian<N> + Copy>(&mut self, values: &[T]) -> io::Result<()> {
+        self.write_endian_all(values, Endian::Little)
+    }
+    /// Write all with [`Endian::Native`], as in [`Self::write_endian_all`].
+    fn write_ne_all<T: BitEndian<N> + Copy>(&mut self, values: &[T]) -> io::Result<()> {
+        self.write_endian_all(values, Endian::Native)
+    }
+    /// Write a zeroed placeholder, to be [`fill`](Slot::fill)ed in later
+    /// according to a run-time endianness.
+    ///
+    /// ```
+    /// use bitendian::io::WriteExt as _;
+    /// use std::io::{Cursor, Seek as _, Write as _};
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let mut w = Cursor::new(vec![]);
+    /// let len = w.reserve_be::<u32>()?;
+    /// w.write_all(b"body")?;
+    /// let body_len = w.stream_position()? - len.end();
+    /// len.fill(&mut w, body_len as u32)?;
+    /// w.write_all(b"!")?;
+    /// assert_eq!(w.into_inner(), b"\0\0\0\x04body!");
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    fn reserve_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> io::Result<Slot<N, T>>
+    where
+        Self: io::Seek,
+    {
+        let position = self.stream_position()?;
+        self.write_all(&[0; N])?;
+        Ok(Slot {
+            position,
+            endian,
+            _value: PhantomData,
+        })
+    }
+    /// Reserve with [`Endian::Big`], as in [`Self::reserve_endian`].
+    fn reserve_be<T: BitEndian<N>>(&mut self) -> io::Result<Slot<N, T>>
+    where
+        Self: io::Seek,
+    {
+        self.reserve_endian(Endian::Big)
+    }
+    /// Reserve with [`Endian::Little`], as in [`Self::reserve_endian`].
+    fn reserve_le<T: BitEndian<N>>(&mut self) -> io::Result<Slot<N, T>>
+    where
+        Self: io::Seek,
+    {
+        self.reserve_endian(Endian::Little)
+    }
+    /// Reserve with [`Endian::Native`], as in [`Self::reserve_endian`].
+    fn reserve_ne<T: BitEndian<N>>(&mut self) -> io::Result<Slot<N, T>>
+    where
+        Self: io::Seek,
+    {
+        self.reserve_endian(Endian::Native)
+    }
 }
 impl<const N: usize, W> WriteExt<N> for W where W: io::Write {}
+
+/// A type which can be read field-by-field according to a run-time
+/// endianness, e.g a header struct made of primitives.
+///
+/// Use [`DecodeExt`] to read implementors.
+///
+/// ```
+/// use bitendian::io::{Decode, DecodeExt as _, Encode, EncodeExt as _};
+/// use bitendian::Endian;
+/// use std::io;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Header {
+///     count: u16,
+///     offset: i32,
+/// }
+///
+/// impl Decode for Header {
+///     fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+///         Ok(Self {
+///             count: Decode::decode(reader, endian)?,
+///             offset: Decode::decode(reader, endian)?,
+///         })
+///     }
+/// }
+///
+/// impl Encode for Header {
+///     fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+///         self.count.encode(writer, endian)?;
+///         self.offset.encode(writer, endian)
+///     }
+/// }
+///
+/// # fn doit() -> io::Result<()> {
+/// let header = Header { count: 1, offset: -1 };
+/// let mut buf = vec![];
+/// buf.encode_be(&header)?;
+/// assert_eq!(buf, [0, 1, 0xff, 0xff, 0xff, 0xff]);
+/// assert_eq!(buf.as_slice().decode_be::<Header>()?, header);
+/// # Ok(())
+/// # }
+/// # doit().unwrap()
+/// ```
+///
+/// With the `derive` feature, the implementations can be generated instead
+/// with `#[derive(Encode, Decode)]`, which also documents the field
+/// attributes it accepts.
+pub trait Decode: Sized {
+    /// Read a value according to a run-time endianness.
+    fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self>;
+}
+
+/// A type which can be written field-by-field according to a run-time
+/// endianness.
+///
+/// See [`Decode`] for an example, and use [`EncodeExt`] to write implementors.
+pub trait Encode {
+    /// Write this value according to a run-time endianness.
+    fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()>;
+}
+
+macro_rules! codec {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Decode for $ty {
+                fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+                    let mut bytes = [0u8; size_of::<$ty>()];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(<$ty>::from_bytes_endian(bytes, endian))
+                }
+            }
+            impl Encode for $ty {
+                fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+                    writer.write_all(&self.to_bytes_endian(endian))
+                }
+            }
+        )*
+    };
+}
+codec!(u8, i8, u16, i16, u32, i32, f32, u64, i64, f64, u128, i128, usize, isize);
+codec!(std::net::Ipv4Addr, std::net::Ipv6Addr);
+#[cfg(feature = "half")]
+codec!(half::f16, half::bf16);
+
+macro_rules! codec_atomic {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Decode for $ty {
+                fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+                    Decode::decode(reader, endian).map(Self::new)
+                }
+            }
+            /// A [`Relaxed`](std::sync::atomic::Ordering::Relaxed) snapshot
+            /// of the contained value.
+            impl Encode for $ty {
+                fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+                    self.load(std::sync::atomic::Ordering::Relaxed).encode(writer, endian)
+                }
+            }
+        )*
+    };
+}
+#[cfg(target_has_atomic = "8")]
+codec_atomic!(std::sync::atomic::AtomicU8, std::sync::atomic::AtomicI8);
+#[cfg(target_has_atomic = "16")]
+codec_atomic!(std::sync::atomic::AtomicU16, std::sync::atomic::AtomicI16);
+#[cfg(target_has_atomic = "32")]
+codec_atomic!(std::sync::atomic::AtomicU32, std::sync::atomic::AtomicI32);
+#[cfg(target_has_atomic = "64")]
+codec_atomic!(std::sync::atomic::AtomicU64, std::sync::atomic::AtomicI64);
+#[cfg(target_has_atomic = "ptr")]
+codec_atomic!(
+    std::sync::atomic::AtomicUsize,
+    std::sync::atomic::AtomicIsize
+);
+#[cfg(feature = "portable-atomic")]
+codec_atomic!(
+    portable_atomic::AtomicU8,
+    portable_atomic::AtomicI8,
+    portable_atomic::AtomicU16,
+    portable_atomic::AtomicI16,
+    portable_atomic::AtomicU32,
+    portable_atomic::AtomicI32,
+    portable_atomic::AtomicF32,
+    portable_atomic::AtomicU64,
+    portable_atomic::AtomicI64,
+    portable_atomic::AtomicF64,
+    portable_atomic::AtomicU128,
+    portable_atomic::AtomicI128,
+    portable_atomic::AtomicUsize,
+    portable_atomic::AtomicIsize,
+);
+
+macro_rules! codec_newtype {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl<T: Decode> Decode for std::num::$ty<T> {
+                fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+                    T::decode(reader, endian).map(Self)
+                }
+            }
+            impl<T: Encode> Encode for std::num::$ty<T> {
+                fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+                    self.0.encode(writer, endian)
+                }
+            }
+        )*
+    };
+}
+codec_newtype!(Wrapping, Saturating);
+
+macro_rules! codec_non_zero {
+    ($($ty:ident($inner:ty)),* $(,)?) => {
+        $(
+            /// Zero is invalid, see [`TryBitEndian`].
+            impl Decode for std::num::$ty {
+                fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+                    Self::try_from(<$inner>::decode(reader, endian)?)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                }
+            }
+            impl Encode for std::num::$ty {
+                fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+                    self.get().encode(writer, endian)
+                }
+            }
+        )*
+    };
+}
+codec_non_zero!(
+    NonZeroU8(u8),
+    NonZeroI8(i8),
+    NonZeroU16(u16),
+    NonZeroI16(i16),
+    NonZeroU32(u32),
+    NonZeroI32(i32),
+    NonZeroU64(u64),
+    NonZeroI64(i64),
+    NonZeroU128(u128),
+    NonZeroI128(i128),
+    NonZeroUsize(usize),
+    NonZeroIsize(isize),
+);
+
+/// As the address, then the port, as in e.g SOCKS.
+///
+/// Use [`Endian::Big`] for network order.
+impl Decode for std::net::SocketAddrV4 {
+    fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+        Ok(Self::new(
+            Decode::decode(reader, endian)?,
+            Decode::decode(reader, endian)?,
+        ))
+    }
+}
+/// As the address, then the port.
+impl Encode for std::net::SocketAddrV4 {
+    fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+        self.ip().encode(writer, endian)?;
+        self.port().encode(writer, endian)
+    }
+}
+
+/// As the address, then the port, as in e.g SOCKS.
+///
+/// The flow information and scope ID are not on the wire, and are decoded as
+/// zero.
+/// Use [`Endian::Big`] for network order.
+impl Decode for std::net::SocketAddrV6 {
+    fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+        Ok(Self::new(
+            Decode::decode(reader, endian)?,
+            Decode::decode(reader, endian)?,
+            0,
+            0,
+        ))
+    }
+}
+/// As the address, then the port.
+impl Encode for std::net::SocketAddrV6 {
+    fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+        self.ip().encode(writer, endian)?;
+        self.port().encode(writer, endian)
+    }
+}
+
+/// As a `u64` of seconds, then a `u32` of nanoseconds, which must be less than
+/// one second.
+///
+/// ```
+/// use bitendian::io::{DecodeExt as _, EncodeExt as _};
+/// use std::time::Duration;
+///
+/// # fn doit() -> std::io::Result<()> {
+/// let mut buf = vec![];
+/// buf.encode_be(&Duration::from_millis(1500))?;
+/// assert_eq!(buf, [0, 0, 0, 0, 0, 0, 0, 1, 0x1d, 0xcd, 0x65, 0x00]);
+/// assert_eq!(buf.as_slice().decode_be::<Duration>()?, Duration::from_millis(1500));
+/// # Ok(())
+/// # }
+/// # doit().unwrap()
+/// ```
+impl Decode for std::time::Duration {
+    fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+        let secs = u64::decode(reader, endian)?;
+        match u32::decode(reader, endian)? {
+            nanos @ ..1_000_000_000 => Ok(Self::new(secs, nanos)),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "nanoseconds must be less than one second",
+            )),
+        }
+    }
+}
+/// As a `u64` of seconds, then a `u32` of nanoseconds.
+impl Encode for std::time::Duration {
+    fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+        self.as_secs().encode(writer, endian)?;
+        self.subsec_nanos().encode(writer, endian)
+    }
+}
+
+/// As `0` or `1`, see [`TryBitEndian`].
+impl Decode for bool {
+    fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+        bool::try_from_bytes_endian([u8::decode(reader, endian)?], endian)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+/// As `0` or `1`.
+impl Encode for bool {
+    fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+        u8::from(*self).encode(writer, endian)
+    }
+}
+
+/// As its `u32` code point, see [`TryBitEndian`].
+impl Decode for char {
+    fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+        char::try_from(u32::decode(reader, endian)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+/// As its `u32` code point.
+impl Encode for char {
+    fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+        u32::from(*self).encode(writer, endian)
+    }
+}
+
+/// As a presence byte, `0` or `1`, then the value if it is present.
+impl<T: Decode> Decode for Option<T> {
+    fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+        match bool::decode(reader, endian)? {
+            true => T::decode(reader, endian).map(Some),
+            false => Ok(None),
+        }
+    }
+}
+/// As a presence byte, `0` or `1`, then the value if it is present.
+impl<T: Encode> Encode for Option<T> {
+    fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+        self.is_some().encode(writer, endian)?;
+        match self {
+            Some(it) => it.encode(writer, endian),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Decode, const M: usize> Decode for [T; M] {
+    fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+        let items = (0..M)
+            .map(|_| T::decode(reader, endian))
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(items
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("collected exactly M items")))
+    }
+}
+impl<T: Encode, const M: usize> Encode for [T; M] {
+    fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+        self.iter().try_for_each(|it| it.encode(writer, endian))
+    }
+}
+
+macro_rules! codec_tuple {
+    ($($ty:ident),*) => {
+        /// Each field in order, so small records can be read in one call.
+        impl<$($ty: Decode),*> Decode for ($($ty,)*) {
+            #[allow(unused_variables)]
+            fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+                Ok(($($ty::decode(reader, endian)?,)*))
+            }
+        }
+        impl<$($ty: Encode),*> Encode for ($($ty,)*) {
+            #[allow(non_snake_case, unused_variables)]
+            fn encode<W: io::Write + ?Sized>(&self, writer: &mut W, endian: Endian) -> io::Result<()> {
+                let ($($ty,)*) = self;
+                $($ty.encode(writer, endian)?;)*
+                Ok(())
+            }
+        }
+    };
+}
+codec_tuple!();
+codec_tuple!(A);
+codec_tuple!(A, B);
+codec_tuple!(A, B, C);
+codec_tuple!(A, B, C, D);
+codec_tuple!(A, B, C, D, E);
+codec_tuple!(A, B, C, D, E, F);
+codec_tuple!(A, B, C, D, E, F, G);
+codec_tuple!(A, B, C, D, E, F, G, H);
+codec_tuple!(A, B, C, D, E, F, G, H, I);
+codec_tuple!(A, B, C, D, E, F, G, H, I, J);
+codec_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+codec_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Decode a value from the start of `bytes` according to a run-time
+/// endianness, returning the rest of the input.
+///
+/// This is the counterpart of [`BitEndian::parse_endian`] for composite
+/// types.
+///
+/// ```
+/// use bitendian::io;
+///
+/// # fn doit() -> std::io::Result<()> {
+/// let input = [0, 1, 0, 0, 0, 2, 0xff];
+/// let ((id, len), rest) = io::parse_be::<(u16, u32)>(&input)?;
+/// assert_eq!((id, len, rest), (1, 2, &[0xff][..]));
+/// # Ok(())
+/// # }
+/// # doit().unwrap()
+/// ```
+pub fn parse_endian<T: Decode>(mut bytes: &[u8], endian: Endian) -> io::Result<(T, &[u8])> {
+    let it = T::decode(&mut bytes, endian)?;
+    Ok((it, bytes))
+}
+
+/// Parse with [`Endian::Big`], as in [`parse_endian`].
+pub fn parse_be<T: Decode>(bytes: &[u8]) -> io::Result<(T, &[u8])> {
+    parse_endian(bytes, Endian::Big)
+}
+
+/// Parse with [`Endian::Little`], as in [`parse_endian`].
+pub fn parse_le<T: Decode>(bytes: &[u8]) -> io::Result<(T, &[u8])> {
+    parse_endian(bytes, Endian::Little)
+}
+
+/// Parse with [`Endian::Native`], as in [`parse_endian`].
+pub fn parse_ne<T: Decode>(bytes: &[u8]) -> io::Result<(T, &[u8])> {
+    parse_endian(bytes, Endian::Native)
+}
+
+/// Extends [`std::io::Read`] with methods for reading [`Decode`] types.
+///
+/// This is the counterpart of [`ReadExt`] for composite types.
+pub trait DecodeExt: io::Read {
+    /// Decode according to a run-time endianness.
+    fn decode_endian<T: Decode>(&mut self, endian: Endian) -> io::Result<T> {
+        T::decode(self, endian)
+    }
+    /// Decode with [`Endian::Big`].
+    fn decode_be<T: Decode>(&mut self) -> io::Result<T> {
+        self.decode_endian(Endian::Big)
+    }
+    /// Decode with [`Endian::Little`].
+    fn decode_le<T: Decode>(&mut self) -> io::Result<T> {
+        self.decode_endian(Endian::Little)
+    }
+    /// Decode with [`Endian::Native`].
+    fn decode_ne<T: Decode>(&mut self) -> io::Result<T> {
+        self.decode_endian(Endian::Native)
+    }
+    /// Read a `usize` which was written as a `u64`, according to a run-time
+    /// endianness.
+    ///
+    /// Unlike reading a `usize` directly, this is the same width on every
+    /// platform.
+    /// Values which don't fit in a `usize` are reported as
+    /// [`io::ErrorKind::InvalidData`].
+    fn read_usize_from_u64_endian(&mut self, endian: Endian) -> io::Result<usize> {
+        usize::try_from(u64::decode(self, endian)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+    /// Read a `usize` from a `u64` with [`Endian::Big`], as in
+    /// [`Self::read_usize_from_u64_endian`].
+    fn read_usize_from_u64_be(&mut self) -> io::Result<usize> {
+        self.read_usize_from_u64_endian(Endian::Big)
+    }
+    /// Read a `usize` from a `u64` with [`Endian::Little`], as in
+    /// [`Self::read_usize_from_u64_endian`].
+    fn read_usize_from_u64_le(&mut self) -> io::Result<usize> {
+        self.read_usize_from_u64_endian(Endian::Little)
+    }
+    /// Read a `usize` from a `u64` with [`Endian::Native`], as in
+    /// [`Self::read_usize_from_u64_endian`].
+    fn read_usize_from_u64_ne(&mut self) -> io::Result<usize> {
+        self.read_usize_from_u64_endian(Endian::Native)
+    }
+    /// Lazily decode values until the end of the reader, according to a
+    /// run-time endianness.
+    ///
+    /// The iterator ends cleanly if the reader ends between values.
+    /// If it ends partway through a value, the iterator yields
+    /// [`io::ErrorKind::UnexpectedEof`], and then ends.
+    /// Other errors are yielded in the same way.
+    ///
+    /// ```
+    /// use bitendian::io::{Decode, DecodeExt as _};
+    /// use bitendian::Endian;
+    /// use std::io;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Sample {
+    ///     channel: u8,
+    ///     value: i16,
+    /// }
+    ///
+    /// impl Decode for Sample {
+    ///     fn decode<R: io::Read + ?Sized>(reader: &mut R, endian: Endian) -> io::Result<Self> {
+    ///         Ok(Self {
+    ///             channel: Decode::decode(reader, endian)?,
+    ///             value: Decode::decode(reader, endian)?,
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let mut r = [1, 0, 2, 2, 0xff, 0xff].as_slice();
+    /// let samples = r.decode_stream_be::<Sample>().collect::<io::Result<Vec<_>>>()?;
+    /// assert_eq!(
+    ///     samples,
+    ///     [Sample { channel: 1, value: 2 }, Sample { channel: 2, value: -1 }]
+    /// );
+    ///
+    /// let e = [1, 0].as_slice().decode_stream_be::<Sample>().next().unwrap();
+    /// assert_eq!(e.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn decode_stream_endian<T: Decode>(&mut self, endian: Endian) -> DecodeStream<&mut Self, T> {
+        DecodeStream {
+            reader: self,
+            endian,
+            done: false,
+            _out: PhantomData,
+        }
+    }
+    /// Decode values with [`Endian::Big`], as in
+    /// [`Self::decode_stream_endian`].
+    fn decode_stream_be<T: Decode>(&mut self) -> DecodeStream<&mut Self, T> {
+        self.decode_stream_endian(Endian::Big)
+    }
+    /// Decode values with [`Endian::Little`], as in
+    /// [`Self::decode_stream_endian`].
+    fn decode_stream_le<T: Decode>(&mut self) -> DecodeStream<&mut Self, T> {
+        self.decode_stream_endian(Endian::Little)
+    }
+    /// Decode values with [`Endian::Native`], as in
+    /// [`Self::decode_stream_endian`].
+    fn decode_stream_ne<T: Decode>(&mut self) -> DecodeStream<&mut Self, T> {
+        self.decode_stream_endian(Endian::Native)
+    }
+}
+impl<R> DecodeExt for R where R: io::Read + ?Sized {}
+
+/// Iterator for [`DecodeExt::decode_stream_endian`], see that method for
+/// more.
+#[derive(Debug)]
+pub struct DecodeStream<R, T> {
+    reader: R,
+    endian: Endian,
+    done: bool,
+    _out: PhantomData<fn() -> T>,
+}
+
+impl<R: io::Read, T: Decode> Iterator for DecodeStream<R, T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut reader = __derive::Counted::new(&mut self.reader);
+        match T::decode(&mut reader, self.endian) {
+            Ok(it) => Some(Ok(it)),
+            Err(e) => {
+                self.done = true;
+                match (e.kind(), reader.offset) {
+                    // the reader ended between values
+                    (io::ErrorKind::UnexpectedEof, 0) => None,
+                    _ => Some(Err(e)),
+                }
+            }
+        }
+    }
+}
+
+impl<R: io::Read, T: Decode> std::iter::FusedIterator for DecodeStream<R, T> {}
+
+/// Extends [`std::io::Write`] with methods for writing [`Encode`] types.
+///
+/// This is the counterpart of [`WriteExt`] for composite types.
+pub trait EncodeExt: io::Write {
+    /// Encode according to a run-time endianness.
+    fn encode_endian<T: Encode + ?Sized>(&mut self, it: &T, endian: Endian) -> io::Result<()> {
+        it.encode(self, endian)
+    }
+    /// Encode with [`Endian::Big`].
+    fn encode_be<T: Encode + ?Sized>(&mut self, it: &T) -> io::Result<()> {
+        self.encode_endian(it, Endian::Big)
+    }
+    /// Encode with [`Endian::Little`].
+    fn encode_le<T: Encode + ?Sized>(&mut self, it: &T) -> io::Result<()> {
+        self.encode_endian(it, Endian::Little)
+    }
+    /// Encode with [`Endian::Native`].
+    fn encode_ne<T: Encode + ?Sized>(&mut self, it: &T) -> io::Result<()> {
+        self.encode_endian(it, Endian::Native)
+    }
+    /// Write a `usize` as a `u64`, according to a run-time endianness.
+    ///
+    /// Unlike writing a `usize` directly, this is the same width on every
+    /// platform.
+    /// Values which don't fit in a `u64` are reported as
+    /// [`io::ErrorKind::InvalidInput`].
+    ///
+    /// ```
+    /// use bitendian::io::{DecodeExt as _, EncodeExt as _};
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let mut buf = vec![];
+    /// buf.write_usize_as_u64_le(3)?;
+    /// assert_eq!(buf, [3, 0, 0, 0, 0, 0, 0, 0]);
+    /// assert_eq!(buf.as_slice().read_usize_from_u64_le()?, 3);
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    fn write_usize_as_u64_endian(&mut self, it: usize, endian: Endian) -> io::Result<()> {
+        u64::try_from(it)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+            .encode(self, endian)
+    }
+    /// Write a `usize` as a `u64` with [`Endian::Big`], as in
+    /// [`Self::write_usize_as_u64_endian`].
+    fn write_usize_as_u64_be(&mut self, it: usize) -> io::Result<()> {
+        self.write_usize_as_u64_endian(it, Endian::Big)
+    }
+    /// Write a `usize` as a `u64` with [`Endian::Little`], as in
+    /// [`Self::write_usize_as_u64_endian`].
+    fn write_usize_as_u64_le(&mut self, it: usize) -> io::Result<()> {
+        self.write_usize_as_u64_endian(it, Endian::Little)
+    }
+    /// Write a `usize` as a `u64` with [`Endian::Native`], as in
+    /// [`Self::write_usize_as_u64_endian`].
+    fn write_usize_as_u64_ne(&mut self, it: usize) -> io::Result<()> {
+        self.write_usize_as_u64_endian(it, Endian::Native)
+    }
+}
+impl<W> EncodeExt for W where W: io::Write + ?Sized {}
+
+/// Support for the code generated by the derives, not public API.
+#[doc(hidden)]
+pub mod __derive {
+    use super::{Decode, Encode};
+    use crate::Endian;
+    use std::{
+        fmt,
+        io::{self, Read as _},
+    };
+
+    /// Tracks the offset from the start of a derived type, for alignment.
+    pub struct Counted<'a, T: ?Sized> {
+        pub inner: &'a mut T,
+        pub offset: u64,
+    }
+
+    impl<'a, T: ?Sized> Counted<'a, T> {
+        pub fn new(inner: &'a mut T) -> Self {
+            Self { inner, offset: 0 }
+        }
+    }
+
+    impl<T: io::Read + ?Sized> io::Read for Counted<'_, T> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.offset += n as u64;
+            Ok(n)
+        }
+    }
+
+    impl<T: io::Write + ?Sized> io::Write for Counted<'_, T> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let n = self.inner.write(buf)?;
+            self.offset += n as u64;
+            Ok(n)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            self.inner.flush()
+        }
+    }
+
+    /// The number of bytes needed to bring `offset` up to a multiple of `align`.
+    pub fn padding(offset: u64, align: u64) -> u64 {
+        offset.next_multiple_of(align) - offset
+    }
+
+    pub fn skip<R: io::Read + ?Sized>(reader: &mut R, n: u64) -> io::Result<()> {
+        match io::copy(&mut io::Read::take(reader, n), &mut io::sink())? == n {
+            true => Ok(()),
+            false => Err(io::ErrorKind::UnexpectedEof.into()),
+        }
+    }
+
+    pub fn zeroes<W: io::Write + ?Sized>(writer: &mut W, n: u64) -> io::Result<()> {
+        io::copy(&mut io::Read::take(io::repeat(0), n), writer).map(drop)
+    }
+
+    pub fn check_magic<T: PartialEq + fmt::Debug>(found: T, expected: T) -> io::Result<()> {
+        match found == expected {
+            true => Ok(()),
+            false => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "magic doesn't match: expected {:02x?}, found {:02x?}",
+                    expected, found
+                ),
+            )),
+        }
+    }
+
+    pub fn unknown_discriminant(name: &str, discriminant: impl fmt::Display) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unknown discriminant {} for {}", discriminant, name),
+        )
+    }
+
+    /// A collection which may be preceded by its length.
+    pub trait Prefixed: Sized {
+        fn prefix_len(&self) -> usize;
+        fn encode_body<W: io::Write + ?Sized>(
+            &self,
+            writer: &mut W,
+            endian: Endian,
+        ) -> io::Result<()>;
+        fn decode_body<R: io::Read + ?Sized>(
+            reader: &mut R,
+            endian: Endian,
+            len: usize,
+        ) -> io::Result<Self>;
+    }
+
+    impl<T: Encode + Decode> Prefixed for Vec<T> {
+        fn prefix_len(&self) -> usize {
+            self.len()
+        }
+        fn encode_body<W: io::Write + ?Sized>(
+            &self,
+            writer: &mut W,
+            endian: Endian,
+        ) -> io::Result<()> {
+            self.iter().try_for_each(|it| it.encode(writer, endian))
+        }
+        fn decode_body<R: io::Read + ?Sized>(
+            reader: &mut R,
+            endian: Endian,
+            len: usize,
+        ) -> io::Result<Self> {
+            // don't trust `len` for the allocation
+            let mut items = Vec::with_capacity(len.min(1024));
+            for _ in 0..len {
+                items.push(T::decode(reader, endian)?)
+            }
+            Ok(items)
+        }
+    }
+
+    #[cfg(feature = "smallvec")]
+    impl<A: smallvec::Array> Prefixed for smallvec::SmallVec<A>
+    where
+        A::Item: Encode + Decode,
+    {
+        fn prefix_len(&self) -> usize {
+            self.len()
+        }
+        fn encode_body<W: io::Write + ?Sized>(
+            &self,
+            writer: &mut W,
+            endian: Endian,
+        ) -> io::Result<()> {
+            self.iter().try_for_each(|it| it.encode(writer, endian))
+        }
+        fn decode_body<R: io::Read + ?Sized>(
+            reader: &mut R,
+            endian: Endian,
+            len: usize,
+        ) -> io::Result<Self> {
+            // stays inline up to the array's capacity, and doesn't trust
+            // `len` for the allocation beyond that
+            let mut items = Self::new();
+            items.reserve(len.min(1024));
+            for _ in 0..len {
+                items.push(A::Item::decode(reader, endian)?)
+            }
+            Ok(items)
+        }
+    }
+
+    impl Prefixed for String {
+        fn prefix_len(&self) -> usize {
+            self.len()
+        }
+        fn encode_body<W: io::Write + ?Sized>(&self, writer: &mut W, _: Endian) -> io::Result<()> {
+            writer.write_all(self.as_bytes())
+        }
+        fn decode_body<R: io::Read + ?Sized>(
+            reader: &mut R,
+            _: Endian,
+            len: usize,
+        ) -> io::Result<Self> {
+            let mut bytes = vec![];
+            io::Read::take(reader, len as u64).read_to_end(&mut bytes)?;
+            if bytes.len() != len {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }
+    }
+
+    pub fn encode_prefixed<P, T, W>(it: &T, writer: &mut W, endian: Endian) -> io::Result<()>
+    where
+        P: TryFrom<usize> + Encode,
+        T: Prefixed,
+        W: io::Write + ?Sized,
+    {
+        let len = P::try_from(it.prefix_len()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "length is too large for the length prefix",
+            )
+        })?;
+        len.encode(writer, endian)?;
+        it.encode_body(writer, endian)
+    }
+
+    pub fn decode_prefixed<P, T, R>(reader: &mut R, endian: Endian) -> io::Result<T>
+    where
+        P: TryInto<usize> + Decode,
+        T: Prefixed,
+        R: io::Read + ?Sized,
+    {
+        let len = P::decode(reader, endian)?.try_into().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "length prefix is too large for this platform",
+            )
+        })?;
+        T::decode_body(reader, endian, len)
+    }
+}
+
+/// A placeholder returned by [`WriteExt::reserve_endian`], see that method
+/// for more.
+#[derive(Debug)]
+#[must_use = "the placeholder remains zeroed until filled"]
+pub struct Slot<const N: usize, T> {
+    position: u64,
+    endian: Endian,
+    _value: PhantomData<fn(T)>,
+}
+
+impl<const N: usize, T: BitEndian<N>> Slot<N, T> {
+    /// The stream position of the start of the placeholder.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+    /// The stream position just after the placeholder.
+    pub fn end(&self) -> u64 {
+        self.position + N as u64
+    }
+    /// Overwrite the placeholder with `value`, then seek back to where the
+    /// writer was.
+    pub fn fill<W>(self, writer: &mut W, value: T) -> io::Result<()>
+    where
+        W: io::Write + io::Seek + ?Sized,
+    {
+        let resume = writer.stream_position()?;
+        writer.seek(io::SeekFrom::Start(self.position))?;
+        writer.write_all(&value.to_bytes_endian(self.endian))?;
+        writer.seek(io::SeekFrom::Start(resume))?;
+        Ok(())
+    }
+}
+
+/// A sentinel written by one peer in its native byte order, so that the
+/// other can detect that byte order from how it arrives.
+///
+/// See [`write_negotiation`] and [`detect_negotiation`].
+pub const NEGOTIATION_WORD: u32 = 0x0A0B_0C0D;
+
+/// Write [`NEGOTIATION_WORD`] in native byte order.
+///
+/// ```
+/// use bitendian::io::{detect_negotiation, write_negotiation};
+/// use bitendian::Endian;
+///
+/// # fn doit() -> std::io::Result<()> {
+/// let mut wire = vec![];
+/// write_negotiation(&mut wire)?;
+/// let peer = detect_negotiation(&mut wire.as_slice())?;
+/// assert_eq!(peer, Endian::Native.canonical());
+/// # Ok(())
+/// # }
+/// # doit().unwrap()
+/// ```
+pub fn write_negotiation(writer: &mut (impl io::Write + ?Sized)) -> io::Result<()> {
+    writer.write_all(&NEGOTIATION_WORD.to_ne_bytes())
+}
+
+/// Read [`NEGOTIATION_WORD`], returning the byte order it was written in,
+/// either [`Endian::Big`] or [`Endian::Little`].
+///
+/// Any other word is reported as [`io::ErrorKind::InvalidData`].
+pub fn detect_negotiation(reader: &mut (impl io::Read + ?Sized)) -> io::Result<Endian> {
+    let mut word = [0u8; 4];
+    reader.read_exact(&mut word)?;
+    negotiated(word)
+}
+
+pub(crate) fn negotiated(word: [u8; 4]) -> io::Result<Endian> {
+    if word == NEGOTIATION_WORD.to_be_bytes() {
+        Ok(Endian::Big)
+    } else if word == NEGOTIATION_WORD.to_le_bytes() {
+        Ok(Endian::Little)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unrecognised byte order negotiation word",
+        ))
+    }
+}
+
+/// A writer which buffers its output until [`commit`](Self::commit)ted, so
+/// that a partially-written frame can be [`rollback`](Self::rollback)ed
+/// instead of reaching the underlying writer.
+///
+/// ```
+/// use bitendian::io::{Checkpointed, WriteExt as _};
+///
+/// # fn doit() -> std::io::Result<()> {
+/// let mut w = Checkpointed::new(vec![]);
+/// w.write_be(1u16)?;
+/// let checkpoint = w.checkpoint();
+/// w.write_be(2u16)?; // ...and then serialization fails
+/// w.rollback(checkpoint);
+/// w.commit()?;
+/// assert_eq!(w.get_ref(), &[0, 1]);
+/// # Ok(())
+/// # }
+/// # doit().unwrap()
+/// ```
+///
+/// Uncommitted output is discarded when this is dropped.
+#[derive(Debug)]
+pub struct Checkpointed<W> {
+    inner: W,
+    buffer: Vec<u8>,
+    committed: u64,
+}
+
+/// A position in a [`Checkpointed`] writer, to [`rollback`](Checkpointed::rollback) to.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Checkpoint(u64);
+
+impl<W: io::Write> Checkpointed<W> {
+    /// Wrap `inner`, with nothing buffered.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buffer: Vec::new(),
+            committed: 0,
+        }
+    }
+    /// Mark the current position.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.committed + self.buffer.len() as u64)
+    }
+    /// Discard everything written since `checkpoint`.
+    ///
+    /// # Panics
+    /// - If `checkpoint` has since been committed.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        let len = checkpoint
+            .0
+            .checked_sub(self.committed)
+            .expect("checkpoint has already been committed");
+        self.buffer.truncate(len as usize)
+    }
+    /// Write everything buffered to the underlying writer.
+    ///
+    /// This does not [`flush`](io::Write::flush) the underlying writer.
+    pub fn commit(&mut self) -> io::Result<()> {
+        self.inner.write_all(&self.buffer)?;
+        self.committed += self.buffer.len() as u64;
+        self.buffer.clear();
+        Ok(())
+    }
+    /// The bytes which have been written, but not committed.
+    pub fn uncommitted(&self) -> &[u8] {
+        &self.buffer
+    }
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+    /// Get a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+    /// Return the underlying writer, discarding any uncommitted output.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: io::Write> io::Write for Checkpointed<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    /// Flushes the underlying writer, but does not [`commit`](Self::commit).
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A reader which can look ahead at upcoming bytes, even if the underlying
+/// reader is not [`BufRead`](io::BufRead) or [`Seek`](io::Seek), e.g a pipe.
+///
+/// Peeked bytes are returned by subsequent reads.
+///
+/// ```
+/// use bitendian::io::{PeekExt as _, PeekReader, ReadExt as _};
+///
+/// # fn doit() -> std::io::Result<()> {
+/// let mut r = PeekReader::new([0, 1, 0, 2].as_slice());
+/// let tag = r.peek_be::<u16>()?;
+/// assert_eq!(tag, 1);
+/// assert_eq!(r.peek_bytes(3)?, [0, 1, 0]);
+/// let read: u32 = r.read_be()?;
+/// assert_eq!(read, 0x0001_0002);
+/// # Ok(())
+/// # }
+/// # doit().unwrap()
+/// ```
+#[derive(Debug)]
+pub struct PeekReader<R> {
+    inner: R,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl<R: io::Read> PeekReader<R> {
+    /// Wrap `inner`, with nothing buffered.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buffer: Vec::new(),
+            position: 0,
+        }
+    }
+    /// Return the next `n` bytes without consuming them.
+    ///
+    /// Only as many bytes as needed are read from the underlying reader.
+    ///
+    /// If the underlying reader ends first, [`io::ErrorKind::UnexpectedEof`]
+    /// is returned, but any bytes read remain buffered.
+    pub fn peek_bytes(&mut self, n: usize) -> io::Result<&[u8]> {
+        if self.buffered().len() < n {
+            self.buffer.drain(..self.position);
+            self.position = 0;
+            let mut filled = self.buffer.len();
+            self.buffer.resize(n, 0);
+            while filled < n {
+                match self.inner.read(&mut self.buffer[filled..]) {
+                    Ok(0) => break,
+                    Ok(read) => filled += read,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => {
+                        self.buffer.truncate(filled);
+                        return Err(e);
+                    }
+                }
+            }
+            self.buffer.truncate(filled);
+            if filled < n {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+        }
+        Ok(&self.buffered()[..n])
+    }
+    /// Consume the next `n` bytes, borrowing them rather than copying them
+    /// out.
+    ///
+    /// The bytes are valid until the next read, which is enough to inspect a
+    /// length-prefixed field, or copy parts of it, without allocating.
+    ///
+    /// ```
+    /// use bitendian::io::{PeekReader, ReadExt as _};
+    ///
+    /// # fn doit() -> std::io::Result<()> {
+    /// let mut r = PeekReader::new([0, 2, b'h', b'i', 0xff].as_slice());
+    /// let len: u16 = r.read_be()?;
+    /// assert_eq!(r.read_bytes_ref(len.into())?, b"hi");
+    /// let trailer: u8 = r.read_be()?;
+    /// assert_eq!(trailer, 0xff);
+    /// # Ok(())
+    /// # }
+    /// # doit().unwrap()
+    /// ```
+    ///
+    /// If the underlying reader ends first, [`io::ErrorKind::UnexpectedEof`]
+    /// is returned, and nothing is consumed.
+    pub fn read_bytes_ref(&mut self, n: usize) -> io::Result<&[u8]> {
+        self.peek_bytes(n)?;
+        let start = self.position;
+        self.position += n;
+        Ok(&self.buffer[start..self.position])
+    }
+    /// The bytes which have been peeked, but not yet read.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer[self.position..]
+    }
+    /// Get a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+    /// Get a mutable reference to the underlying reader.
+    ///
+    /// Reading from it directly skips any [`buffered`](Self::buffered) bytes.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+    /// Return the underlying reader, discarding any [`buffered`](Self::buffered)
+    /// bytes.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: io::Read> io::Read for PeekReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.buffer.len() {
+            return self.inner.read(buf);
+        }
+        let read = io::Read::read(&mut self.buffered(), buf)?;
+        self.position += read;
+        if self.position == self.buffer.len() {
+            self.buffer.clear();
+            self.position = 0;
+        }
+        Ok(read)
+    }
+}
+
+/// Extends [`PeekReader`] with methods for peeking in an endian-dependent way.
+///
+/// See [`PeekReader`] for usage examples.
+pub trait PeekExt<const N: usize> {
+    /// Decode the next value according to a run-time endianness, without
+    /// consuming it.
+    fn peek_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> io::Result<T>;
+    /// Peek with [`Endian::Big`].
+    fn peek_be<T: BitEndian<N>>(&mut self) -> io::Result<T> {
+        self.peek_endian(Endian::Big)
+    }
+    /// Peek with [`Endian::Little`].
+    fn peek_le<T: BitEndian<N>>(&mut self) -> io::Result<T> {
+        self.peek_endian(Endian::Little)
+    }
+    /// Peek with [`Endian::Native`].
+    fn peek_ne<T: BitEndian<N>>(&mut self) -> io::Result<T> {
+        self.peek_endian(Endian::Native)
+    }
+}
+impl<const N: usize, R: io::Read> PeekExt<N> for PeekReader<R> {
+    fn peek_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> io::Result<T> {
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(self.peek_bytes(N)?);
+        Ok(T::from_bytes_endian(bytes, endian))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_array() {
+        let mut r = [1, 2, 3].as_slice();
+        assert_eq!(r.read_array_le::<u8, 2>().unwrap(), [1, 2]);
+        assert_eq!(
+            r.read_array_ne::<u16, 1>().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(
+            r.read_array_endian::<u32, 0>(Endian::Big).unwrap(),
+            [0u32; 0]
+        );
+    }
+
+    #[test]
+    fn read_vec() {
+        let bytes = (0..=u16::MAX)
+            .flat_map(u16::to_be_bytes)
+            .collect::<Vec<_>>();
+        let values = bytes.as_slice().read_vec_be::<u16>(1 << 16).unwrap();
+        assert!(values.into_iter().eq(0..=u16::MAX));
+
+        let mut r = &bytes[..6];
+        assert_eq!(
+            r.read_vec_endian::<u32>(usize::MAX / 2, Endian::Little)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+        // fails on the data, not the allocation
+        assert_eq!(
+            r.read_vec_le::<u32>(usize::MAX / 4).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(r.read_vec_ne::<u8>(0).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn read_collect() {
+        let mut r = [0, 1, 0, 2, 0, 3].as_slice();
+        let values = r
+            .read_collect_be::<u16, std::collections::BTreeSet<_>>(2)
+            .unwrap();
+        assert!(values.into_iter().eq([1, 2]));
+        let e = r.read_collect_le::<u16, Vec<_>>(2).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    #[cfg(feature = "smallvec")]
+    fn read_collect_smallvec() {
+        let mut r = [1, 0, 2, 0, 3, 0].as_slice();
+        let values = r
+            .read_collect_le::<u16, smallvec::SmallVec<[_; 2]>>(2)
+            .unwrap();
+        assert!(!values.spilled());
+        assert_eq!(*values, [1, 2]);
+        let values = r
+            .read_collect_ne::<u16, smallvec::SmallVec<[_; 0]>>(0)
+            .unwrap();
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn read_to_end() {
+        let bytes = (0..=u16::MAX)
+            .flat_map(u16::to_le_bytes)
+            .collect::<Vec<_>>();
+        // values straddle the short reads
+        let mut r = io::Read::chain(&bytes[..3], &bytes[3..]);
+        let values = r.read_to_end_le::<u16>().unwrap();
+        assert!(values.into_iter().eq(0..=u16::MAX));
+
+        let e = io::Read::chain(&bytes[..5], &bytes[5..11])
+            .read_to_end_endian::<u32>(Endian::Big)
+            .unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(
+            e.into_inner().unwrap().downcast_ref(),
+            Some(&crate::slice::TrailingBytes { offset: 8, len: 3 })
+        );
+        assert_eq!(
+            io::empty().read_to_end_ne::<u64>().unwrap(),
+            Vec::<u64>::new()
+        );
+    }
+
+    #[test]
+    fn iter() {
+        let bytes = [0, 1, 0, 2, 0];
+        let mut r = io::Read::chain(&bytes[..1], &bytes[1..]);
+        let mut it = r.iter_endian::<u16>(Endian::Big);
+        assert_eq!(it.next().unwrap().unwrap(), 1);
+        assert_eq!(it.next().unwrap().unwrap(), 2);
+        assert_eq!(
+            it.next().unwrap().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert!(it.next().is_none());
+
+        let values = [1, 0, 2, 0]
+            .as_slice()
+            .iter_le::<u16>()
+            .collect::<io::Result<Vec<_>>>();
+        assert_eq!(values.unwrap(), [1, 2]);
+        assert!(io::empty().iter_ne::<u32>().next().is_none());
+    }
+
+    #[test]
+    fn zero_width() {
+        let mut r = io::Cursor::new(vec![1u8]);
+        assert_eq!(r.read_vec_be::<[u8; 0]>(3).unwrap(), vec![[]; 3]);
+        assert_eq!(
+            r.read_to_end_le::<[u8; 0]>().unwrap(),
+            Vec::<[u8; 0]>::new()
+        );
+        assert!(r.iter_ne::<[u8; 0]>().next().is_none());
+        let mut out = [[0u8; 0]; 2];
+        r.read_be_into(&mut out).unwrap();
+        assert_eq!(out, [[]; 2]);
+        // nothing was read
+        assert_eq!(r.position(), 0);
+    }
+
+    #[test]
+    fn read_into() {
+        let bytes = (0..=u16::MAX)
+            .flat_map(u16::to_le_bytes)
+            .collect::<Vec<_>>();
+        let mut values = vec![0u16; 1 << 16];
+        bytes.as_slice().read_le_into(&mut values).unwrap();
+        assert!(values.iter().copied().eq(0..=u16::MAX));
+
+        let mut r = &bytes[..5];
+        let mut values = [0u16; 3];
+        assert_eq!(
+            r.read_endian_into(&mut values, Endian::Big)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        r.read_be_into::<u64>(&mut []).unwrap();
+    }
+
+    #[test]
+    fn write_all() {
+        let values = (0..=u16::MAX).collect::<Vec<_>>();
+        let mut buf = vec![];
+        buf.write_be_all(&values).unwrap();
+        assert!(buf
+            .chunks(2)
+            .map(|it| [it[0], it[1]])
+            .eq(values.iter().map(|it| it.to_be_bytes())));
+
+        let mut buf = [0u8; 3];
+        assert_eq!(
+            buf.as_mut_slice()
+                .write_endian_all(&[1u16, 2], Endian::Little)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::WriteZero
+        );
+        assert_eq!(buf, [1, 0, 2]);
+        io::sink().write_ne_all::<u8>(&[]).unwrap();
+        io::sink().write_le_all(&[1.5f32]).unwrap();
+    }
+
+    #[test]
+    fn checkpointed() {
+        let mut w = Checkpointed::new(vec![]);
+        w.write_be(1u8).unwrap();
+        w.write_be(2u8).unwrap();
+        w.commit().unwrap();
+        let second = w.checkpoint();
+        w.write_be(3u8).unwrap();
+        let third = w.checkpoint();
+        w.write_be(4u8).unwrap();
+        w.rollback(third);
+        assert_eq!(w.uncommitted(), [3]);
+        w.rollback(second);
+        assert_eq!(w.uncommitted(), [0u8; 0]);
+        assert_eq!(w.into_inner(), [1, 2]);
+    }
+
+    #[test]
+    fn reserve() {
+        let mut w = io::Cursor::new(vec![]);
+        let outer = w.reserve_le::<u16>().unwrap();
+        let inner = w.reserve_be::<u32>().unwrap();
+        w.write_be(0xffu8).unwrap();
+        inner.fill(&mut w, 0x01020304).unwrap();
+        outer.fill(&mut w, 7).unwrap();
+        w.write_be(0xeeu8).unwrap();
+        assert_eq!(w.into_inner(), [7, 0, 1, 2, 3, 4, 0xff, 0xee]);
+    }
+
+    #[test]
+    fn peek() {
+        // yields a byte at a time, like a slow pipe
+        struct Trickle<'a>(&'a [u8]);
+        impl io::Read for Trickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let n = buf.len().min(self.0.len()).min(1);
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Ok(n)
+            }
+        }
+
+        let mut r = PeekReader::new(Trickle(&[1, 2, 3, 4, 5]));
+        assert_eq!(r.peek_le::<u16>().unwrap(), 0x0201);
+        assert_eq!(r.read_be::<u8>().unwrap(), 1);
+        assert_eq!(r.peek_bytes(3).unwrap(), [2, 3, 4]);
+        assert_eq!(r.read_bytes_ref(1).unwrap(), [2]);
+        assert_eq!(r.read_be::<u8>().unwrap(), 3);
+        assert_eq!(
+            r.peek_bytes(3).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(r.buffered(), [4, 5]);
+        let mut rest = vec![];
+        io::Read::read_to_end(&mut r, &mut rest).unwrap();
+        assert_eq!(rest, [4, 5]);
+    }
+
+    #[test]
+    fn try_read() {
+        #[derive(Debug, PartialEq)]
+        struct Odd(u8);
+        impl TryBitEndian<1> for Odd {
+            type Error = std::fmt::Error;
+            fn try_from_le_bytes([it]: [u8; 1]) -> Result<Self, Self::Error> {
+                match it % 2 {
+                    1 => Ok(Odd(it)),
+                    _ => Err(std::fmt::Error),
+                }
+            }
+            fn try_from_be_bytes(bytes: [u8; 1]) -> Result<Self, Self::Error> {
+                Self::try_from_le_bytes(bytes)
+            }
+            fn try_from_ne_bytes(bytes: [u8; 1]) -> Result<Self, Self::Error> {
+                Self::try_from_le_bytes(bytes)
+            }
+        }
+
+        let mut r = [1, 2, 0, 3].as_slice();
+        assert_eq!(r.try_read_be::<Odd>().unwrap(), Odd(1));
+        assert_eq!(
+            r.try_read_be::<Odd>().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(r.try_read_le::<u16>().unwrap(), 0x0300);
+
+        let mut r = [1, 2, 2].as_slice();
+        assert!(r.try_read_be::<bool>().unwrap());
+        assert_eq!(
+            r.try_read_be::<bool>().unwrap_err().to_string(),
+            "invalid bool 0x02"
+        );
+        assert!(r.read_be::<crate::LenientBool>().unwrap().0);
+
+        let mut r = [0, 1, 0, 0].as_slice();
+        assert_eq!(r.try_read_be::<std::num::NonZeroU16>().unwrap().get(), 1);
+        assert_eq!(
+            r.try_read_be::<std::num::NonZeroI16>().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        let mut r = [0, 0, 0, b'a', 0, 0, 0xd8, 0].as_slice();
+        assert_eq!(r.try_read_be::<char>().unwrap(), 'a');
+        assert_eq!(
+            r.try_read_be::<char>().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn codec() {
+        let mut buf = vec![];
+        buf.encode_le(&[1u16, 2]).unwrap();
+        buf.encode_be(&-2i8).unwrap();
+        buf.encode_le(&'a').unwrap();
+        buf.write_be(std::num::Wrapping(3u16)).unwrap();
+        assert_eq!(buf, [1, 0, 2, 0, 0xfe, b'a', 0, 0, 0, 0, 3]);
+        let mut r = buf.as_slice();
+        assert_eq!(r.decode_le::<[u16; 2]>().unwrap(), [1, 2]);
+        assert_eq!(r.decode_be::<i8>().unwrap(), -2);
+        assert_eq!(r.decode_le::<char>().unwrap(), 'a');
+        assert_eq!(
+            r.decode_be::<std::num::Saturating<u16>>().unwrap(),
+            std::num::Saturating(3)
+        );
+        assert_eq!(
+            r.decode_be::<u8>().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn read_as() {
+        let mut r = [0, 0, 1, 0, 0xff, 0xff, 0xff, 0xff].as_slice();
+        assert_eq!(r.read_le_as::<u32, u64>().unwrap(), 0x0001_0000);
+        assert_eq!(
+            r.read_le_as::<i32, u16>().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn usize_as_u64() {
+        let mut buf = vec![];
+        buf.write_usize_as_u64_be(0x0102).unwrap();
+        assert_eq!(buf, [0, 0, 0, 0, 0, 0, 1, 2]);
+        assert_eq!(buf.as_slice().read_usize_from_u64_be().unwrap(), 0x0102);
+        if usize::BITS < 64 {
+            assert_eq!(
+                [0xff; 8]
+                    .as_slice()
+                    .read_usize_from_u64_be()
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::InvalidData
+            );
+        }
+    }
+
+    #[test]
+    fn option() {
+        let mut buf = vec![];
+        buf.write_option_le(Some(2u16)).unwrap();
+        buf.encode_le(&Some((3u8, None::<u32>))).unwrap();
+        buf.encode_le(&None::<u8>).unwrap();
+        assert_eq!(buf, [1, 2, 0, 1, 3, 0, 0]);
+        let mut r = buf.as_slice();
+        assert_eq!(r.decode_le::<Option<u16>>().unwrap(), Some(2));
+        assert_eq!(
+            r.decode_le::<Option<(u8, Option<u32>)>>().unwrap(),
+            Some((3, None))
+        );
+        assert_eq!(r.read_option_le::<u8>().unwrap(), None);
+        assert_eq!(
+            [2, 0].as_slice().read_option_le::<u8>().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn tuple() {
+        let mut buf = vec![];
+        buf.encode_be(&(1u16, 2u32, 3u8)).unwrap();
+        assert_eq!(buf, [0, 1, 0, 0, 0, 2, 3]);
+        let (count, offset, flags): (u16, u32, u8) = buf.as_slice().decode_be().unwrap();
+        assert_eq!((count, offset, flags), (1, 2, 3));
+        assert_eq!(
+            buf.as_slice()
+                .decode_be::<(u16, u32, u16)>()
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn decode_stream() {
+        let mut r = [0, 1, 2, 0, 3, 4].as_slice();
+        let records = r
+            .decode_stream_be::<(u16, u8)>()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records, [(1, 2), (3, 4)]);
+        assert_eq!(r.decode_stream_le::<(u16, u8)>().count(), 0);
+
+        let mut r = [1, 0, 2, 3, 0].as_slice();
+        let mut it = r.decode_stream_le::<(u16, u8)>();
+        assert_eq!(it.next().unwrap().unwrap(), (1, 2));
+        assert_eq!(
+            it.next().unwrap().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn atomic() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        let counter = AtomicU32::new(0x0102_0304);
+        let mut buf = vec![];
+        buf.encode_be(&counter).unwrap();
+        counter.fetch_add(1, Ordering::Relaxed);
+        buf.encode_le(&counter).unwrap();
+        assert_eq!(buf, [1, 2, 3, 4, 5, 3, 2, 1]);
+        let mut r = buf.as_slice();
+        assert_eq!(r.read_be::<AtomicU32>().unwrap().into_inner(), 0x0102_0304);
+        assert_eq!(
+            r.decode_le::<AtomicU32>().unwrap().into_inner(),
+            0x0102_0305
+        );
+    }
+
+    #[test]
+    fn ip() {
+        let mut r = [127, 0, 0, 1, 1, 0, 0, 127].as_slice();
+        assert_eq!(
+            r.read_be::<std::net::Ipv4Addr>().unwrap(),
+            std::net::Ipv4Addr::LOCALHOST
+        );
+        assert_eq!(
+            r.read_le::<std::net::Ipv4Addr>().unwrap(),
+            std::net::Ipv4Addr::LOCALHOST
+        );
+        let mut buf = vec![];
+        buf.write_be(std::net::Ipv6Addr::LOCALHOST).unwrap();
+        assert_eq!(buf, std::net::Ipv6Addr::LOCALHOST.octets());
+
+        let v4 = std::net::SocketAddrV4::new(std::net::Ipv4Addr::new(10, 0, 0, 1), 0x1234);
+        let mut buf = vec![];
+        buf.encode_be(&v4).unwrap();
+        assert_eq!(buf, [10, 0, 0, 1, 0x12, 0x34]);
+        assert_eq!(
+            buf.as_slice()
+                .decode_be::<std::net::SocketAddrV4>()
+                .unwrap(),
+            v4
+        );
+
+        let v6 = std::net::SocketAddrV6::new(std::net::Ipv6Addr::LOCALHOST, 80, 0, 0);
+        let mut buf = vec![];
+        buf.encode_be(&v6).unwrap();
+        assert_eq!(buf.len(), 18);
+        assert_eq!(
+            buf.as_slice()
+                .decode_be::<std::net::SocketAddrV6>()
+                .unwrap(),
+            v6
+        );
+    }
+
+    #[test]
+    fn duration() {
+        let mut r = [0u8; 10].as_slice();
+        assert_eq!(
+            r.decode_le::<std::time::Duration>().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        let mut r = [0u8, 0, 0, 0, 0, 0, 0, 0, 0x3b, 0x9a, 0xca, 0x00].as_slice();
+        assert_eq!(
+            r.decode_be::<std::time::Duration>().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "half")]
+    fn half() {
+        let mut r = [0x3c, 0x00, 0x00, 0x3f].as_slice();
+        assert_eq!(r.read_be::<half::f16>().unwrap(), half::f16::ONE);
+        assert_eq!(
+            r.read_le::<half::bf16>().unwrap(),
+            half::bf16::from_f32(0.5)
+        );
+        assert_eq!(
+            r.decode_be::<u8>().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "derive")]
+    fn derive_support() {
+        use __derive::{decode_prefixed, encode_prefixed};
+        let err = encode_prefixed::<u8, _, _>(&vec![0u8; 256], &mut vec![], Endian::Big);
+        assert_eq!(err.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        let err = decode_prefixed::<u8, String, _>(&mut &[2, 0xff, 0xff][..], Endian::Big);
+        assert_eq!(err.unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let err = decode_prefixed::<u8, String, _>(&mut &[2, b'a'][..], Endian::Big);
+        assert_eq!(err.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+        #[cfg(feature = "smallvec")]
+        {
+            let mut buf = vec![];
+            let items = smallvec::SmallVec::<[u16; 2]>::from_slice(&[1, 2]);
+            encode_prefixed::<u8, _, _>(&items, &mut buf, Endian::Little).unwrap();
+            assert_eq!(buf, [2, 1, 0, 2, 0]);
+            let decoded: smallvec::SmallVec<[u16; 2]> =
+                decode_prefixed::<u8, _, _>(&mut buf.as_slice(), Endian::Little).unwrap();
+            assert_eq!((decoded, items.spilled()), (items, false));
+        }
+        assert_eq!(
+            __derive::check_magic(*b"RIFX", *b"RIFF")
+                .unwrap_err()
+                .to_string(),
+            "magic doesn't match: expected [52, 49, 46, 46], found [52, 49, 46, 58]"
+        );
+    }
+
+    #[test]
+    fn negotiation() {
+        assert_eq!(
+            detect_negotiation(&mut &[0x0a, 0x0b, 0x0c, 0x0d][..]).unwrap(),
+            Endian::Big
+        );
+        assert_eq!(
+            detect_negotiation(&mut &[0x0d, 0x0c, 0x0b, 0x0a][..]).unwrap(),
+            Endian::Little
+        );
+        assert_eq!(
+            detect_negotiation(&mut &[0x0b, 0x0a, 0x0d, 0x0c][..])
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    #[should_panic = "checkpoint has already been committed"]
+    fn rollback_committed() {
+        let mut w = Checkpointed::new(vec![]);
+        let checkpoint = w.checkpoint();
+        w.write_be(1u8).unwrap();
+        w.commit().unwrap();
+        w.write_be(2u8).unwrap();
+        w.rollback(checkpoint);
+    }
+}
@@ -0,0 +1,119 @@
+//! Decoding from iterators of bytes, e.g those received one at a time from a
+//! serial port or ring buffer.
+//!
+//! ```
+//! use bitendian::iter::ByteIterExt as _;
+//!
+//! let mut uart = [0x12, 0x34, 0x01].into_iter();
+//! let id: u16 = uart.read_be().unwrap();
+//! assert_eq!(id, 0x1234);
+//! assert_eq!(uart.read_le::<u16>(), None);
+//! ```
+//!
+//! Fallible iterators like [`std::io::Bytes`] are supported by
+//! [`TryByteIterExt`].
+
+use crate::{BitEndian, Endian};
+
+/// Extends iterators of bytes with methods for reading in an
+/// endian-dependent way.
+///
+/// Reads return [`None`] if the iterator ends before `N` bytes are yielded.
+/// Any bytes yielded before then are lost.
+pub trait ByteIterExt<const N: usize>: Iterator<Item = u8> {
+    /// Read according to a run-time endianness.
+    fn read_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> Option<T> {
+        let mut bytes = [0u8; N];
+        for byte in &mut bytes {
+            *byte = self.next()?;
+        }
+        Some(T::from_bytes_endian(bytes, endian))
+    }
+    /// Read with [`Endian::Big`].
+    fn read_be<T: BitEndian<N>>(&mut self) -> Option<T> {
+        self.read_endian(Endian::Big)
+    }
+    /// Read with [`Endian::Little`].
+    fn read_le<T: BitEndian<N>>(&mut self) -> Option<T> {
+        self.read_endian(Endian::Little)
+    }
+    /// Read with [`Endian::Native`].
+    fn read_ne<T: BitEndian<N>>(&mut self) -> Option<T> {
+        self.read_endian(Endian::Native)
+    }
+}
+impl<const N: usize, I: Iterator<Item = u8> + ?Sized> ByteIterExt<N> for I {}
+
+/// Extends iterators of [`io::Result<u8>`](std::io::Result), e.g
+/// [`std::io::Bytes`], with methods for reading in an endian-dependent way.
+///
+/// Reads fail with [`std::io::ErrorKind::UnexpectedEof`] if the iterator ends
+/// before `N` bytes are yielded.
+/// Any bytes yielded before then, or before an error, are lost.
+///
+/// ```
+/// use bitendian::iter::TryByteIterExt as _;
+/// use std::io::Read as _;
+///
+/// let mut bytes = [0x00, 0x2a, b'\n'].bytes();
+/// let answer: u16 = bytes.read_be()?;
+/// assert_eq!(answer, 42);
+/// assert_eq!(bytes.next().transpose()?, Some(b'\n'));
+/// # Ok::<_, std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub trait TryByteIterExt<const N: usize>: Iterator<Item = std::io::Result<u8>> {
+    /// Read according to a run-time endianness.
+    fn read_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> std::io::Result<T> {
+        let mut bytes = [0u8; N];
+        for byte in &mut bytes {
+            *byte = self.next().ok_or(std::io::ErrorKind::UnexpectedEof)??;
+        }
+        Ok(T::from_bytes_endian(bytes, endian))
+    }
+    /// Read with [`Endian::Big`].
+    fn read_be<T: BitEndian<N>>(&mut self) -> std::io::Result<T> {
+        self.read_endian(Endian::Big)
+    }
+    /// Read with [`Endian::Little`].
+    fn read_le<T: BitEndian<N>>(&mut self) -> std::io::Result<T> {
+        self.read_endian(Endian::Little)
+    }
+    /// Read with [`Endian::Native`].
+    fn read_ne<T: BitEndian<N>>(&mut self) -> std::io::Result<T> {
+        self.read_endian(Endian::Native)
+    }
+}
+#[cfg(feature = "std")]
+impl<const N: usize, I: Iterator<Item = std::io::Result<u8>> + ?Sized> TryByteIterExt<N> for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial() {
+        let mut it = [1, 2, 3].into_iter();
+        assert_eq!(it.read_endian::<u16>(Endian::Little), Some(0x0201));
+        assert_eq!(it.read_ne::<u32>(), None);
+        assert_eq!(it.next(), None);
+        assert_eq!(core::iter::empty::<u8>().read_be::<[u8; 0]>(), Some([]));
+    }
+
+    #[test]
+    fn fallible() {
+        use std::io;
+
+        let mut it = [Ok(1), Ok(2), Err(io::ErrorKind::Other.into()), Ok(3)].into_iter();
+        assert_eq!(it.read_endian::<u16>(Endian::Big).unwrap(), 0x0102);
+        assert_eq!(
+            it.read_le::<u16>().unwrap_err().kind(),
+            io::ErrorKind::Other
+        );
+        assert_eq!(
+            it.read_ne::<u16>().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+}
@@ -0,0 +1,303 @@
+//! Order-preserving ("memcomparable") key encoding.
+//!
+//! Tuples of [`KeyPart`]s are encoded such that comparing the encoded bytes
+//! lexicographically gives the same result as comparing the original values,
+//! as needed for keys in sorted key-value stores.
+//!
+//! ```
+//! use bitendian::keys;
+//!
+//! let a = keys::encode(&(1u32, -1.5f64, b"apple".to_vec()));
+//! let b = keys::encode(&(1u32, -1.5f64, b"apples".to_vec()));
+//! let c = keys::encode(&(2u32, -9.0f64, b"".to_vec()));
+//! assert!(a < b && b < c);
+//!
+//! let (n, f, s): (u32, f64, Vec<u8>) = keys::decode(&a).unwrap();
+//! assert_eq!((n, f, s.as_slice()), (1, -1.5, &b"apple"[..]));
+//! ```
+//!
+//! The encoding of each part is:
+//! - Unsigned integers: big-endian.
+//! - Signed integers: big-endian, with the sign bit flipped.
+//! - Floats: big-endian, with the sign bit flipped for positive numbers, and
+//!   all bits flipped for negative numbers. This matches [`f64::total_cmp`].
+//! - Byte strings: each `0x00` is escaped as `0x00 0xFF`, and the string is
+//!   terminated by `0x00 0x00`.
+//! - [`bool`]: a single byte, `0` or `1`.
+
+use crate::BitEndian;
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// A value which can be encoded as (part of) an order-preserving key.
+///
+/// Tuples of parts are themselves parts.
+pub trait KeyPart: Sized {
+    /// Append the encoding of `self` to `out`.
+    fn encode_key(&self, out: &mut Vec<u8>);
+    /// Decode a value from the start of `input`, advancing it past the
+    /// consumed bytes.
+    fn decode_key(input: &mut &[u8]) -> Result<Self, DecodeError>;
+}
+
+/// Encode `key` into a new buffer.
+pub fn encode<K: KeyPart>(key: &K) -> Vec<u8> {
+    let mut out = Vec::new();
+    key.encode_key(&mut out);
+    out
+}
+
+/// Decode a whole key from `bytes`, failing if any bytes are left over.
+pub fn decode<K: KeyPart>(mut bytes: &[u8]) -> Result<K, DecodeError> {
+    let key = K::decode_key(&mut bytes)?;
+    match bytes.is_empty() {
+        true => Ok(key),
+        false => Err(DecodeError::TrailingBytes),
+    }
+}
+
+/// Error returned when decoding a malformed key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input ended part-way through a value.
+    UnexpectedEnd,
+    /// A byte which is not valid for the part being decoded.
+    InvalidByte(u8),
+    /// A string was not valid UTF-8.
+    InvalidUtf8,
+    /// The input was longer than the key.
+    TrailingBytes,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => f.write_str("unexpected end of key"),
+            DecodeError::InvalidByte(it) => {
+                f.write_fmt(format_args!("invalid byte {:#04x} in key", it))
+            }
+            DecodeError::InvalidUtf8 => f.write_str("invalid UTF-8 in key"),
+            DecodeError::TrailingBytes => f.write_str("trailing bytes after key"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+fn take<const N: usize>(input: &mut &[u8]) -> Result<[u8; N], DecodeError> {
+    match input.split_first_chunk::<N>() {
+        Some((head, tail)) => {
+            *input = tail;
+            Ok(*head)
+        }
+        None => Err(DecodeError::UnexpectedEnd),
+    }
+}
+
+macro_rules! unsigned {
+    ($($width:literal { $($ty:ty),* $(,)? }),* $(,)?) => {
+        $($(
+            impl KeyPart for $ty {
+                fn encode_key(&self, out: &mut Vec<u8>) {
+                    out.extend(BitEndian::<$width>::to_be_bytes(*self))
+                }
+                fn decode_key(input: &mut &[u8]) -> Result<Self, DecodeError> {
+                    take(input).map(<$ty as BitEndian<$width>>::from_be_bytes)
+                }
+            }
+        )*)*
+    };
+}
+unsigned!(1 { u8 }, 2 { u16 }, 4 { u32 }, 8 { u64 }, 16 { u128 });
+
+macro_rules! signed {
+    ($($width:literal { $($ty:ty => $unsigned:ty),* $(,)? }),* $(,)?) => {
+        $($(
+            impl KeyPart for $ty {
+                fn encode_key(&self, out: &mut Vec<u8>) {
+                    let flipped = (*self as $unsigned) ^ !(<$unsigned>::MAX >> 1);
+                    flipped.encode_key(out)
+                }
+                fn decode_key(input: &mut &[u8]) -> Result<Self, DecodeError> {
+                    let flipped = <$unsigned>::decode_key(input)?;
+                    Ok((flipped ^ !(<$unsigned>::MAX >> 1)) as $ty)
+                }
+            }
+        )*)*
+    };
+}
+signed!(
+    1 { i8 => u8 },
+    2 { i16 => u16 },
+    4 { i32 => u32 },
+    8 { i64 => u64 },
+    16 { i128 => u128 },
+);
+
+macro_rules! float {
+    ($($ty:ty => $unsigned:ty),* $(,)?) => {
+        $(
+            impl KeyPart for $ty {
+                fn encode_key(&self, out: &mut Vec<u8>) {
+                    let bits = self.to_bits();
+                    let sign = !(<$unsigned>::MAX >> 1);
+                    let flipped = match bits & sign {
+                        0 => bits ^ sign,
+                        _ => !bits,
+                    };
+                    flipped.encode_key(out)
+                }
+                fn decode_key(input: &mut &[u8]) -> Result<Self, DecodeError> {
+                    let flipped = <$unsigned>::decode_key(input)?;
+                    let sign = !(<$unsigned>::MAX >> 1);
+                    let bits = match flipped & sign {
+                        0 => !flipped,
+                        _ => flipped ^ sign,
+                    };
+                    Ok(<$ty>::from_bits(bits))
+                }
+            }
+        )*
+    };
+}
+float!(f32 => u32, f64 => u64);
+
+impl KeyPart for bool {
+    fn encode_key(&self, out: &mut Vec<u8>) {
+        out.push(u8::from(*self))
+    }
+    fn decode_key(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match take::<1>(input)? {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            [other] => Err(DecodeError::InvalidByte(other)),
+        }
+    }
+}
+
+/// Encode a byte string, as described in the [module documentation](mod@self).
+pub fn encode_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+    for byte in bytes {
+        match byte {
+            0 => out.extend([0x00, 0xff]),
+            _ => out.push(*byte),
+        }
+    }
+    out.extend([0x00, 0x00])
+}
+
+/// Decode a byte string, as described in the [module documentation](mod@self).
+pub fn decode_bytes(input: &mut &[u8]) -> Result<Vec<u8>, DecodeError> {
+    let mut out = Vec::new();
+    loop {
+        match take::<1>(input)? {
+            [0] => match take::<1>(input)? {
+                [0x00] => return Ok(out),
+                [0xff] => out.push(0),
+                [other] => return Err(DecodeError::InvalidByte(other)),
+            },
+            [byte] => out.push(byte),
+        }
+    }
+}
+
+impl KeyPart for Vec<u8> {
+    fn encode_key(&self, out: &mut Vec<u8>) {
+        encode_bytes(self, out)
+    }
+    fn decode_key(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        decode_bytes(input)
+    }
+}
+
+impl KeyPart for String {
+    fn encode_key(&self, out: &mut Vec<u8>) {
+        encode_bytes(self.as_bytes(), out)
+    }
+    fn decode_key(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        String::from_utf8(decode_bytes(input)?).map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+macro_rules! tuple {
+    ($($ty:ident),*) => {
+        impl<$($ty: KeyPart),*> KeyPart for ($($ty,)*) {
+            #[allow(non_snake_case)]
+            fn encode_key(&self, out: &mut Vec<u8>) {
+                let ($($ty,)*) = self;
+                $($ty.encode_key(out);)*
+            }
+            fn decode_key(input: &mut &[u8]) -> Result<Self, DecodeError> {
+                Ok(($($ty::decode_key(input)?,)*))
+            }
+        }
+    };
+}
+tuple!(A);
+tuple!(A, B);
+tuple!(A, B, C);
+tuple!(A, B, C, D);
+tuple!(A, B, C, D, E);
+tuple!(A, B, C, D, E, F);
+tuple!(A, B, C, D, E, F, G);
+tuple!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_order_preserved<K: KeyPart + PartialOrd + fmt::Debug>(values: &[K]) {
+        for a in values {
+            for b in values {
+                assert_eq!(
+                    a.partial_cmp(b),
+                    encode(a).partial_cmp(&encode(b)),
+                    "{a:?} vs {b:?}"
+                );
+            }
+            assert_eq!(&decode::<K>(&encode(a)).unwrap(), a);
+        }
+    }
+
+    #[test]
+    fn order() {
+        assert_order_preserved(&[i32::MIN, -2, -1, 0, 1, 2, i32::MAX]);
+        assert_order_preserved(&[0u64, 1, 255, 256, u64::MAX]);
+        assert_order_preserved(&[
+            f64::NEG_INFINITY,
+            -1.5,
+            -f64::MIN_POSITIVE,
+            0.0,
+            f64::MIN_POSITIVE,
+            1.5,
+            f64::INFINITY,
+        ]);
+        assert_order_preserved(&[
+            b"".to_vec(),
+            b"\0".to_vec(),
+            b"\0\0".to_vec(),
+            b"\0\x01".to_vec(),
+            b"a".to_vec(),
+            b"a\0".to_vec(),
+            b"ab".to_vec(),
+        ]);
+        assert_order_preserved(&[
+            (String::from("a"), -1i8),
+            (String::from("a"), 1i8),
+            (String::from("ab"), -1i8),
+        ]);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(decode::<u16>(&[0]), Err(DecodeError::UnexpectedEnd));
+        assert_eq!(decode::<u8>(&[0, 0]), Err(DecodeError::TrailingBytes));
+        assert_eq!(
+            decode::<Vec<u8>>(&[b'a', 0, 1]),
+            Err(DecodeError::InvalidByte(1))
+        );
+        assert_eq!(decode::<bool>(&[2]), Err(DecodeError::InvalidByte(2)));
+    }
+}
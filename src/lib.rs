@@ -24,6 +24,9 @@
 //! # futures::executor::block_on(doit()).unwrap();
 //! ```
 //!
+//! Without `std`, the `alloc` feature keeps the helpers which only need an
+//! allocator, like `keys`, `slice::interleave_vec` and `cursor::ReadAllocExt`.
+//!
 //! # Comparison with [`byteorder`].
 //! - This crate leverages type inference to avoid [defining dozens of e.g write_uXX methods].
 //!   ```
@@ -55,28 +58,104 @@
 //! - This crate supports run-time endianness.
 //! - This crate supports [`futures::io`] and [`tokio::io`] via the `futures`
 //!   and `tokio` features respectively.
-//! - This crate only supports rust's built-in types, not, eg. [`u24`].
+//! - This crate supports odd-width integers like [`U24`] as types, rather than
+//!   with extra methods like [`read_u24`].
 //! - Both crates support `#![no_std]` by disabling the default `std` feature.
 //!
 //! [`byteorder`]: https://docs.rs/byteorder/1/byteorder/index.html
 //! [defining dozens of e.g write_uXX methods]: https://docs.rs/byteorder/1/byteorder/trait.WriteBytesExt.html#method.write_u8
-//! [`u24`]: https://docs.rs/byteorder/1/byteorder/trait.WriteBytesExt.html#method.write_u24
+//! [`read_u24`]: https://docs.rs/byteorder/1/byteorder/trait.ReadBytesExt.html#method.read_u24
 //! [`futures::io`]: https://docs.rs/futures/0.3/futures/io/
 //! [`tokio::io`]: https://docs.rs/tokio/1/tokio/io/index.html
 
 #![cfg_attr(do_doc_cfg, feature(doc_cfg))]
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(rustdoc::redundant_explicit_links)] // required for `cargo-rdme`
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod ascii;
+pub mod bcd;
+#[cfg(feature = "bitflags")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "bitflags")))]
+pub mod bitflags;
+pub mod bits;
+#[cfg(feature = "bitvec")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "bitvec")))]
+pub mod bitvec;
+#[cfg(feature = "bytes")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "bytes")))]
+pub mod bytes;
+pub mod cbor;
+pub mod cursor;
+pub mod data_view;
+#[cfg(feature = "ffi")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "ffi")))]
+pub mod ffi;
+#[cfg(feature = "fixed")]
+mod fixed_impl;
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub mod fortran;
 #[cfg(feature = "futures")]
 #[cfg_attr(do_doc_cfg, doc(cfg(feature = "futures")))]
 pub mod futures;
+#[cfg(feature = "heapless")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "heapless")))]
+pub mod heapless;
+pub mod ieee754;
+pub mod int;
 #[cfg(feature = "std")]
 #[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
 pub mod io;
+pub mod iter;
+#[cfg(feature = "alloc")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod keys;
+pub mod limbs;
+#[cfg(feature = "memmap2")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "memmap2")))]
+pub mod memmap2;
+#[cfg(feature = "miette")]
+mod miette_impl;
+pub mod msgpack;
+pub mod ntp;
+pub mod ordered_varint;
+pub mod preamble;
+pub mod ring;
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal_impl;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "serde")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "serde")))]
+pub use serde_impl::{serde_be, serde_le};
+#[cfg(feature = "portable-simd")]
+mod simd_impl;
+pub mod slice;
+pub mod tlv;
 #[cfg(feature = "tokio")]
 #[cfg_attr(do_doc_cfg, doc(cfg(feature = "tokio")))]
 pub mod tokio;
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub mod unix_time;
+#[cfg(feature = "uuid")]
+mod uuid_impl;
+#[cfg(feature = "ux")]
+mod ux_impl;
+mod wrapper;
+pub mod x87;
+
+pub use int::{I24, I40, I48, I56, U24, U40, U48, U56};
+pub use wrapper::{
+    Be, F32Be, F32Le, F64Be, F64Le, I128Be, I128Le, I16Be, I16Le, I32Be, I32Le, I64Be, I64Le, Le,
+    U128Be, U128Le, U16Be, U16Le, U32Be, U32Le, U64Be, U64Le,
+};
 
 /// A type that can be infallibly written to or read from an array in an
 /// [endian](Endian)-dependent manner.
@@ -137,7 +216,227 @@ pub trait BitEndian<const N: usize> {
             Endian::Native => Self::from_ne_bytes(bytes),
         }
     }
+
+    /// Decode a value from the start of `bytes` according to a run-time
+    /// endianness, returning the rest of the input.
+    ///
+    /// ```
+    /// use bitendian::BitEndian as _;
+    ///
+    /// let input = [0, 1, 2, 0xff];
+    /// let (id, rest) = u16::parse_be(&input).unwrap();
+    /// let (len, rest) = u8::parse_le(rest).unwrap();
+    /// assert_eq!((id, len, rest), (1, 2, &[0xff][..]));
+    /// assert!(u16::parse_be(rest).is_err());
+    /// ```
+    fn parse_endian(bytes: &[u8], endian: Endian) -> Result<(Self, &[u8]), cursor::UnexpectedEnd>
+    where
+        Self: Sized,
+    {
+        match bytes.split_first_chunk::<N>() {
+            Some((chunk, rest)) => Ok((Self::from_bytes_endian(*chunk, endian), rest)),
+            None => Err(cursor::UnexpectedEnd {
+                offset: 0,
+                needed: N,
+                remaining: bytes.len(),
+            }),
+        }
+    }
+    /// Parse with [`Endian::Big`], as in [`Self::parse_endian`].
+    fn parse_be(bytes: &[u8]) -> Result<(Self, &[u8]), cursor::UnexpectedEnd>
+    where
+        Self: Sized,
+    {
+        Self::parse_endian(bytes, Endian::Big)
+    }
+    /// Parse with [`Endian::Little`], as in [`Self::parse_endian`].
+    fn parse_le(bytes: &[u8]) -> Result<(Self, &[u8]), cursor::UnexpectedEnd>
+    where
+        Self: Sized,
+    {
+        Self::parse_endian(bytes, Endian::Little)
+    }
+    /// Parse with [`Endian::Native`], as in [`Self::parse_endian`].
+    fn parse_ne(bytes: &[u8]) -> Result<(Self, &[u8]), cursor::UnexpectedEnd>
+    where
+        Self: Sized,
+    {
+        Self::parse_endian(bytes, Endian::Native)
+    }
+}
+
+/// A type that can be read from an array in an [endian](Endian)-dependent
+/// manner, but which may fail, e.g because not every bit pattern is valid.
+///
+/// Every [`BitEndian`] type implements this trait, with an [`Infallible`](core::convert::Infallible)
+/// error.
+///
+/// Use the `try_read_*` methods of e.g `io::ReadExt` to read implementors,
+/// where failures are reported as [`std::io::ErrorKind::InvalidData`].
+pub trait TryBitEndian<const N: usize>: Sized {
+    /// The error returned when the bytes don't represent a valid value.
+    type Error;
+
+    /// Create a value from its representation as a byte array in little
+    /// endian.
+    fn try_from_le_bytes(bytes: [u8; N]) -> Result<Self, Self::Error>;
+    /// Create a value from its representation as a byte array in big
+    /// (network) endian.
+    fn try_from_be_bytes(bytes: [u8; N]) -> Result<Self, Self::Error>;
+    /// Create a value from its memory representation as a byte array in
+    /// native endianness.
+    fn try_from_ne_bytes(bytes: [u8; N]) -> Result<Self, Self::Error>;
+    /// Delegates to the appropriate method according to a run-time endianness.
+    fn try_from_bytes_endian(bytes: [u8; N], endian: Endian) -> Result<Self, Self::Error> {
+        match endian {
+            Endian::Little => Self::try_from_le_bytes(bytes),
+            Endian::Big | Endian::Network => Self::try_from_be_bytes(bytes),
+            Endian::Native => Self::try_from_ne_bytes(bytes),
+        }
+    }
+}
+
+impl<const N: usize, T: BitEndian<N>> TryBitEndian<N> for T {
+    type Error = core::convert::Infallible;
+
+    fn try_from_le_bytes(bytes: [u8; N]) -> Result<Self, Self::Error> {
+        Ok(T::from_le_bytes(bytes))
+    }
+    fn try_from_be_bytes(bytes: [u8; N]) -> Result<Self, Self::Error> {
+        Ok(T::from_be_bytes(bytes))
+    }
+    fn try_from_ne_bytes(bytes: [u8; N]) -> Result<Self, Self::Error> {
+        Ok(T::from_ne_bytes(bytes))
+    }
+}
+
+/// `char`s are represented as their `u32` code point, which must be a valid
+/// [`char`].
+///
+/// Encode with e.g `u32::from(c).to_be_bytes()`.
+impl TryBitEndian<4> for char {
+    type Error = core::char::CharTryFromError;
+
+    fn try_from_le_bytes(bytes: [u8; 4]) -> Result<Self, Self::Error> {
+        char::try_from(u32::from_le_bytes(bytes))
+    }
+    fn try_from_be_bytes(bytes: [u8; 4]) -> Result<Self, Self::Error> {
+        char::try_from(u32::from_be_bytes(bytes))
+    }
+    fn try_from_ne_bytes(bytes: [u8; 4]) -> Result<Self, Self::Error> {
+        char::try_from(u32::from_ne_bytes(bytes))
+    }
+}
+
+/// `bool`s are represented as a single byte, which must be `0` or `1`.
+///
+/// See [`LenientBool`] to accept any non-zero byte as `true`.
+///
+/// Encode with e.g `u8::from(b)`.
+impl TryBitEndian<1> for bool {
+    type Error = InvalidBool;
+
+    fn try_from_le_bytes([byte]: [u8; 1]) -> Result<Self, Self::Error> {
+        match byte {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(InvalidBool(byte)),
+        }
+    }
+    fn try_from_be_bytes(bytes: [u8; 1]) -> Result<Self, Self::Error> {
+        Self::try_from_le_bytes(bytes)
+    }
+    fn try_from_ne_bytes(bytes: [u8; 1]) -> Result<Self, Self::Error> {
+        Self::try_from_le_bytes(bytes)
+    }
+}
+
+/// Error returned when decoding a `bool` from a byte other than `0` or `1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidBool(pub u8);
+
+impl core::fmt::Display for InvalidBool {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("invalid bool {:#04x}", self.0))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidBool {}
+
+/// A `bool` which is decoded as `true` from any non-zero byte, and encoded as
+/// `0` or `1`.
+///
+/// ```
+/// use bitendian::{BitEndian as _, LenientBool};
+///
+/// assert!(LenientBool::from_be_bytes([0xff]).0);
+/// assert_eq!(LenientBool(true).to_be_bytes(), [1]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct LenientBool(pub bool);
+
+impl BitEndian<1> for LenientBool {
+    fn to_le_bytes(self) -> [u8; 1] {
+        [u8::from(self.0)]
+    }
+    fn to_be_bytes(self) -> [u8; 1] {
+        self.to_le_bytes()
+    }
+    fn to_ne_bytes(self) -> [u8; 1] {
+        self.to_le_bytes()
+    }
+    fn from_le_bytes([byte]: [u8; 1]) -> Self {
+        Self(byte != 0)
+    }
+    fn from_be_bytes(bytes: [u8; 1]) -> Self {
+        Self::from_le_bytes(bytes)
+    }
+    fn from_ne_bytes(bytes: [u8; 1]) -> Self {
+        Self::from_le_bytes(bytes)
+    }
+}
+
+macro_rules! try_bit_endian_non_zero {
+    ($($ty:ident($inner:ty)),* $(,)?) => {
+        $(
+            /// Zero is invalid.
+            impl TryBitEndian<{ core::mem::size_of::<$inner>() }> for core::num::$ty {
+                type Error = core::num::TryFromIntError;
+
+                fn try_from_le_bytes(
+                    bytes: [u8; core::mem::size_of::<$inner>()],
+                ) -> Result<Self, Self::Error> {
+                    Self::try_from(<$inner>::from_le_bytes(bytes))
+                }
+                fn try_from_be_bytes(
+                    bytes: [u8; core::mem::size_of::<$inner>()],
+                ) -> Result<Self, Self::Error> {
+                    Self::try_from(<$inner>::from_be_bytes(bytes))
+                }
+                fn try_from_ne_bytes(
+                    bytes: [u8; core::mem::size_of::<$inner>()],
+                ) -> Result<Self, Self::Error> {
+                    Self::try_from(<$inner>::from_ne_bytes(bytes))
+                }
+            }
+        )*
+    };
 }
+try_bit_endian_non_zero!(
+    NonZeroU8(u8),
+    NonZeroI8(i8),
+    NonZeroU16(u16),
+    NonZeroI16(i16),
+    NonZeroU32(u32),
+    NonZeroI32(i32),
+    NonZeroU64(u64),
+    NonZeroI64(i64),
+    NonZeroU128(u128),
+    NonZeroI128(i128),
+    NonZeroUsize(usize),
+    NonZeroIsize(isize),
+);
 
 macro_rules! bit_endian {
     ($($width:literal { $($ty:ty),* $(,)? }),* $(,)?) => {
@@ -176,6 +475,191 @@ bit_endian!(
     16 { u128, i128 },
 );
 
+#[cfg(feature = "half")]
+bit_endian!(2 { half::f16, half::bf16 });
+
+macro_rules! bit_endian_newtype {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl<const N: usize, T: BitEndian<N>> BitEndian<N> for core::num::$ty<T> {
+                fn to_le_bytes(self) -> [u8; N] {
+                    self.0.to_le_bytes()
+                }
+                fn to_be_bytes(self) -> [u8; N] {
+                    self.0.to_be_bytes()
+                }
+                fn to_ne_bytes(self) -> [u8; N] {
+                    self.0.to_ne_bytes()
+                }
+
+                fn from_le_bytes(bytes: [u8; N]) -> Self {
+                    Self(T::from_le_bytes(bytes))
+                }
+                fn from_be_bytes(bytes: [u8; N]) -> Self {
+                    Self(T::from_be_bytes(bytes))
+                }
+                fn from_ne_bytes(bytes: [u8; N]) -> Self {
+                    Self(T::from_ne_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+bit_endian_newtype!(Wrapping, Saturating);
+
+/// Bytes are passed through unchanged, regardless of endianness.
+///
+/// This lets raw fields like magic numbers, reserved areas and hashes be read
+/// alongside numbers.
+/// ```
+/// use bitendian::io::ReadExt as _;
+///
+/// # fn doit() -> std::io::Result<()> {
+/// let mut r = &b"GIF89a\x40\x01"[..];
+/// let magic: [u8; 6] = r.read_le()?;
+/// let width: u16 = r.read_le()?;
+/// assert_eq!((&magic, width), (b"GIF89a", 320));
+/// # Ok(())
+/// # }
+/// # doit().unwrap()
+/// ```
+impl<const N: usize> BitEndian<N> for [u8; N] {
+    fn to_le_bytes(self) -> [u8; N] {
+        self
+    }
+    fn to_be_bytes(self) -> [u8; N] {
+        self
+    }
+    fn to_ne_bytes(self) -> [u8; N] {
+        self
+    }
+
+    fn from_le_bytes(bytes: [u8; N]) -> Self {
+        bytes
+    }
+    fn from_be_bytes(bytes: [u8; N]) -> Self {
+        bytes
+    }
+    fn from_ne_bytes(bytes: [u8; N]) -> Self {
+        bytes
+    }
+}
+
+macro_rules! bit_endian_via {
+    ($($width:literal { $($ty:ty as $repr:ty),* $(,)? }),* $(,)?) => {
+        $($(
+            #[doc = concat!("As a `", stringify!($repr), "`, so big-endian is network order.")]
+            impl BitEndian<$width> for $ty {
+                fn to_le_bytes(self) -> [u8; $width] {
+                    <$repr>::from(self).to_le_bytes()
+                }
+                fn to_be_bytes(self) -> [u8; $width] {
+                    <$repr>::from(self).to_be_bytes()
+                }
+                fn to_ne_bytes(self) -> [u8; $width] {
+                    <$repr>::from(self).to_ne_bytes()
+                }
+
+                fn from_le_bytes(bytes: [u8; $width]) -> Self {
+                    Self::from(<$repr>::from_le_bytes(bytes))
+                }
+                fn from_be_bytes(bytes: [u8; $width]) -> Self {
+                    Self::from(<$repr>::from_be_bytes(bytes))
+                }
+                fn from_ne_bytes(bytes: [u8; $width]) -> Self {
+                    Self::from(<$repr>::from_ne_bytes(bytes))
+                }
+            }
+        )*)*
+    };
+}
+bit_endian_via!(
+    4 { core::net::Ipv4Addr as u32 },
+    16 { core::net::Ipv6Addr as u128 },
+);
+
+macro_rules! bit_endian_atomic {
+    ($($width:literal { $($ty:ty as $repr:ty),* $(,)? }),* $(,)?) => {
+        $($(
+            /// The contained value.
+            ///
+            /// Encoding consumes the atomic, so to snapshot a shared atomic,
+            /// load it first, or use [`io::Encode`](crate::io::Encode).
+            impl BitEndian<$width> for $ty {
+                fn to_le_bytes(self) -> [u8; $width] {
+                    self.into_inner().to_le_bytes()
+                }
+                fn to_be_bytes(self) -> [u8; $width] {
+                    self.into_inner().to_be_bytes()
+                }
+                fn to_ne_bytes(self) -> [u8; $width] {
+                    self.into_inner().to_ne_bytes()
+                }
+
+                fn from_le_bytes(bytes: [u8; $width]) -> Self {
+                    Self::new(<$repr>::from_le_bytes(bytes))
+                }
+                fn from_be_bytes(bytes: [u8; $width]) -> Self {
+                    Self::new(<$repr>::from_be_bytes(bytes))
+                }
+                fn from_ne_bytes(bytes: [u8; $width]) -> Self {
+                    Self::new(<$repr>::from_ne_bytes(bytes))
+                }
+            }
+        )*)*
+    };
+}
+#[cfg(target_has_atomic = "8")]
+bit_endian_atomic!(1 {
+    core::sync::atomic::AtomicU8 as u8,
+    core::sync::atomic::AtomicI8 as i8,
+});
+#[cfg(target_has_atomic = "16")]
+bit_endian_atomic!(2 {
+    core::sync::atomic::AtomicU16 as u16,
+    core::sync::atomic::AtomicI16 as i16,
+});
+#[cfg(target_has_atomic = "32")]
+bit_endian_atomic!(4 {
+    core::sync::atomic::AtomicU32 as u32,
+    core::sync::atomic::AtomicI32 as i32,
+});
+#[cfg(target_has_atomic = "64")]
+bit_endian_atomic!(8 {
+    core::sync::atomic::AtomicU64 as u64,
+    core::sync::atomic::AtomicI64 as i64,
+});
+#[cfg(all(target_has_atomic = "ptr", target_pointer_width = "16"))]
+bit_endian_atomic!(2 {
+    core::sync::atomic::AtomicUsize as usize,
+    core::sync::atomic::AtomicIsize as isize,
+});
+#[cfg(all(target_has_atomic = "ptr", target_pointer_width = "32"))]
+bit_endian_atomic!(4 {
+    core::sync::atomic::AtomicUsize as usize,
+    core::sync::atomic::AtomicIsize as isize,
+});
+#[cfg(all(target_has_atomic = "ptr", target_pointer_width = "64"))]
+bit_endian_atomic!(8 {
+    core::sync::atomic::AtomicUsize as usize,
+    core::sync::atomic::AtomicIsize as isize,
+});
+
+#[cfg(feature = "portable-atomic")]
+bit_endian_atomic!(
+    1 { portable_atomic::AtomicU8 as u8, portable_atomic::AtomicI8 as i8 },
+    2 { portable_atomic::AtomicU16 as u16, portable_atomic::AtomicI16 as i16 },
+    4 { portable_atomic::AtomicU32 as u32, portable_atomic::AtomicI32 as i32, portable_atomic::AtomicF32 as f32 },
+    8 { portable_atomic::AtomicU64 as u64, portable_atomic::AtomicI64 as i64, portable_atomic::AtomicF64 as f64 },
+    16 { portable_atomic::AtomicU128 as u128, portable_atomic::AtomicI128 as i128 },
+);
+#[cfg(all(feature = "portable-atomic", target_pointer_width = "16"))]
+bit_endian_atomic!(2 { portable_atomic::AtomicUsize as usize, portable_atomic::AtomicIsize as isize });
+#[cfg(all(feature = "portable-atomic", target_pointer_width = "32"))]
+bit_endian_atomic!(4 { portable_atomic::AtomicUsize as usize, portable_atomic::AtomicIsize as isize });
+#[cfg(all(feature = "portable-atomic", target_pointer_width = "64"))]
+bit_endian_atomic!(8 { portable_atomic::AtomicUsize as usize, portable_atomic::AtomicIsize as isize });
+
 #[cfg(target_pointer_width = "8")]
 bit_endian!(1 { usize, isize });
 #[cfg(target_pointer_width = "16")]
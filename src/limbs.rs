@@ -0,0 +1,133 @@
+//! Multi-word integers stored as arrays of fixed-width limbs.
+//!
+//! Both the endianness of each limb and the order of the limbs are
+//! configurable, since formats disagree on both.
+//! In memory, limbs are always least significant first.
+//!
+//! ```
+//! use bitendian::{limbs::{decode_limbs, LimbOrder}, Endian};
+//!
+//! let bytes = [0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02];
+//! let big: [u32; 2] = decode_limbs(&bytes, Endian::Big, LimbOrder::MostSignificantFirst);
+//! assert_eq!(big, [2, 1]);
+//! let mixed: [u32; 2] = decode_limbs(&bytes, Endian::Big, LimbOrder::LeastSignificantFirst);
+//! assert_eq!(mixed, [1, 2]);
+//! ```
+
+use crate::{BitEndian, Endian};
+
+/// The order in which limbs are stored.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LimbOrder {
+    /// The most significant limb comes first, as in a big-endian integer.
+    MostSignificantFirst,
+    /// The least significant limb comes first, as in a little-endian integer.
+    LeastSignificantFirst,
+}
+
+impl LimbOrder {
+    /// The index into `bytes` of limb `i` (counting from least significant)
+    /// of `count`.
+    fn chunk(self, i: usize, count: usize) -> usize {
+        match self {
+            LimbOrder::MostSignificantFirst => count - 1 - i,
+            LimbOrder::LeastSignificantFirst => i,
+        }
+    }
+}
+
+/// Decode `L` limbs of `N` bytes each, returning them least significant first.
+///
+/// # Panics
+/// - If `bytes` is not exactly `N * L` bytes long.
+pub fn decode_limbs<const N: usize, T: BitEndian<N>, const L: usize>(
+    bytes: &[u8],
+    endian: Endian,
+    order: LimbOrder,
+) -> [T; L] {
+    assert_eq!(bytes.len(), N * L, "expected {} limbs of {} bytes", L, N);
+    let (chunks, _) = bytes.as_chunks::<N>();
+    core::array::from_fn(|i| T::from_bytes_endian(chunks[order.chunk(i, L)], endian))
+}
+
+/// Encode `limbs`, which are least significant first, into `out`.
+///
+/// # Panics
+/// - If `out` is not exactly `N * L` bytes long.
+pub fn encode_limbs<const N: usize, T: BitEndian<N> + Copy, const L: usize>(
+    limbs: &[T; L],
+    endian: Endian,
+    order: LimbOrder,
+    out: &mut [u8],
+) {
+    assert_eq!(out.len(), N * L, "expected {} limbs of {} bytes", L, N);
+    let (chunks, _) = out.as_chunks_mut::<N>();
+    for (i, limb) in limbs.iter().enumerate() {
+        chunks[order.chunk(i, L)] = limb.to_bytes_endian(endian);
+    }
+}
+
+/// Read `L` limbs, as in [`decode_limbs`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn read_limbs<const N: usize, T: BitEndian<N>, const L: usize>(
+    reader: &mut (impl std::io::Read + ?Sized),
+    endian: Endian,
+    order: LimbOrder,
+) -> std::io::Result<[T; L]> {
+    let mut chunks = [[0u8; N]; L];
+    reader.read_exact(chunks.as_flattened_mut())?;
+    Ok(decode_limbs(chunks.as_flattened(), endian, order))
+}
+
+/// Write `limbs`, as in [`encode_limbs`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn write_limbs<const N: usize, T: BitEndian<N> + Copy, const L: usize>(
+    writer: &mut (impl std::io::Write + ?Sized),
+    limbs: &[T; L],
+    endian: Endian,
+    order: LimbOrder,
+) -> std::io::Result<()> {
+    let mut chunks = [[0u8; N]; L];
+    encode_limbs(limbs, endian, order, chunks.as_flattened_mut());
+    writer.write_all(chunks.as_flattened())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let value = 0x0011_2233_4455_6677_8899_aabb_ccdd_eeffu128;
+        let limbs = [0x8899_aabb_ccdd_eeffu64, 0x0011_2233_4455_6677];
+        for (endian, order, expected) in [
+            (
+                Endian::Big,
+                LimbOrder::MostSignificantFirst,
+                value.to_be_bytes(),
+            ),
+            (
+                Endian::Little,
+                LimbOrder::LeastSignificantFirst,
+                value.to_le_bytes(),
+            ),
+        ] {
+            let mut buf = vec![];
+            write_limbs(&mut buf, &limbs, endian, order).unwrap();
+            assert_eq!(buf, expected);
+            let actual: [u64; 2] = read_limbs(&mut buf.as_slice(), endian, order).unwrap();
+            assert_eq!(actual, limbs);
+        }
+
+        let mut buf = [0; 8];
+        encode_limbs(
+            &[0x0102u16, 0x0304, 0x0506, 0x0708],
+            Endian::Little,
+            LimbOrder::MostSignificantFirst,
+            &mut buf,
+        );
+        assert_eq!(buf, [0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01]);
+    }
+}
@@ -0,0 +1,185 @@
+//! Typed views into [`memmap2`](https://docs.rs/memmap2/0.9/memmap2/)
+//! mappings, for the map-then-parse workflow.
+//!
+//! The views are checked for length and alignment.
+//! Data stored in a foreign endianness can be converted in place with
+//! [`view_fixed_up`].
+//! To leave the file untouched, map it with [`MmapOptions::map_copy`].
+//!
+//! ```
+//! use bitendian::{memmap2::{view, Error}, Endian};
+//! use memmap2::MmapMut;
+//!
+//! let mut map = MmapMut::map_anon(16)?;
+//! map[..8].copy_from_slice(&1u64.to_ne_bytes());
+//! let map = map.make_read_only()?;
+//!
+//! let values = view::<u64>(&map, 0, 2)?;
+//! assert_eq!(values, [1, 0]);
+//! assert_eq!(view::<u64>(&map, 4, 1), Err(Error::Misaligned { offset: 4, align: 8 }));
+//! assert_eq!(view::<u64>(&map, 8, 2), Err(Error::OutOfBounds { offset: 8, size: 16, len: 16 }));
+//! # Ok::<_, std::io::Error>(())
+//! ```
+//!
+//! [`MmapOptions::map_copy`]: ::memmap2::MmapOptions::map_copy
+
+use crate::{slice::swap_endian_in_place, BitEndian, Endian};
+use ::memmap2::{Mmap, MmapMut};
+use core::{fmt, mem, ops::Range, slice};
+
+/// Types for which any correctly sized and aligned bytes are a valid value,
+/// so may be viewed in place.
+///
+/// This trait is sealed.
+pub trait Plain: Copy + sealed::Sealed {}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! plain {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $ty {}
+            impl Plain for $ty {}
+        )*
+    };
+}
+plain!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, f32, f64);
+
+/// View `count` `T`s in native endianness, starting `offset` bytes into `map`.
+pub fn view<T: Plain>(map: &Mmap, offset: usize, count: usize) -> Result<&[T], Error> {
+    let bytes = &map[region::<T>(map, offset, count)?];
+    // SAFETY:
+    // - `region` checked the length and alignment.
+    // - `T: Plain`, so any bytes are valid.
+    Ok(unsafe { slice::from_raw_parts(bytes.as_ptr().cast(), count) })
+}
+
+/// Mutably view `count` `T`s in native endianness, as in [`view`].
+pub fn view_mut<T: Plain>(
+    map: &mut MmapMut,
+    offset: usize,
+    count: usize,
+) -> Result<&mut [T], Error> {
+    let region = region::<T>(map, offset, count)?;
+    let bytes = &mut map[region];
+    // SAFETY: as in `view`
+    Ok(unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr().cast(), count) })
+}
+
+/// Mutably view `count` `T`s which are stored according to `endian`,
+/// converting them to native endianness in place first.
+///
+/// This writes to `map`, and so to the underlying file for shared mappings.
+pub fn view_fixed_up<const N: usize, T: Plain + BitEndian<N>>(
+    map: &mut MmapMut,
+    offset: usize,
+    count: usize,
+    endian: Endian,
+) -> Result<&mut [T], Error> {
+    let values = view_mut(map, offset, count)?;
+    swap_endian_in_place(values, endian);
+    Ok(values)
+}
+
+fn region<T>(map: &[u8], offset: usize, count: usize) -> Result<Range<usize>, Error> {
+    let size = count.saturating_mul(mem::size_of::<T>());
+    let region = offset..offset.saturating_add(size);
+    let Some(bytes) = map.get(region.clone()) else {
+        return Err(Error::OutOfBounds {
+            offset,
+            size,
+            len: map.len(),
+        });
+    };
+    match bytes.as_ptr().cast::<T>().is_aligned() {
+        true => Ok(region),
+        false => Err(Error::Misaligned {
+            offset,
+            align: mem::align_of::<T>(),
+        }),
+    }
+}
+
+/// Error returned when a view doesn't fit in a mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// The view extends past the end of the mapping.
+    OutOfBounds {
+        /// The offset of the view.
+        offset: usize,
+        /// The length of the view, in bytes.
+        size: usize,
+        /// The length of the mapping, in bytes.
+        len: usize,
+    },
+    /// The view doesn't start at a multiple of the type's alignment.
+    Misaligned {
+        /// The offset of the view.
+        offset: usize,
+        /// The alignment of the type.
+        align: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::OutOfBounds { offset, size, len } => f.write_fmt(format_args!(
+                "view of {} bytes at offset {} is out of bounds for a mapping of {} bytes",
+                size, offset, len
+            )),
+            Error::Misaligned { offset, align } => f.write_fmt(format_args!(
+                "view at offset {} isn't aligned to {} bytes",
+                offset, align
+            )),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// As [`std::io::ErrorKind::UnexpectedEof`] or
+/// [`std::io::ErrorKind::InvalidData`].
+impl From<Error> for std::io::Error {
+    fn from(value: Error) -> Self {
+        let kind = match value {
+            Error::OutOfBounds { .. } => std::io::ErrorKind::UnexpectedEof,
+            Error::Misaligned { .. } => std::io::ErrorKind::InvalidData,
+        };
+        std::io::Error::new(kind, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fix_up() {
+        let mut map = MmapMut::map_anon(12).unwrap();
+        map[4..].copy_from_slice(&[0, 0, 0, 1, 0, 0, 0, 2]);
+        let values = view_fixed_up::<4, u32>(&mut map, 4, 2, Endian::Big).unwrap();
+        assert_eq!(values, [1, 2]);
+        values[0] = 3;
+        assert_eq!(map[4..8], 3u32.to_ne_bytes());
+        assert_eq!(
+            view_mut::<u32>(&mut map, 8, 2),
+            Err(Error::OutOfBounds {
+                offset: 8,
+                size: 8,
+                len: 12
+            })
+        );
+        assert_eq!(
+            view_mut::<u32>(&mut map, usize::MAX, usize::MAX).unwrap_err(),
+            Error::OutOfBounds {
+                offset: usize::MAX,
+                size: usize::MAX,
+                len: 12
+            }
+        );
+        assert_eq!(view_mut::<u8>(&mut map, 12, 0), Ok(&mut [][..]));
+    }
+}
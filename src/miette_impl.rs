@@ -0,0 +1,59 @@
+//! Positioned errors carry a labeled span, so a [`miette::Report`] with the
+//! input attached as its source code points at the failure.
+
+use crate::{ascii, cursor};
+use miette::{Diagnostic, LabeledSpan};
+use std::{fmt::Display, iter};
+
+impl Diagnostic for cursor::UnexpectedEnd {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new("bitendian::cursor::unexpected_end"))
+    }
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(iter::once(LabeledSpan::new(
+            Some(format!("needed {} bytes here", self.needed)),
+            self.offset,
+            self.remaining,
+        ))))
+    }
+}
+
+impl Diagnostic for ascii::ParseError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new("bitendian::ascii::parse_error"))
+    }
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = match self.is_overflow() {
+            true => "number too large",
+            false => "invalid digit",
+        };
+        Some(Box::new(iter::once(LabeledSpan::new(
+            Some(label.into()),
+            self.offset(),
+            1,
+        ))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ascii, cursor::ByteReader};
+    use miette::{Diagnostic as _, LabeledSpan};
+
+    #[test]
+    fn labels() {
+        let mut reader = ByteReader::new(&[0, 1, 2]);
+        reader.advance(2).unwrap();
+        let e = reader.read_bytes(4).unwrap_err();
+        assert_eq!(
+            e.labels().unwrap().collect::<Vec<_>>(),
+            [LabeledSpan::new(Some("needed 4 bytes here".into()), 2, 1)]
+        );
+
+        let e = ascii::parse_octal::<u32>(b"0008\0").unwrap_err();
+        assert_eq!(
+            e.labels().unwrap().collect::<Vec<_>>(),
+            [LabeledSpan::new(Some("invalid digit".into()), 3, 1)]
+        );
+    }
+}
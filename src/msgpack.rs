@@ -0,0 +1,313 @@
+//! [MessagePack](https://github.com/msgpack/msgpack/blob/master/spec.md#int-format-family)
+//! numbers, for hand-rolled framing which doesn't need a full MessagePack
+//! implementation.
+//!
+//! Integers are encoded in the shortest of the fixint, int and uint formats,
+//! floats with the `float 32` and `float 64` markers.
+//! Payloads are big-endian.
+//!
+//! ```
+//! use bitendian::msgpack::{encode_i64, decode, Number, MAX_LEN};
+//!
+//! let mut buf = [0; MAX_LEN];
+//! assert_eq!(encode_i64(-5, &mut buf), [0xfb]);
+//! assert_eq!(encode_i64(-200, &mut buf), [0xd1, 0xff, 0x38]);
+//! assert_eq!(encode_i64(200, &mut buf), [0xcc, 0xc8]);
+//!
+//! assert_eq!(decode(&[0xd1, 0xff, 0x38]), Ok((Number::Signed(-200), &[][..])));
+//! ```
+
+use crate::BitEndian;
+use core::fmt;
+
+/// The longest possible encoding, a marker and eight bytes of payload.
+pub const MAX_LEN: usize = 9;
+
+const UINT8: u8 = 0xcc;
+const UINT16: u8 = 0xcd;
+const UINT32: u8 = 0xce;
+const UINT64: u8 = 0xcf;
+const INT8: u8 = 0xd0;
+const INT16: u8 = 0xd1;
+const INT32: u8 = 0xd2;
+const INT64: u8 = 0xd3;
+const FLOAT32: u8 = 0xca;
+const FLOAT64: u8 = 0xcb;
+
+/// A decoded number.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    /// A positive fixint, or a `uint` format.
+    Unsigned(u64),
+    /// A negative fixint, or an `int` format.
+    Signed(i64),
+    /// A `float 32`.
+    F32(f32),
+    /// A `float 64`.
+    F64(f64),
+}
+
+/// Encode `value` into `buf` in the shortest form, returning the used
+/// portion.
+pub fn encode_u64(value: u64, buf: &mut [u8; MAX_LEN]) -> &[u8] {
+    match value {
+        0..=0x7f => put(buf, value as u8, []),
+        0x80..=0xff => put(buf, UINT8, [value as u8]),
+        0x100..=0xffff => put(buf, UINT16, (value as u16).to_be_bytes()),
+        0x1_0000..=0xffff_ffff => put(buf, UINT32, (value as u32).to_be_bytes()),
+        _ => put(buf, UINT64, value.to_be_bytes()),
+    }
+}
+
+/// Encode `value` into `buf` in the shortest form, returning the used
+/// portion.
+///
+/// Non-negative numbers are encoded as in [`encode_u64`].
+pub fn encode_i64(value: i64, buf: &mut [u8; MAX_LEN]) -> &[u8] {
+    match value {
+        0.. => encode_u64(value as u64, buf),
+        -32..=-1 => put(buf, value as u8, []),
+        -0x80..=-33 => put(buf, INT8, [value as u8]),
+        -0x8000..=-0x81 => put(buf, INT16, (value as i16).to_be_bytes()),
+        -0x8000_0000..=-0x8001 => put(buf, INT32, (value as i32).to_be_bytes()),
+        _ => put(buf, INT64, value.to_be_bytes()),
+    }
+}
+
+/// Encode `value` into `buf`, returning the used portion.
+pub fn encode_f32(value: f32, buf: &mut [u8; MAX_LEN]) -> &[u8] {
+    put(buf, FLOAT32, value.to_be_bytes())
+}
+
+/// Encode `value` into `buf`, returning the used portion.
+pub fn encode_f64(value: f64, buf: &mut [u8; MAX_LEN]) -> &[u8] {
+    put(buf, FLOAT64, value.to_be_bytes())
+}
+
+fn put<const N: usize>(buf: &mut [u8; MAX_LEN], marker: u8, payload: [u8; N]) -> &[u8] {
+    buf[0] = marker;
+    buf[1..=N].copy_from_slice(&payload);
+    &buf[..=N]
+}
+
+/// Decode a number from the start of `bytes`, returning the rest of the
+/// input.
+///
+/// Numbers which are not in the shortest form are accepted.
+pub fn decode(bytes: &[u8]) -> Result<(Number, &[u8]), DecodeError> {
+    let (marker, rest) = bytes.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+    let len = payload_len(*marker)?;
+    if rest.len() < len {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    let (payload, rest) = rest.split_at(len);
+    let number = match *marker {
+        0x00..=0x7f => Number::Unsigned((*marker).into()),
+        0xe0..=0xff => Number::Signed((*marker as i8).into()),
+        UINT8 => Number::Unsigned(get::<1, u8>(payload).into()),
+        UINT16 => Number::Unsigned(get::<2, u16>(payload).into()),
+        UINT32 => Number::Unsigned(get::<4, u32>(payload).into()),
+        UINT64 => Number::Unsigned(get(payload)),
+        INT8 => Number::Signed(get::<1, i8>(payload).into()),
+        INT16 => Number::Signed(get::<2, i16>(payload).into()),
+        INT32 => Number::Signed(get::<4, i32>(payload).into()),
+        INT64 => Number::Signed(get(payload)),
+        FLOAT32 => Number::F32(get(payload)),
+        _ => Number::F64(get(payload)),
+    };
+    Ok((number, rest))
+}
+
+/// Decode an integer from the start of `bytes`, returning the rest of the
+/// input.
+pub fn decode_u64(bytes: &[u8]) -> Result<(u64, &[u8]), DecodeError> {
+    match decode(bytes)? {
+        (Number::Unsigned(it), rest) => Ok((it, rest)),
+        (Number::Signed(it), rest) => match u64::try_from(it) {
+            Ok(it) => Ok((it, rest)),
+            Err(_) => Err(DecodeError::Overflow),
+        },
+        _ => Err(DecodeError::NotAnInteger(bytes[0])),
+    }
+}
+
+/// Decode an integer from the start of `bytes`, returning the rest of the
+/// input.
+pub fn decode_i64(bytes: &[u8]) -> Result<(i64, &[u8]), DecodeError> {
+    match decode(bytes)? {
+        (Number::Signed(it), rest) => Ok((it, rest)),
+        (Number::Unsigned(it), rest) => match i64::try_from(it) {
+            Ok(it) => Ok((it, rest)),
+            Err(_) => Err(DecodeError::Overflow),
+        },
+        _ => Err(DecodeError::NotAnInteger(bytes[0])),
+    }
+}
+
+fn payload_len(marker: u8) -> Result<usize, DecodeError> {
+    match marker {
+        0x00..=0x7f | 0xe0..=0xff => Ok(0),
+        UINT8 | INT8 => Ok(1),
+        UINT16 | INT16 => Ok(2),
+        UINT32 | INT32 | FLOAT32 => Ok(4),
+        UINT64 | INT64 | FLOAT64 => Ok(8),
+        _ => Err(DecodeError::NotANumber(marker)),
+    }
+}
+
+fn get<const N: usize, T: BitEndian<N>>(payload: &[u8]) -> T {
+    let mut buf = [0u8; N];
+    buf.copy_from_slice(payload);
+    T::from_be_bytes(buf)
+}
+
+/// Error returned when decoding a malformed number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input ended part-way through a number.
+    UnexpectedEnd,
+    /// The marker is not that of a number.
+    NotANumber(u8),
+    /// The marker is that of a float, but an integer was expected.
+    NotAnInteger(u8),
+    /// The integer does not fit in the output type.
+    Overflow,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => f.write_str("unexpected end of number"),
+            DecodeError::NotANumber(it) => {
+                f.write_fmt(format_args!("marker {:#04x} is not a number", it))
+            }
+            DecodeError::NotAnInteger(it) => {
+                f.write_fmt(format_args!("marker {:#04x} is not an integer", it))
+            }
+            DecodeError::Overflow => f.write_str("integer too large"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// Write `value`, as in [`encode_u64`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn write_u64(writer: &mut (impl std::io::Write + ?Sized), value: u64) -> std::io::Result<()> {
+    writer.write_all(encode_u64(value, &mut [0; MAX_LEN]))
+}
+
+/// Write `value`, as in [`encode_i64`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn write_i64(writer: &mut (impl std::io::Write + ?Sized), value: i64) -> std::io::Result<()> {
+    writer.write_all(encode_i64(value, &mut [0; MAX_LEN]))
+}
+
+/// Write `value`, as in [`encode_f32`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn write_f32(writer: &mut (impl std::io::Write + ?Sized), value: f32) -> std::io::Result<()> {
+    writer.write_all(encode_f32(value, &mut [0; MAX_LEN]))
+}
+
+/// Write `value`, as in [`encode_f64`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn write_f64(writer: &mut (impl std::io::Write + ?Sized), value: f64) -> std::io::Result<()> {
+    writer.write_all(encode_f64(value, &mut [0; MAX_LEN]))
+}
+
+/// Read a number, as in [`decode`].
+///
+/// Malformed numbers are reported as [`std::io::ErrorKind::InvalidData`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn read(reader: &mut (impl std::io::Read + ?Sized)) -> std::io::Result<Number> {
+    let mut buf = [0; MAX_LEN];
+    reader.read_exact(&mut buf[..1])?;
+    let len =
+        payload_len(buf[0]).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    reader.read_exact(&mut buf[1..=len])?;
+    decode(&buf[..=len])
+        .map(|(number, _)| number)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal() {
+        for (value, len) in [
+            (i64::MIN, 9),
+            (-0x8000_0001, 9),
+            (-0x8000_0000, 5),
+            (-0x8001, 5),
+            (-0x8000, 3),
+            (-0x81, 3),
+            (-0x80, 2),
+            (-33, 2),
+            (-32, 1),
+            (0, 1),
+            (0x7f, 1),
+            (0x80, 2),
+            (0x100, 3),
+            (0x1_0000, 5),
+            (0x1_0000_0000, 9),
+            (i64::MAX, 9),
+        ] {
+            let mut buf = [0; MAX_LEN];
+            let encoded = encode_i64(value, &mut buf);
+            assert_eq!(encoded.len(), len, "{value}");
+            assert_eq!(decode_i64(encoded), Ok((value, &[][..])));
+        }
+        let mut buf = [0; MAX_LEN];
+        assert_eq!(
+            decode_u64(encode_u64(u64::MAX, &mut buf)),
+            Ok((u64::MAX, &[][..]))
+        );
+        assert_eq!(
+            decode(encode_f32(1.5, &mut buf)),
+            Ok((Number::F32(1.5), &[][..]))
+        );
+        assert_eq!(
+            encode_f64(1.0, &mut buf),
+            [0xcb, 0x3f, 0xf0, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(decode(&[0xcd, 0x01]), Err(DecodeError::UnexpectedEnd));
+        assert_eq!(decode(&[0xc0]), Err(DecodeError::NotANumber(0xc0)));
+        assert_eq!(decode_u64(&[0xff]), Err(DecodeError::Overflow));
+        assert_eq!(
+            decode_i64(&[0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            Err(DecodeError::Overflow)
+        );
+        assert_eq!(
+            decode_i64(&[0xca, 0, 0, 0, 0]),
+            Err(DecodeError::NotAnInteger(0xca))
+        );
+    }
+
+    #[test]
+    fn io() {
+        let mut buf = vec![];
+        write_i64(&mut buf, -200).unwrap();
+        write_u64(&mut buf, 300).unwrap();
+        write_f64(&mut buf, 0.5).unwrap();
+        write_f32(&mut buf, -0.5).unwrap();
+        let mut buf = buf.as_slice();
+        assert_eq!(read(&mut buf).unwrap(), Number::Signed(-200));
+        assert_eq!(read(&mut buf).unwrap(), Number::Unsigned(300));
+        assert_eq!(read(&mut buf).unwrap(), Number::F64(0.5));
+        assert_eq!(read(&mut buf).unwrap(), Number::F32(-0.5));
+        assert!(buf.is_empty());
+    }
+}
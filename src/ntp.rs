@@ -0,0 +1,218 @@
+//! [NTP](https://www.rfc-editor.org/rfc/rfc5905#section-6) fixed-point time
+//! formats.
+//!
+//! [`Timestamp`] is the 32.32 timestamp format, and [`Short`] the 16.16 short
+//! format used for delays and dispersions.
+//! Both implement [`BitEndian`], so can be read and written like integers.
+//!
+//! ```
+//! use bitendian::{ntp::Timestamp, BitEndian};
+//! use std::time::{Duration, SystemTime};
+//!
+//! let timestamp = Timestamp::from_system_time(SystemTime::UNIX_EPOCH).unwrap();
+//! assert_eq!(timestamp.to_be_bytes(), [0x83, 0xaa, 0x7e, 0x80, 0, 0, 0, 0]);
+//!
+//! let half = Timestamp::from_be_bytes([0, 0, 0, 0, 0x80, 0, 0, 0]);
+//! assert_eq!(half.to_duration(), Duration::from_millis(500));
+//! ```
+
+use crate::BitEndian;
+use core::time::Duration;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// A 64-bit NTP timestamp, seconds and a binary fraction of a second.
+///
+/// Timestamps count from the start of an NTP era, the first of which began
+/// at 1900-01-01 00:00 UTC, and the second of which begins in 2036.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Timestamp {
+    /// Whole seconds since the start of the era.
+    pub seconds: u32,
+    /// Fractions of a second, in units of `2^-32` seconds.
+    pub fraction: u32,
+}
+
+impl Timestamp {
+    /// The Unix epoch, in the first era.
+    pub const UNIX_EPOCH: Self = Self {
+        seconds: 2_208_988_800,
+        fraction: 0,
+    };
+
+    /// Return the time since the start of the era, rounded to the nearest
+    /// nanosecond.
+    pub fn to_duration(self) -> Duration {
+        Duration::new(
+            self.seconds.into(),
+            fraction_to_nanos(self.fraction.into(), 32),
+        )
+    }
+
+    /// Convert a time since the start of an era, rounding down to the nearest
+    /// fraction.
+    ///
+    /// Returns [`None`] if `duration` is longer than an era.
+    pub fn from_duration(duration: Duration) -> Option<Self> {
+        Some(Self {
+            seconds: duration.as_secs().try_into().ok()?,
+            fraction: nanos_to_fraction(duration.subsec_nanos(), 32) as u32,
+        })
+    }
+
+    /// Interpret this timestamp as being in the first era.
+    #[cfg(feature = "std")]
+    #[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+    pub fn to_system_time(self) -> std::time::SystemTime {
+        std::time::SystemTime::UNIX_EPOCH + self.to_duration() - Self::UNIX_EPOCH.to_duration()
+    }
+
+    /// Convert a time to the timestamp for its era.
+    ///
+    /// Returns [`None`] for times before 1900.
+    #[cfg(feature = "std")]
+    #[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+    pub fn from_system_time(time: std::time::SystemTime) -> Option<Self> {
+        let since_1900 = match time.duration_since(std::time::SystemTime::UNIX_EPOCH) {
+            Ok(after) => Self::UNIX_EPOCH.to_duration().checked_add(after)?,
+            Err(before) => Self::UNIX_EPOCH
+                .to_duration()
+                .checked_sub(before.duration())?,
+        };
+        let era = Duration::from_secs(1 << 32);
+        Self::from_duration(Duration::new(
+            since_1900.as_secs() % era.as_secs(),
+            since_1900.subsec_nanos(),
+        ))
+    }
+
+    /// The timestamp as a single fixed-point number.
+    pub const fn to_bits(self) -> u64 {
+        (self.seconds as u64) << 32 | self.fraction as u64
+    }
+
+    /// The inverse of [`Self::to_bits`].
+    pub const fn from_bits(bits: u64) -> Self {
+        Self {
+            seconds: (bits >> 32) as u32,
+            fraction: bits as u32,
+        }
+    }
+}
+
+/// A 32-bit NTP short format duration, seconds and a binary fraction of a
+/// second.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Short {
+    /// Whole seconds.
+    pub seconds: u16,
+    /// Fractions of a second, in units of `2^-16` seconds.
+    pub fraction: u16,
+}
+
+impl Short {
+    /// Return the duration, rounded to the nearest nanosecond.
+    pub fn to_duration(self) -> Duration {
+        Duration::new(
+            self.seconds.into(),
+            fraction_to_nanos(self.fraction.into(), 16),
+        )
+    }
+
+    /// Convert a duration, rounding down to the nearest fraction.
+    ///
+    /// Returns [`None`] if `duration` is too long.
+    pub fn from_duration(duration: Duration) -> Option<Self> {
+        Some(Self {
+            seconds: duration.as_secs().try_into().ok()?,
+            fraction: nanos_to_fraction(duration.subsec_nanos(), 16) as u16,
+        })
+    }
+
+    /// The duration as a single fixed-point number.
+    pub const fn to_bits(self) -> u32 {
+        (self.seconds as u32) << 16 | self.fraction as u32
+    }
+
+    /// The inverse of [`Self::to_bits`].
+    pub const fn from_bits(bits: u32) -> Self {
+        Self {
+            seconds: (bits >> 16) as u16,
+            fraction: bits as u16,
+        }
+    }
+}
+
+fn fraction_to_nanos(fraction: u64, bits: u32) -> u32 {
+    // round to nearest, so that durations survive a round trip
+    ((fraction * NANOS_PER_SEC + (1 << (bits - 1))) >> bits) as u32
+}
+
+fn nanos_to_fraction(nanos: u32, bits: u32) -> u64 {
+    (u64::from(nanos) << bits) / NANOS_PER_SEC
+}
+
+macro_rules! bit_endian {
+    ($($width:literal { $ty:ty => $bits:ty }),* $(,)?) => {
+        $(
+            impl BitEndian<$width> for $ty {
+                fn to_le_bytes(self) -> [u8; $width] {
+                    self.to_bits().to_le_bytes()
+                }
+                fn to_be_bytes(self) -> [u8; $width] {
+                    self.to_bits().to_be_bytes()
+                }
+                fn to_ne_bytes(self) -> [u8; $width] {
+                    self.to_bits().to_ne_bytes()
+                }
+
+                fn from_le_bytes(bytes: [u8; $width]) -> Self {
+                    Self::from_bits(<$bits>::from_le_bytes(bytes))
+                }
+                fn from_be_bytes(bytes: [u8; $width]) -> Self {
+                    Self::from_bits(<$bits>::from_be_bytes(bytes))
+                }
+                fn from_ne_bytes(bytes: [u8; $width]) -> Self {
+                    Self::from_bits(<$bits>::from_ne_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+bit_endian!(8 { Timestamp => u64 }, 4 { Short => u32 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    #[test]
+    fn duration() {
+        let duration = Duration::new(5, 250_000_000);
+        let timestamp = Timestamp::from_duration(duration).unwrap();
+        assert_eq!(timestamp.fraction, 1 << 30);
+        assert_eq!(timestamp.to_duration(), duration);
+
+        let short = Short::from_duration(duration).unwrap();
+        assert_eq!(short.to_be_bytes(), [0, 5, 0x40, 0]);
+        assert_eq!(Short::from_be_bytes([0, 5, 0x40, 0]), short);
+        assert_eq!(short.to_duration(), duration);
+
+        assert_eq!(Short::from_duration(Duration::from_secs(1 << 16)), None);
+        assert_eq!(Timestamp::from_duration(Duration::from_secs(1 << 32)), None);
+    }
+
+    #[test]
+    fn system_time() {
+        let now = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+        let timestamp = Timestamp::from_system_time(now).unwrap();
+        assert_eq!(timestamp.to_system_time(), now);
+
+        // the second era
+        let era_1 = SystemTime::UNIX_EPOCH + Duration::from_secs((1 << 32) - 2_208_988_800);
+        assert_eq!(
+            Timestamp::from_system_time(era_1),
+            Some(Timestamp::default())
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Order-preserving variable-length integers, in the style of the
+//! [FoundationDB tuple layer](https://github.com/apple/foundationdb/blob/main/design/tuple.md#integer).
+//!
+//! Each integer is encoded as a prefix byte which gives its sign and length,
+//! followed by its minimal big-endian representation.
+//! Encoded integers compare (lexicographically) in the same order as the
+//! integers they represent, so they are suitable for index keys where a fixed
+//! eight byte encoding would waste space.
+//!
+//! ```
+//! use bitendian::ordered_varint::{encode_i64, decode_i64, MAX_LEN};
+//!
+//! let mut a = [0; MAX_LEN];
+//! let mut b = [0; MAX_LEN];
+//! let a = encode_i64(-300, &mut a);
+//! let b = encode_i64(7, &mut b);
+//! assert_eq!(a, [0x12, 0xfe, 0xd3]);
+//! assert_eq!(b, [0x15, 0x07]);
+//! assert!(a < b);
+//!
+//! assert_eq!(decode_i64(a), Ok((-300, &[][..])));
+//! ```
+
+use core::fmt;
+
+/// The longest possible encoding, a prefix byte and eight bytes of magnitude.
+pub const MAX_LEN: usize = 9;
+
+/// The prefix for zero.
+/// Positive numbers of length `n` use `ZERO + n`, negative numbers `ZERO - n`.
+const ZERO: u8 = 0x14;
+
+/// Encode `value` into `buf`, returning the used portion.
+pub fn encode_u64(value: u64, buf: &mut [u8; MAX_LEN]) -> &[u8] {
+    let len = significant_bytes(value);
+    buf[0] = ZERO + len as u8;
+    buf[1..=len].copy_from_slice(&value.to_be_bytes()[8 - len..]);
+    &buf[..=len]
+}
+
+/// Encode `value` into `buf`, returning the used portion.
+///
+/// Negative numbers store the one's complement of their magnitude, so that
+/// larger magnitudes sort first.
+pub fn encode_i64(value: i64, buf: &mut [u8; MAX_LEN]) -> &[u8] {
+    match value.is_negative() {
+        false => encode_u64(value as u64, buf),
+        true => {
+            let magnitude = value.unsigned_abs();
+            let len = significant_bytes(magnitude);
+            buf[0] = ZERO - len as u8;
+            buf[1..=len].copy_from_slice(&(!magnitude).to_be_bytes()[8 - len..]);
+            &buf[..=len]
+        }
+    }
+}
+
+/// Decode a non-negative integer from the start of `bytes`, returning the
+/// rest of the input.
+pub fn decode_u64(bytes: &[u8]) -> Result<(u64, &[u8]), DecodeError> {
+    let (prefix, rest) = bytes.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+    let len = match prefix.checked_sub(ZERO) {
+        Some(len) if len as usize <= 8 => len as usize,
+        _ => return Err(DecodeError::InvalidPrefix(*prefix)),
+    };
+    let (magnitude, rest) = split_magnitude(rest, len)?;
+    match magnitude.first() {
+        Some(0) => Err(DecodeError::NotMinimal),
+        _ => Ok((from_be_slice(magnitude), rest)),
+    }
+}
+
+/// Decode an integer from the start of `bytes`, returning the rest of the
+/// input.
+pub fn decode_i64(bytes: &[u8]) -> Result<(i64, &[u8]), DecodeError> {
+    let prefix = *bytes.first().ok_or(DecodeError::UnexpectedEnd)?;
+    match prefix.checked_sub(ZERO) {
+        Some(_) => {
+            let (value, rest) = decode_u64(bytes)?;
+            let value = i64::try_from(value).map_err(|_| DecodeError::Overflow)?;
+            Ok((value, rest))
+        }
+        None => {
+            let len = match ZERO - prefix {
+                len if len as usize <= 8 => len as usize,
+                _ => return Err(DecodeError::InvalidPrefix(prefix)),
+            };
+            let (complement, rest) = split_magnitude(&bytes[1..], len)?;
+            if complement.first() == Some(&0xff) {
+                return Err(DecodeError::NotMinimal);
+            }
+            let magnitude = !from_be_slice(complement) & (u64::MAX >> (64 - 8 * len));
+            let value = 0i64
+                .checked_sub_unsigned(magnitude)
+                .ok_or(DecodeError::Overflow)?;
+            Ok((value, rest))
+        }
+    }
+}
+
+fn significant_bytes(value: u64) -> usize {
+    (64 - value.leading_zeros() as usize).div_ceil(8)
+}
+
+fn split_magnitude(bytes: &[u8], len: usize) -> Result<(&[u8], &[u8]), DecodeError> {
+    match bytes.len() >= len {
+        true => Ok(bytes.split_at(len)),
+        false => Err(DecodeError::UnexpectedEnd),
+    }
+}
+
+fn from_be_slice(bytes: &[u8]) -> u64 {
+    let mut buf = [0u8; 8];
+    buf[8 - bytes.len()..].copy_from_slice(bytes);
+    u64::from_be_bytes(buf)
+}
+
+/// Error returned when decoding a malformed integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input ended part-way through an integer.
+    UnexpectedEnd,
+    /// The prefix byte is not that of an integer.
+    InvalidPrefix(u8),
+    /// The integer has redundant leading bytes, so would not sort correctly.
+    NotMinimal,
+    /// The integer does not fit in the output type.
+    Overflow,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => f.write_str("unexpected end of integer"),
+            DecodeError::InvalidPrefix(it) => {
+                f.write_fmt(format_args!("invalid integer prefix {:#04x}", it))
+            }
+            DecodeError::NotMinimal => f.write_str("integer is not minimally encoded"),
+            DecodeError::Overflow => f.write_str("integer too large"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// Write `value`, as in [`encode_u64`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn write_u64(writer: &mut (impl std::io::Write + ?Sized), value: u64) -> std::io::Result<()> {
+    writer.write_all(encode_u64(value, &mut [0; MAX_LEN]))
+}
+
+/// Read a non-negative integer, as in [`decode_u64`].
+///
+/// Malformed integers are reported as [`std::io::ErrorKind::InvalidData`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn read_u64(reader: &mut (impl std::io::Read + ?Sized)) -> std::io::Result<u64> {
+    decode_u64(read_encoded(reader, &mut [0; MAX_LEN])?)
+        .map(|(value, _)| value)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Write `value`, as in [`encode_i64`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn write_i64(writer: &mut (impl std::io::Write + ?Sized), value: i64) -> std::io::Result<()> {
+    writer.write_all(encode_i64(value, &mut [0; MAX_LEN]))
+}
+
+/// Read an integer, as in [`decode_i64`].
+///
+/// Malformed integers are reported as [`std::io::ErrorKind::InvalidData`].
+#[cfg(feature = "std")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+pub fn read_i64(reader: &mut (impl std::io::Read + ?Sized)) -> std::io::Result<i64> {
+    decode_i64(read_encoded(reader, &mut [0; MAX_LEN])?)
+        .map(|(value, _)| value)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(feature = "std")]
+fn read_encoded<'a>(
+    reader: &mut (impl std::io::Read + ?Sized),
+    buf: &'a mut [u8; MAX_LEN],
+) -> std::io::Result<&'a [u8]> {
+    reader.read_exact(&mut buf[..1])?;
+    let len = usize::from(buf[0].abs_diff(ZERO)).min(MAX_LEN - 1);
+    reader.read_exact(&mut buf[1..=len])?;
+    Ok(&buf[..=len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let values = [
+            i64::MIN,
+            i64::MIN + 1,
+            -65536,
+            -65535,
+            -256,
+            -255,
+            -1,
+            0,
+            1,
+            255,
+            256,
+            i64::MAX,
+        ];
+        for a in values {
+            let mut buf_a = [0; MAX_LEN];
+            let encoded_a = encode_i64(a, &mut buf_a);
+            assert_eq!(decode_i64(encoded_a), Ok((a, &[][..])));
+            for b in values {
+                let mut buf_b = [0; MAX_LEN];
+                assert_eq!(a.cmp(&b), encoded_a.cmp(encode_i64(b, &mut buf_b)));
+            }
+        }
+        assert_eq!(encode_u64(0, &mut [0; MAX_LEN]), [ZERO]);
+        assert_eq!(
+            decode_u64(encode_u64(u64::MAX, &mut [0; MAX_LEN])),
+            Ok((u64::MAX, &[][..]))
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            decode_u64(&[0x16, 0x00, 0x01]),
+            Err(DecodeError::NotMinimal)
+        );
+        assert_eq!(decode_i64(&[0x13, 0xff]), Err(DecodeError::NotMinimal));
+        assert_eq!(decode_u64(&[0x16, 0x01]), Err(DecodeError::UnexpectedEnd));
+        assert_eq!(decode_i64(&[0x00]), Err(DecodeError::InvalidPrefix(0)));
+        assert_eq!(
+            decode_i64(encode_u64(u64::MAX, &mut [0; MAX_LEN])),
+            Err(DecodeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn io() {
+        let mut buf = vec![];
+        write_i64(&mut buf, -300).unwrap();
+        write_u64(&mut buf, 0).unwrap();
+        let mut buf = buf.as_slice();
+        assert_eq!(read_i64(&mut buf).unwrap(), -300);
+        assert_eq!(read_u64(&mut buf).unwrap(), 0);
+        assert!(buf.is_empty());
+    }
+}
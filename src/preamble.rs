@@ -0,0 +1,194 @@
+//! The "magic, endianness, version" preamble which starts many file formats.
+//!
+//! The layout is:
+//! - The magic bytes.
+//! - A single byte giving the endianness of the rest of the file, `l` or `B`
+//!   by default (as in D-Bus).
+//! - A `u16` version, in that endianness.
+//!
+//! ```
+//! use bitendian::{preamble::Preamble, Endian};
+//!
+//! # fn doit() -> std::io::Result<()> {
+//! const FORMAT: Preamble = Preamble::new(b"MYFMT", 1..=3);
+//!
+//! let mut file = vec![];
+//! FORMAT.write(&mut file, 2, Endian::Little)?;
+//! assert_eq!(file, b"MYFMTl\x02\x00");
+//!
+//! let parsed = FORMAT.read(&mut file.as_slice())?;
+//! assert_eq!(parsed.version, 2);
+//! assert_eq!(parsed.endian, Endian::Little);
+//! // ...then read the rest of the file with `parsed.endian`
+//! # Ok(())
+//! # }
+//! # doit().unwrap()
+//! ```
+
+use crate::{BitEndian as _, Endian};
+use core::{fmt, ops::RangeInclusive};
+
+/// Describes a preamble, see the [module documentation](mod@self).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Preamble<'a> {
+    /// The bytes which identify the format.
+    pub magic: &'a [u8],
+    /// The versions which are accepted when parsing.
+    pub versions: RangeInclusive<u16>,
+    /// The byte which marks little-endian files.
+    pub little_marker: u8,
+    /// The byte which marks big-endian files.
+    pub big_marker: u8,
+}
+
+/// A parsed preamble.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Parsed {
+    /// The file's version.
+    pub version: u16,
+    /// The endianness of the rest of the file, either [`Endian::Little`] or
+    /// [`Endian::Big`].
+    pub endian: Endian,
+}
+
+impl<'a> Preamble<'a> {
+    /// A preamble with the default endianness markers.
+    pub const fn new(magic: &'a [u8], versions: RangeInclusive<u16>) -> Self {
+        Self {
+            magic,
+            versions,
+            little_marker: b'l',
+            big_marker: b'B',
+        }
+    }
+
+    /// The length of the preamble, in bytes.
+    pub const fn encoded_len(&self) -> usize {
+        self.magic.len() + 3
+    }
+
+    /// Parse and validate a preamble from the start of `bytes`, returning the
+    /// rest of the input.
+    pub fn parse<'b>(&self, bytes: &'b [u8]) -> Result<(Parsed, &'b [u8]), Error> {
+        if bytes.len() < self.encoded_len() {
+            return Err(Error::UnexpectedEnd);
+        }
+        let (magic, rest) = bytes.split_at(self.magic.len());
+        if magic != self.magic {
+            return Err(Error::BadMagic);
+        }
+        let endian = match rest[0] {
+            it if it == self.little_marker => Endian::Little,
+            it if it == self.big_marker => Endian::Big,
+            other => return Err(Error::UnknownEndian(other)),
+        };
+        let version = u16::from_bytes_endian([rest[1], rest[2]], endian);
+        if !self.versions.contains(&version) {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        Ok((Parsed { version, endian }, &rest[3..]))
+    }
+
+    /// Read and validate a preamble.
+    ///
+    /// Invalid preambles are reported as [`std::io::ErrorKind::InvalidData`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+    pub fn read(&self, reader: &mut (impl std::io::Read + ?Sized)) -> std::io::Result<Parsed> {
+        let mut buf = vec![0; self.encoded_len()];
+        reader.read_exact(&mut buf)?;
+        self.parse(&buf)
+            .map(|(parsed, _)| parsed)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Write a preamble.
+    ///
+    /// The version is not checked against [`Self::versions`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+    pub fn write(
+        &self,
+        writer: &mut (impl std::io::Write + ?Sized),
+        version: u16,
+        endian: Endian,
+    ) -> std::io::Result<()> {
+        let marker = match endian.is_little() {
+            true => self.little_marker,
+            false => self.big_marker,
+        };
+        let [a, b] = version.to_bytes_endian(endian);
+        writer.write_all(self.magic)?;
+        writer.write_all(&[marker, a, b])
+    }
+}
+
+/// Error returned when parsing an invalid preamble.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input is shorter than the preamble.
+    UnexpectedEnd,
+    /// The magic bytes don't match.
+    BadMagic,
+    /// The endianness marker is not recognised.
+    UnknownEndian(u8),
+    /// The version is outside of [`Preamble::versions`].
+    UnsupportedVersion(u16),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnexpectedEnd => f.write_str("unexpected end of preamble"),
+            Error::BadMagic => f.write_str("magic bytes don't match"),
+            Error::UnknownEndian(it) => {
+                f.write_fmt(format_args!("unknown endianness marker {:#04x}", it))
+            }
+            Error::UnsupportedVersion(it) => {
+                f.write_fmt(format_args!("unsupported version {}", it))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMAT: Preamble = Preamble::new(b"\x89FMT", 2..=4);
+
+    #[test]
+    fn round_trip() {
+        for endian in [Endian::Big, Endian::Little, Endian::Native] {
+            let mut buf = vec![];
+            FORMAT.write(&mut buf, 3, endian).unwrap();
+            buf.push(0xff);
+            let (parsed, rest) = FORMAT.parse(&buf).unwrap();
+            assert_eq!(parsed.version, 3);
+            assert_eq!(parsed.endian, endian.canonical());
+            assert_eq!(rest, [0xff]);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(FORMAT.parse(b"\x89FMTB\x00"), Err(Error::UnexpectedEnd));
+        assert_eq!(FORMAT.parse(b"\x89FMXB\x00\x03"), Err(Error::BadMagic));
+        assert_eq!(
+            FORMAT.parse(b"\x89FMTX\x00\x03"),
+            Err(Error::UnknownEndian(b'X'))
+        );
+        assert_eq!(
+            FORMAT.parse(b"\x89FMTl\x00\x03"),
+            Err(Error::UnsupportedVersion(0x0300))
+        );
+        assert_eq!(
+            FORMAT.read(&mut &b"\x89FMTl"[..]).unwrap_err().kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Decoding from ring buffers, without first making them contiguous.
+//!
+//! ```
+//! use bitendian::ring::RingExt as _;
+//! use std::collections::VecDeque;
+//!
+//! let mut ring = VecDeque::with_capacity(4);
+//! ring.extend([0xff, 0xff, 0xff, 0x00]);
+//! ring.drain(..3);
+//! ring.extend([0x01, 0x02]); // may wrap around
+//!
+//! assert_eq!(ring.peek_be::<u16>(), Some(0x0001));
+//! assert_eq!(ring.pop_be::<u16>(), Some(0x0001));
+//! assert_eq!(ring.pop_be::<u16>(), None);
+//! assert_eq!(ring.pop_be::<u8>(), Some(0x02));
+//! ```
+//!
+//! Custom ring buffers can opt in by implementing [`RingBuffer`].
+
+use crate::{BitEndian, Endian};
+
+/// A ring buffer of bytes, which may wrap around.
+pub trait RingBuffer {
+    /// The buffered bytes, in order, as (up to) two contiguous slices.
+    fn as_slices(&self) -> (&[u8], &[u8]);
+    /// Remove the first `n` bytes.
+    ///
+    /// Callers will never pass an `n` greater than the number of buffered
+    /// bytes.
+    fn consume(&mut self, n: usize);
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "alloc")))]
+impl RingBuffer for alloc::collections::VecDeque<u8> {
+    fn as_slices(&self) -> (&[u8], &[u8]) {
+        alloc::collections::VecDeque::as_slices(self)
+    }
+    fn consume(&mut self, n: usize) {
+        self.drain(..n);
+    }
+}
+
+impl<B: RingBuffer + ?Sized> RingBuffer for &mut B {
+    fn as_slices(&self) -> (&[u8], &[u8]) {
+        B::as_slices(self)
+    }
+    fn consume(&mut self, n: usize) {
+        B::consume(self, n)
+    }
+}
+
+/// Extends [`RingBuffer`]s with methods for decoding in an endian-dependent
+/// way.
+///
+/// Each method returns [`None`] if fewer than `N` bytes are buffered.
+///
+/// See [module docs](mod@self) for usage examples.
+pub trait RingExt<const N: usize>: RingBuffer {
+    /// Decode the next value according to a run-time endianness, without
+    /// consuming it.
+    fn peek_endian<T: BitEndian<N>>(&self, endian: Endian) -> Option<T> {
+        let (front, back) = self.as_slices();
+        if front.len() + back.len() < N {
+            return None;
+        }
+        let mut bytes = [0u8; N];
+        let split = front.len().min(N);
+        bytes[..split].copy_from_slice(&front[..split]);
+        bytes[split..].copy_from_slice(&back[..N - split]);
+        Some(T::from_bytes_endian(bytes, endian))
+    }
+    /// Peek with [`Endian::Big`].
+    fn peek_be<T: BitEndian<N>>(&self) -> Option<T> {
+        self.peek_endian(Endian::Big)
+    }
+    /// Peek with [`Endian::Little`].
+    fn peek_le<T: BitEndian<N>>(&self) -> Option<T> {
+        self.peek_endian(Endian::Little)
+    }
+    /// Peek with [`Endian::Native`].
+    fn peek_ne<T: BitEndian<N>>(&self) -> Option<T> {
+        self.peek_endian(Endian::Native)
+    }
+    /// Decode and consume the next value according to a run-time endianness.
+    fn pop_endian<T: BitEndian<N>>(&mut self, endian: Endian) -> Option<T> {
+        let it = self.peek_endian(endian)?;
+        self.consume(N);
+        Some(it)
+    }
+    /// Pop with [`Endian::Big`].
+    fn pop_be<T: BitEndian<N>>(&mut self) -> Option<T> {
+        self.pop_endian(Endian::Big)
+    }
+    /// Pop with [`Endian::Little`].
+    fn pop_le<T: BitEndian<N>>(&mut self) -> Option<T> {
+        self.pop_endian(Endian::Little)
+    }
+    /// Pop with [`Endian::Native`].
+    fn pop_ne<T: BitEndian<N>>(&mut self) -> Option<T> {
+        self.pop_endian(Endian::Native)
+    }
+}
+impl<const N: usize, B> RingExt<N> for B where B: RingBuffer + ?Sized {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fixed-capacity ring, to check the trait is implementable.
+    struct Fixed {
+        bytes: [u8; 4],
+        start: usize,
+        len: usize,
+    }
+
+    impl RingBuffer for Fixed {
+        fn as_slices(&self) -> (&[u8], &[u8]) {
+            let end = self.start + self.len;
+            match end <= self.bytes.len() {
+                true => (&self.bytes[self.start..end], &[]),
+                false => (
+                    &self.bytes[self.start..],
+                    &self.bytes[..end - self.bytes.len()],
+                ),
+            }
+        }
+        fn consume(&mut self, n: usize) {
+            self.start = (self.start + n) % self.bytes.len();
+            self.len -= n;
+        }
+    }
+
+    #[test]
+    fn wraparound() {
+        let mut ring = Fixed {
+            bytes: [3, 4, 1, 2],
+            start: 2,
+            len: 4,
+        };
+        assert_eq!(ring.peek_le::<u32>(), Some(0x04030201));
+        assert_eq!(ring.pop_be::<u8>(), Some(1));
+        assert_eq!(ring.pop_be::<u16>(), Some(0x0203));
+        assert_eq!(ring.pop_be::<u16>(), None);
+        assert_eq!(ring.pop_be::<u8>(), Some(4));
+        assert_eq!(ring.peek_be::<u8>(), None);
+    }
+}
@@ -0,0 +1,49 @@
+//! [`Be`] and [`Le`] have the same representation on every platform, so they
+//! archive as themselves.
+
+use crate::{Be, Le};
+use rkyv::{
+    bytecheck::CheckBytes, rancor::Fallible, traits::NoUndef, Archive, Deserialize, Place,
+    Portable, Serialize,
+};
+
+macro_rules! archive {
+    ($($name:ident),*) => {
+        $(
+            // SAFETY: `repr(transparent)` over a byte array, which is portable
+            unsafe impl<T, const N: usize> Portable for $name<T, N> {}
+            // SAFETY: `repr(transparent)` over a byte array, which has no padding
+            unsafe impl<T, const N: usize> NoUndef for $name<T, N> {}
+
+            // SAFETY: every bit pattern is a valid byte array
+            unsafe impl<C: Fallible + ?Sized, T, const N: usize> CheckBytes<C> for $name<T, N> {
+                unsafe fn check_bytes(_: *const Self, _: &mut C) -> Result<(), C::Error> {
+                    Ok(())
+                }
+            }
+
+            impl<T, const N: usize> Archive for $name<T, N> {
+                type Archived = Self;
+                type Resolver = ();
+
+                fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+                    out.write(*self)
+                }
+            }
+
+            impl<S: Fallible + ?Sized, T, const N: usize> Serialize<S> for $name<T, N> {
+                fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                    Ok(())
+                }
+            }
+
+            impl<D: Fallible + ?Sized, T, const N: usize> Deserialize<Self, D> for $name<T, N> {
+                fn deserialize(&self, _: &mut D) -> Result<Self, D::Error> {
+                    Ok(*self)
+                }
+            }
+        )*
+    };
+}
+
+archive!(Be, Le);
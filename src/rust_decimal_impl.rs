@@ -0,0 +1,82 @@
+use crate::BitEndian;
+use rust_decimal::Decimal;
+
+/// As the four `u32` words of [`Decimal::serialize`], flags then the 96-bit
+/// mantissa from its least significant word, each in the chosen endianness.
+///
+/// Little-endian is exactly [`Decimal::serialize`].
+impl BitEndian<16> for Decimal {
+    fn to_le_bytes(self) -> [u8; 16] {
+        self.serialize()
+    }
+    fn to_be_bytes(self) -> [u8; 16] {
+        swap_words(self.serialize())
+    }
+    fn to_ne_bytes(self) -> [u8; 16] {
+        match cfg!(target_endian = "big") {
+            true => BitEndian::to_be_bytes(self),
+            false => BitEndian::to_le_bytes(self),
+        }
+    }
+
+    fn from_le_bytes(bytes: [u8; 16]) -> Self {
+        Decimal::deserialize(bytes)
+    }
+    fn from_be_bytes(bytes: [u8; 16]) -> Self {
+        Decimal::deserialize(swap_words(bytes))
+    }
+    fn from_ne_bytes(bytes: [u8; 16]) -> Self {
+        match cfg!(target_endian = "big") {
+            true => Self::from_be_bytes(bytes),
+            false => Self::from_le_bytes(bytes),
+        }
+    }
+}
+
+fn swap_words(mut bytes: [u8; 16]) -> [u8; 16] {
+    for word in bytes.as_chunks_mut::<4>().0 {
+        word.reverse()
+    }
+    bytes
+}
+
+#[cfg(feature = "std")]
+impl crate::io::Decode for Decimal {
+    fn decode<R: std::io::Read + ?Sized>(
+        reader: &mut R,
+        endian: crate::Endian,
+    ) -> std::io::Result<Self> {
+        let mut bytes = [0; 16];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes_endian(bytes, endian))
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::io::Encode for Decimal {
+    fn encode<W: std::io::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        endian: crate::Endian,
+    ) -> std::io::Result<()> {
+        writer.write_all(&self.to_bytes_endian(endian))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let it = Decimal::new(-12345, 2);
+        let be = BitEndian::to_be_bytes(it);
+        assert_eq!(be[..4], [0x80, 0x02, 0, 0]);
+        assert_eq!(be[4..8], 12345u32.to_be_bytes());
+        assert_eq!(<Decimal as BitEndian<16>>::from_be_bytes(be), it);
+        assert_eq!(
+            <Decimal as BitEndian<16>>::from_le_bytes(it.serialize()),
+            it
+        );
+    }
+}
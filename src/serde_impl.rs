@@ -0,0 +1,177 @@
+use crate::{Be, BitEndian, Endian, Le};
+use core::fmt;
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple as _,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+macro_rules! with_module {
+    ($name:ident, $endian:expr, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// The field is serialized as a fixed-size tuple of bytes (like `[u8; N]`),
+        /// so binary formats emit the bytes verbatim, without a length prefix.
+        ///
+        /// ```
+        /// # use serde::{Serialize, Deserialize};
+        /// #[derive(Serialize, Deserialize)]
+        /// struct Header {
+        #[doc = concat!("    #[serde(with = \"bitendian::", stringify!($name), "\")]")]
+        ///     length: u32,
+        /// }
+        /// ```
+        pub mod $name {
+            use super::*;
+
+            /// Serialize `value` in this module's byte order.
+            pub fn serialize<const N: usize, T, S>(
+                value: &T,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error>
+            where
+                T: BitEndian<N> + Copy,
+                S: Serializer,
+            {
+                serialize_endian(*value, $endian, serializer)
+            }
+
+            /// Deserialize a value in this module's byte order.
+            pub fn deserialize<'de, const N: usize, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: BitEndian<N>,
+                D: Deserializer<'de>,
+            {
+                deserialize_endian($endian, deserializer)
+            }
+        }
+    };
+}
+
+with_module!(
+    serde_be,
+    Endian::Big,
+    "Use as `#[serde(with = \"bitendian::serde_be\")]` to (de)serialize a field in big-endian byte order."
+);
+with_module!(
+    serde_le,
+    Endian::Little,
+    "Use as `#[serde(with = \"bitendian::serde_le\")]` to (de)serialize a field in little-endian byte order."
+);
+
+fn serialize_endian<const N: usize, T, S>(
+    value: T,
+    endian: Endian,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: BitEndian<N>,
+    S: Serializer,
+{
+    let mut tuple = serializer.serialize_tuple(N)?;
+    for byte in value.to_bytes_endian(endian) {
+        tuple.serialize_element(&byte)?;
+    }
+    tuple.end()
+}
+
+fn deserialize_endian<'de, const N: usize, T, D>(
+    endian: Endian,
+    deserializer: D,
+) -> Result<T, D::Error>
+where
+    T: BitEndian<N>,
+    D: Deserializer<'de>,
+{
+    struct BytesVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+        type Value = [u8; N];
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_fmt(format_args!("{} bytes", N))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut bytes = [0u8; N];
+            for (ix, byte) in bytes.iter_mut().enumerate() {
+                *byte = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(ix, &self))?;
+            }
+            Ok(bytes)
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            v.try_into()
+                .map_err(|_| de::Error::invalid_length(v.len(), &self))
+        }
+    }
+
+    let bytes = deserializer.deserialize_tuple(N, BytesVisitor::<N>)?;
+    Ok(T::from_bytes_endian(bytes, endian))
+}
+
+macro_rules! wrapper {
+    ($($name:ident),* $(,)?) => {
+        $(
+            /// As the stored value, so human-readable formats show the number,
+            /// rather than its bytes.
+            ///
+            /// Use [`serde_be`](crate::serde_be) or [`serde_le`](crate::serde_le)
+            /// to serialize the bytes.
+            impl<T: BitEndian<N> + Serialize, const N: usize> Serialize for $name<T, N> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    self.get().serialize(serializer)
+                }
+            }
+            impl<'de, T: BitEndian<N> + Deserialize<'de>, const N: usize> Deserialize<'de>
+                for $name<T, N>
+            {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    T::deserialize(deserializer).map(Self::new)
+                }
+            }
+        )*
+    };
+}
+wrapper!(Be, Le);
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Mixed {
+        #[serde(with = "crate::serde_be")]
+        be: u32,
+        #[serde(with = "crate::serde_le")]
+        le: u32,
+        #[serde(with = "crate::serde_be")]
+        float: f64,
+    }
+
+    #[test]
+    fn bincode() {
+        let it = Mixed {
+            be: 1,
+            le: 1,
+            float: 1.0,
+        };
+        let bytes = bincode::serialize(&it).unwrap();
+        assert_eq!(
+            bytes,
+            [0, 0, 0, 1, 1, 0, 0, 0, 0x3f, 0xf0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(it, bincode::deserialize(&bytes).unwrap());
+    }
+
+    #[test]
+    fn wrapper() {
+        let it = (crate::U16Be::new(1), crate::U16Le::new(2));
+        let bytes = bincode::serialize(&it).unwrap();
+        // bincode writes plain numbers in little-endian
+        assert_eq!(bytes, [1, 0, 2, 0]);
+        assert_eq!(it, bincode::deserialize(&bytes).unwrap());
+    }
+}
@@ -0,0 +1,97 @@
+use crate::BitEndian;
+use core::simd::Simd;
+
+/// Concatenate the encoded lanes.
+fn from_lanes<const S: usize, T, const L: usize, const N: usize>(
+    lanes: [T; L],
+    encode: impl Fn(T) -> [u8; S],
+) -> [u8; N] {
+    let mut bytes = [0; N];
+    let (chunks, _) = bytes.as_chunks_mut::<S>();
+    for (chunk, lane) in chunks.iter_mut().zip(lanes) {
+        *chunk = encode(lane);
+    }
+    bytes
+}
+
+/// Split the bytes into lanes.
+fn to_lanes<const S: usize, T, const L: usize, const N: usize>(
+    bytes: [u8; N],
+    decode: impl Fn([u8; S]) -> T,
+) -> [T; L] {
+    let (chunks, _) = bytes.as_chunks::<S>();
+    core::array::from_fn(|ix| decode(chunks[ix]))
+}
+
+macro_rules! bit_endian_simd {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            bit_endian_simd!(@lanes $ty; 1, 2, 4, 8, 16, 32, 64);
+        )*
+    };
+    (@lanes $ty:ty; $($lanes:literal),*) => {
+        $(
+            /// Each lane in order, so big-endian bytes are the lanes' big-endian
+            /// bytes, concatenated.
+            impl BitEndian<{ size_of::<$ty>() * $lanes }> for Simd<$ty, $lanes> {
+                fn to_le_bytes(self) -> [u8; size_of::<$ty>() * $lanes] {
+                    from_lanes(self.to_array(), <$ty>::to_le_bytes)
+                }
+                fn to_be_bytes(self) -> [u8; size_of::<$ty>() * $lanes] {
+                    from_lanes(self.to_array(), <$ty>::to_be_bytes)
+                }
+                fn to_ne_bytes(self) -> [u8; size_of::<$ty>() * $lanes] {
+                    from_lanes(self.to_array(), <$ty>::to_ne_bytes)
+                }
+
+                fn from_le_bytes(bytes: [u8; size_of::<$ty>() * $lanes]) -> Self {
+                    Self::from_array(to_lanes(bytes, <$ty>::from_le_bytes))
+                }
+                fn from_be_bytes(bytes: [u8; size_of::<$ty>() * $lanes]) -> Self {
+                    Self::from_array(to_lanes(bytes, <$ty>::from_be_bytes))
+                }
+                fn from_ne_bytes(bytes: [u8; size_of::<$ty>() * $lanes]) -> Self {
+                    Self::from_array(to_lanes(bytes, <$ty>::from_ne_bytes))
+                }
+            }
+
+            #[cfg(feature = "std")]
+            impl crate::io::Decode for Simd<$ty, $lanes> {
+                fn decode<R: std::io::Read + ?Sized>(
+                    reader: &mut R,
+                    endian: crate::Endian,
+                ) -> std::io::Result<Self> {
+                    let mut bytes = [0; size_of::<$ty>() * $lanes];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(Self::from_bytes_endian(bytes, endian))
+                }
+            }
+
+            #[cfg(feature = "std")]
+            impl crate::io::Encode for Simd<$ty, $lanes> {
+                fn encode<W: std::io::Write + ?Sized>(
+                    &self,
+                    writer: &mut W,
+                    endian: crate::Endian,
+                ) -> std::io::Result<()> {
+                    writer.write_all(&self.to_bytes_endian(endian))
+                }
+            }
+        )*
+    };
+}
+bit_endian_simd!(u8, i8, u16, i16, u32, i32, f32, u64, i64, f64, usize, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lanes() {
+        let it = Simd::from_array([1u16, 2, 3, 4]);
+        assert_eq!(it.to_be_bytes(), [0, 1, 0, 2, 0, 3, 0, 4]);
+        assert_eq!(it.to_le_bytes(), [1, 0, 2, 0, 3, 0, 4, 0]);
+        assert_eq!(Simd::<u16, 4>::from_le_bytes(it.to_le_bytes()), it);
+        assert_eq!(Simd::<u16, 4>::from_be_bytes(it.to_be_bytes()), it);
+    }
+}
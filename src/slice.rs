@@ -0,0 +1,923 @@
+//! Operations on byte slices.
+//!
+//! ```
+//! use bitendian::{slice, Endian};
+//!
+//! let bytes = [0, 1, 0, 2, 0xff];
+//! let mut it = slice::iter_endian(&bytes, Endian::Big);
+//! assert_eq!(it.len(), 2);
+//! assert_eq!(it.next(), Some(1u16));
+//! assert_eq!(it.next(), Some(2u16));
+//! assert_eq!(it.next(), None);
+//! assert_eq!(it.remainder(), [0xff]);
+//! ```
+
+use crate::{BitEndian, Endian};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::{fmt, iter::FusedIterator, marker::PhantomData, mem::MaybeUninit, slice::ChunksExact};
+
+/// Lazily decode consecutive values from `bytes`, according to a run-time
+/// endianness.
+///
+/// Any trailing bytes which don't make up a whole value are available from
+/// [`Iter::remainder`].
+pub fn iter_endian<const N: usize, T: BitEndian<N>>(
+    bytes: &[u8],
+    endian: Endian,
+) -> Iter<'_, N, T> {
+    // zero-width values take up no bytes, so yield none of them, leaving all
+    // of `bytes` as the remainder
+    let chunk_size = match N {
+        0 => bytes.len() + 1,
+        n => n,
+    };
+    Iter {
+        chunks: bytes.chunks_exact(chunk_size),
+        endian,
+        _out: PhantomData,
+    }
+}
+
+/// Iterate with [`Endian::Big`], as in [`iter_endian`].
+///
+/// ```
+/// use bitendian::slice;
+///
+/// let samples = slice::iter_be::<_, u32>(&[0, 0, 0, 1, 0, 0]);
+/// assert_eq!(samples.remainder(), [0, 0]);
+/// assert!(samples.eq([1]));
+/// ```
+pub fn iter_be<const N: usize, T: BitEndian<N>>(bytes: &[u8]) -> Iter<'_, N, T> {
+    iter_endian(bytes, Endian::Big)
+}
+
+/// Iterate with [`Endian::Little`], as in [`iter_endian`].
+pub fn iter_le<const N: usize, T: BitEndian<N>>(bytes: &[u8]) -> Iter<'_, N, T> {
+    iter_endian(bytes, Endian::Little)
+}
+
+/// Iterate with [`Endian::Native`], as in [`iter_endian`].
+pub fn iter_ne<const N: usize, T: BitEndian<N>>(bytes: &[u8]) -> Iter<'_, N, T> {
+    iter_endian(bytes, Endian::Native)
+}
+
+/// Iterator for [`iter_endian`], see that function for more.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, const N: usize, T> {
+    chunks: ChunksExact<'a, u8>,
+    endian: Endian,
+    _out: PhantomData<fn() -> T>,
+}
+
+impl<'a, const N: usize, T> Iter<'a, N, T> {
+    /// The trailing bytes which don't make up a whole value.
+    ///
+    /// These will never be yielded by the iterator.
+    pub fn remainder(&self) -> &'a [u8] {
+        self.chunks.remainder()
+    }
+}
+
+impl<const N: usize, T: BitEndian<N>> Iterator for Iter<'_, N, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(|it| decode(it, self.endian))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.chunks.nth(n).map(|it| decode(it, self.endian))
+    }
+    fn count(self) -> usize {
+        self.chunks.count()
+    }
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<const N: usize, T: BitEndian<N>> DoubleEndedIterator for Iter<'_, N, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.chunks.next_back().map(|it| decode(it, self.endian))
+    }
+}
+
+impl<const N: usize, T: BitEndian<N>> ExactSizeIterator for Iter<'_, N, T> {}
+impl<const N: usize, T: BitEndian<N>> FusedIterator for Iter<'_, N, T> {}
+
+/// Decode the value at `offset` in `bytes`, according to a run-time
+/// endianness, or [`None`] if it is out of bounds.
+///
+/// ```
+/// use bitendian::slice;
+///
+/// let mut header = [0u8; 8];
+/// slice::put_be(&mut header, 4, 0x0102u16).unwrap();
+/// assert_eq!(header, [0, 0, 0, 0, 1, 2, 0, 0]);
+///
+/// let len: u16 = slice::get_be(&header, 4).unwrap();
+/// assert_eq!(len, 0x0102);
+/// assert_eq!(slice::get_be::<4, u32>(&header, 6), None);
+/// ```
+pub fn get_endian<const N: usize, T: BitEndian<N>>(
+    bytes: &[u8],
+    offset: usize,
+    endian: Endian,
+) -> Option<T> {
+    let chunk = bytes.get(offset..)?.first_chunk::<N>()?;
+    Some(T::from_bytes_endian(*chunk, endian))
+}
+
+/// Get with [`Endian::Big`], as in [`get_endian`].
+pub fn get_be<const N: usize, T: BitEndian<N>>(bytes: &[u8], offset: usize) -> Option<T> {
+    get_endian(bytes, offset, Endian::Big)
+}
+
+/// Get with [`Endian::Little`], as in [`get_endian`].
+pub fn get_le<const N: usize, T: BitEndian<N>>(bytes: &[u8], offset: usize) -> Option<T> {
+    get_endian(bytes, offset, Endian::Little)
+}
+
+/// Get with [`Endian::Native`], as in [`get_endian`].
+pub fn get_ne<const N: usize, T: BitEndian<N>>(bytes: &[u8], offset: usize) -> Option<T> {
+    get_endian(bytes, offset, Endian::Native)
+}
+
+/// Encode `value` at `offset` in `bytes`, according to a run-time
+/// endianness, or return [`None`] without writing if it is out of bounds.
+///
+/// See [`get_endian`] for an example.
+pub fn put_endian<const N: usize, T: BitEndian<N>>(
+    bytes: &mut [u8],
+    offset: usize,
+    value: T,
+    endian: Endian,
+) -> Option<()> {
+    let chunk = bytes.get_mut(offset..)?.first_chunk_mut::<N>()?;
+    *chunk = value.to_bytes_endian(endian);
+    Some(())
+}
+
+/// Put with [`Endian::Big`], as in [`put_endian`].
+pub fn put_be<const N: usize, T: BitEndian<N>>(
+    bytes: &mut [u8],
+    offset: usize,
+    value: T,
+) -> Option<()> {
+    put_endian(bytes, offset, value, Endian::Big)
+}
+
+/// Put with [`Endian::Little`], as in [`put_endian`].
+pub fn put_le<const N: usize, T: BitEndian<N>>(
+    bytes: &mut [u8],
+    offset: usize,
+    value: T,
+) -> Option<()> {
+    put_endian(bytes, offset, value, Endian::Little)
+}
+
+/// Put with [`Endian::Native`], as in [`put_endian`].
+pub fn put_ne<const N: usize, T: BitEndian<N>>(
+    bytes: &mut [u8],
+    offset: usize,
+    value: T,
+) -> Option<()> {
+    put_endian(bytes, offset, value, Endian::Native)
+}
+
+/// Decode the value at `offset` in `bytes`, according to a run-time
+/// endianness, without bounds checks.
+///
+/// See [`get_endian`] for a checked version.
+///
+/// # Safety
+/// - `offset + N` must not exceed `bytes.len()`.
+pub unsafe fn get_endian_unchecked<const N: usize, T: BitEndian<N>>(
+    bytes: &[u8],
+    offset: usize,
+    endian: Endian,
+) -> T {
+    debug_assert!(offset <= bytes.len() && N <= bytes.len() - offset);
+    // SAFETY: upheld by caller, and arrays of bytes have no alignment
+    let chunk = unsafe {
+        bytes
+            .as_ptr()
+            .add(offset)
+            .cast::<[u8; N]>()
+            .read_unaligned()
+    };
+    T::from_bytes_endian(chunk, endian)
+}
+
+/// Get with [`Endian::Big`], as in [`get_endian_unchecked`].
+///
+/// # Safety
+/// - `offset + N` must not exceed `bytes.len()`.
+pub unsafe fn get_be_unchecked<const N: usize, T: BitEndian<N>>(bytes: &[u8], offset: usize) -> T {
+    // SAFETY: upheld by caller
+    unsafe { get_endian_unchecked(bytes, offset, Endian::Big) }
+}
+
+/// Get with [`Endian::Little`], as in [`get_endian_unchecked`].
+///
+/// # Safety
+/// - `offset + N` must not exceed `bytes.len()`.
+pub unsafe fn get_le_unchecked<const N: usize, T: BitEndian<N>>(bytes: &[u8], offset: usize) -> T {
+    // SAFETY: upheld by caller
+    unsafe { get_endian_unchecked(bytes, offset, Endian::Little) }
+}
+
+/// Get with [`Endian::Native`], as in [`get_endian_unchecked`].
+///
+/// # Safety
+/// - `offset + N` must not exceed `bytes.len()`.
+pub unsafe fn get_ne_unchecked<const N: usize, T: BitEndian<N>>(bytes: &[u8], offset: usize) -> T {
+    // SAFETY: upheld by caller
+    unsafe { get_endian_unchecked(bytes, offset, Endian::Native) }
+}
+
+/// Encode `value` at `offset` in `bytes`, according to a run-time
+/// endianness, without bounds checks.
+///
+/// See [`put_endian`] for a checked version.
+///
+/// # Safety
+/// - `offset + N` must not exceed `bytes.len()`.
+pub unsafe fn put_endian_unchecked<const N: usize, T: BitEndian<N>>(
+    bytes: &mut [u8],
+    offset: usize,
+    value: T,
+    endian: Endian,
+) {
+    debug_assert!(offset <= bytes.len() && N <= bytes.len() - offset);
+    let chunk = value.to_bytes_endian(endian);
+    // SAFETY: upheld by caller, and arrays of bytes have no alignment
+    unsafe {
+        bytes
+            .as_mut_ptr()
+            .add(offset)
+            .cast::<[u8; N]>()
+            .write_unaligned(chunk)
+    }
+}
+
+/// Put with [`Endian::Big`], as in [`put_endian_unchecked`].
+///
+/// # Safety
+/// - `offset + N` must not exceed `bytes.len()`.
+pub unsafe fn put_be_unchecked<const N: usize, T: BitEndian<N>>(
+    bytes: &mut [u8],
+    offset: usize,
+    value: T,
+) {
+    // SAFETY: upheld by caller
+    unsafe { put_endian_unchecked(bytes, offset, value, Endian::Big) }
+}
+
+/// Put with [`Endian::Little`], as in [`put_endian_unchecked`].
+///
+/// # Safety
+/// - `offset + N` must not exceed `bytes.len()`.
+pub unsafe fn put_le_unchecked<const N: usize, T: BitEndian<N>>(
+    bytes: &mut [u8],
+    offset: usize,
+    value: T,
+) {
+    // SAFETY: upheld by caller
+    unsafe { put_endian_unchecked(bytes, offset, value, Endian::Little) }
+}
+
+/// Put with [`Endian::Native`], as in [`put_endian_unchecked`].
+///
+/// # Safety
+/// - `offset + N` must not exceed `bytes.len()`.
+pub unsafe fn put_ne_unchecked<const N: usize, T: BitEndian<N>>(
+    bytes: &mut [u8],
+    offset: usize,
+    value: T,
+) {
+    // SAFETY: upheld by caller
+    unsafe { put_endian_unchecked(bytes, offset, value, Endian::Native) }
+}
+
+/// Decode exactly `M` consecutive values from `bytes`, according to a
+/// run-time endianness, or [`None`] if `bytes` is the wrong length.
+///
+/// ```
+/// use bitendian::{slice, Endian};
+///
+/// let bytes = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3];
+/// let xyz: [u32; 3] = slice::array_endian(&bytes, Endian::Big).unwrap();
+/// assert_eq!(xyz, [1, 2, 3]);
+/// assert_eq!(slice::array_endian::<4, u32, 2>(&bytes, Endian::Big), None);
+/// ```
+pub fn array_endian<const N: usize, T: BitEndian<N>, const M: usize>(
+    bytes: &[u8],
+    endian: Endian,
+) -> Option<[T; M]> {
+    if N == 0 {
+        // `M` zero-width values take up no bytes
+        return match bytes {
+            [] => Some(core::array::from_fn(|_| {
+                T::from_bytes_endian([0; N], endian)
+            })),
+            _ => None,
+        };
+    }
+    let (values, []) = bytes.as_chunks::<N>() else {
+        return None;
+    };
+    let values = <&[[u8; N]; M]>::try_from(values).ok()?;
+    Some(values.map(|it| T::from_bytes_endian(it, endian)))
+}
+
+/// Encode all of `values` into `out`, according to a run-time endianness.
+///
+/// This is the inverse of [`decode_slice`], and like [`byteorder`]'s
+/// `write_u32_into`, but for any [`BitEndian`] type.
+///
+/// ```
+/// use bitendian::{slice, Endian};
+///
+/// let mut bytes = [0; 6];
+/// slice::encode_slice(&[1u16, 2, 3], Endian::Big, &mut bytes);
+/// assert_eq!(bytes, [0, 1, 0, 2, 0, 3]);
+///
+/// let mut values = [0u16; 3];
+/// slice::decode_slice(&bytes, Endian::Little, &mut values);
+/// assert_eq!(values, [0x100, 0x200, 0x300]);
+/// ```
+///
+/// # Panics
+/// - If `out` isn't exactly long enough for the values.
+///
+/// [`byteorder`]: https://docs.rs/byteorder/1/byteorder/trait.ByteOrder.html#tymethod.write_u32_into
+pub fn encode_slice<const N: usize, T: BitEndian<N> + Copy>(
+    values: &[T],
+    endian: Endian,
+    out: &mut [u8],
+) {
+    assert_eq!(out.len(), values.len() * N, "output is the wrong length");
+    if N == 0 {
+        // zero-width values have no bytes to copy
+        return;
+    }
+    let (chunks, _) = out.as_chunks_mut::<N>();
+    for (chunk, value) in chunks.iter_mut().zip(values) {
+        *chunk = value.to_bytes_endian(endian);
+    }
+}
+
+/// Decode all of `bytes` into `out`, according to a run-time endianness.
+///
+/// See [`encode_slice`] for an example.
+///
+/// # Panics
+/// - If `bytes` isn't exactly the length of the values.
+pub fn decode_slice<const N: usize, T: BitEndian<N>>(bytes: &[u8], endian: Endian, out: &mut [T]) {
+    assert_eq!(bytes.len(), out.len() * N, "input is the wrong length");
+    if N == 0 {
+        out.fill_with(|| T::from_bytes_endian([0; N], endian));
+        return;
+    }
+    let (chunks, _) = bytes.as_chunks::<N>();
+    for (value, chunk) in out.iter_mut().zip(chunks) {
+        *value = T::from_bytes_endian(*chunk, endian);
+    }
+}
+
+/// Convert each value in place so that its in-memory bytes are in the given
+/// endianness, e.g to fix up a freshly read or memory-mapped buffer.
+///
+/// For primitives, this is its own inverse, so also converts from the
+/// given endianness to native values.
+/// On a processor of the given endianness, this does nothing.
+///
+/// ```
+/// use bitendian::slice;
+///
+/// let mut values = [u32::from_be_bytes([1, 2, 3, 4])];
+/// slice::swap_to_le_in_place(&mut values);
+/// assert_eq!(values[0].to_ne_bytes(), [4, 3, 2, 1]);
+/// ```
+pub fn swap_endian_in_place<const N: usize, T: BitEndian<N> + Copy>(
+    values: &mut [T],
+    endian: Endian,
+) {
+    if endian.canonical() == Endian::Native.canonical() {
+        return;
+    }
+    for value in values {
+        *value = T::from_ne_bytes(value.to_bytes_endian(endian));
+    }
+}
+
+/// Swap with [`Endian::Big`], as in [`swap_endian_in_place`].
+pub fn swap_to_be_in_place<const N: usize, T: BitEndian<N> + Copy>(values: &mut [T]) {
+    swap_endian_in_place(values, Endian::Big)
+}
+
+/// Swap with [`Endian::Little`], as in [`swap_endian_in_place`].
+pub fn swap_to_le_in_place<const N: usize, T: BitEndian<N> + Copy>(values: &mut [T]) {
+    swap_endian_in_place(values, Endian::Little)
+}
+
+/// Encode `value` into the start of an uninitialized buffer, according to a
+/// run-time endianness, returning the initialized bytes and the rest of the
+/// buffer, or [`None`] if it is too short.
+///
+/// This avoids zero-filling a buffer which is about to be overwritten.
+///
+/// ```
+/// use bitendian::{slice, Endian};
+/// use std::mem::MaybeUninit;
+///
+/// let mut buf = [MaybeUninit::<u8>::uninit(); 8];
+/// let (header, rest) = slice::encode_uninit(0x0102u16, Endian::Big, &mut buf).unwrap();
+/// assert_eq!(header, [1, 2]);
+/// let (body, _) = slice::encode_slice_uninit(&[3u8, 4], Endian::Big, rest).unwrap();
+/// assert_eq!(body, [3, 4]);
+/// ```
+pub fn encode_uninit<const N: usize, T: BitEndian<N>>(
+    value: T,
+    endian: Endian,
+    out: &mut [MaybeUninit<u8>],
+) -> Option<(&mut [u8], &mut [MaybeUninit<u8>])> {
+    let (chunk, rest) = out.split_first_chunk_mut::<N>()?;
+    Some((write_uninit(chunk, &value.to_bytes_endian(endian)), rest))
+}
+
+/// Encode all of `values` into the start of an uninitialized buffer, as in
+/// [`encode_uninit`].
+pub fn encode_slice_uninit<'a, const N: usize, T: BitEndian<N> + Copy>(
+    values: &[T],
+    endian: Endian,
+    out: &'a mut [MaybeUninit<u8>],
+) -> Option<(&'a mut [u8], &'a mut [MaybeUninit<u8>])> {
+    let (init, rest) = out.split_at_mut_checked(values.len().checked_mul(N)?)?;
+    // zero-width values have no bytes to copy
+    if N != 0 {
+        let (chunks, _) = init.as_chunks_mut::<N>();
+        for (chunk, value) in chunks.iter_mut().zip(values) {
+            write_uninit(chunk, &value.to_bytes_endian(endian));
+        }
+    }
+    // SAFETY: every chunk was initialized above
+    let init = unsafe { assume_init_mut(init) };
+    Some((init, rest))
+}
+
+/// Decode consecutive values from `bytes` into an uninitialized buffer,
+/// according to a run-time endianness, returning the initialized values.
+///
+/// As many values are decoded as fit in both `bytes` and `out`.
+/// This avoids zero-filling a buffer which is about to be overwritten, e.g
+/// the spare capacity of a [`Vec`](std::vec::Vec).
+///
+/// ```
+/// use bitendian::{slice, Endian};
+///
+/// let bytes = [0, 1, 0, 2, 0, 3];
+/// let mut values = Vec::<u16>::with_capacity(3);
+/// let len = slice::decode_slice_uninit(&bytes, Endian::Big, values.spare_capacity_mut()).len();
+/// // SAFETY: `decode_slice_uninit` initialized the first `len` values
+/// unsafe { values.set_len(len) };
+/// assert_eq!(values, [1, 2, 3]);
+/// ```
+pub fn decode_slice_uninit<'a, const N: usize, T: BitEndian<N>>(
+    bytes: &[u8],
+    endian: Endian,
+    out: &'a mut [MaybeUninit<T>],
+) -> &'a mut [T] {
+    if N == 0 {
+        // any number of zero-width values fit in `bytes`
+        for value in out.iter_mut() {
+            value.write(T::from_bytes_endian([0; N], endian));
+        }
+        // SAFETY: every value was initialized above
+        return unsafe { assume_init_mut(out) };
+    }
+    let (chunks, _) = bytes.as_chunks::<N>();
+    let len = chunks.len().min(out.len());
+    let out = &mut out[..len];
+    for (value, chunk) in out.iter_mut().zip(chunks) {
+        value.write(T::from_bytes_endian(*chunk, endian));
+    }
+    // SAFETY: every value was initialized above
+    unsafe { assume_init_mut(out) }
+}
+
+fn write_uninit<'a>(out: &'a mut [MaybeUninit<u8>], bytes: &[u8]) -> &'a mut [u8] {
+    for (dst, src) in out.iter_mut().zip(bytes) {
+        dst.write(*src);
+    }
+    // SAFETY: `out` and `bytes` are the same length, so every byte was
+    // initialized above
+    unsafe { assume_init_mut(out) }
+}
+
+/// # Safety
+/// - Every element of `slice` must be initialized.
+unsafe fn assume_init_mut<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
+    // SAFETY: upheld by caller, and `MaybeUninit<T>` has the same layout as `T`
+    unsafe { &mut *(slice as *mut [MaybeUninit<T>] as *mut [T]) }
+}
+
+/// Split interleaved records of `C` values each into per-channel slices,
+/// according to a run-time endianness, returning any trailing bytes which
+/// don't make up a whole record.
+///
+/// This is the conversion from an "array of structs" to a "struct of arrays",
+/// e.g for stereo audio or `xyz` coordinates.
+///
+/// ```
+/// use bitendian::{slice, Endian};
+///
+/// let bytes = [0, 1, 0, 2, 0, 3, 0, 4];
+/// let (mut left, mut right) = ([0i16; 2], [0i16; 2]);
+/// let rest = slice::deinterleave_endian(&bytes, Endian::Big, [&mut left, &mut right]);
+/// assert_eq!((left, right), ([1, 3], [2, 4]));
+/// assert!(rest.is_empty());
+/// ```
+///
+/// As in [`iter_endian`], `bytes` holds no zero-width values, so is all
+/// returned as trailing bytes.
+///
+/// # Panics
+/// - If `C` is zero.
+/// - If any channel is shorter than the number of whole records in `bytes`.
+pub fn deinterleave_endian<'a, const N: usize, T: BitEndian<N>, const C: usize>(
+    bytes: &'a [u8],
+    endian: Endian,
+    mut channels: [&mut [T]; C],
+) -> &'a [u8] {
+    assert!(C != 0, "records must have at least one channel");
+    if N == 0 {
+        return bytes;
+    }
+    let (values, _) = bytes.as_chunks::<N>();
+    let records = values.chunks_exact(C);
+    let rest = &bytes[records.len() * C * N..];
+    assert!(
+        channels.iter().all(|it| it.len() >= records.len()),
+        "channels are shorter than the {} records",
+        records.len()
+    );
+    for (i, record) in records.enumerate() {
+        for (channel, value) in channels.iter_mut().zip(record) {
+            channel[i] = T::from_bytes_endian(*value, endian);
+        }
+    }
+    rest
+}
+
+/// The inverse of [`deinterleave_endian`], writing records of `C` values,
+/// one from each channel, to `out`.
+///
+/// # Panics
+/// - If `C` is zero.
+/// - If the channels are of different lengths.
+/// - If `out` isn't exactly long enough for the records.
+pub fn interleave_endian<const N: usize, T: BitEndian<N> + Copy, const C: usize>(
+    channels: [&[T]; C],
+    endian: Endian,
+    out: &mut [u8],
+) {
+    assert!(C != 0, "records must have at least one channel");
+    let records = channels.first().map(|it| it.len()).unwrap_or_default();
+    assert!(
+        channels.iter().all(|it| it.len() == records),
+        "channels are of different lengths"
+    );
+    assert_eq!(out.len(), records * C * N, "output is the wrong length");
+    if N == 0 {
+        // zero-width values have no bytes to copy
+        return;
+    }
+    let (values, _) = out.as_chunks_mut::<N>();
+    for (i, record) in values.chunks_exact_mut(C).enumerate() {
+        for (value, channel) in record.iter_mut().zip(channels) {
+            *value = channel[i].to_bytes_endian(endian);
+        }
+    }
+}
+
+/// Split interleaved records into new per-channel vectors, as in
+/// [`deinterleave_endian`].
+///
+/// Any trailing bytes which don't make up a whole record are ignored.
+///
+/// # Panics
+/// - If `C` is zero.
+#[cfg(feature = "alloc")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn deinterleave_vecs<const N: usize, T: BitEndian<N> + Copy + Default, const C: usize>(
+    bytes: &[u8],
+    endian: Endian,
+) -> [Vec<T>; C] {
+    assert!(C != 0, "records must have at least one channel");
+    // zero-width records never fill any bytes, as in `deinterleave_endian`
+    let records = bytes.len().checked_div(N * C).unwrap_or(0);
+    let mut channels = core::array::from_fn(|_| alloc::vec![T::default(); records]);
+    deinterleave_endian(bytes, endian, channels.each_mut().map(Vec::as_mut_slice));
+    channels
+}
+
+/// Interleave per-channel slices into a new buffer, as in
+/// [`interleave_endian`].
+///
+/// # Panics
+/// - If `C` is zero.
+/// - If the channels are of different lengths.
+#[cfg(feature = "alloc")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn interleave_vec<const N: usize, T: BitEndian<N> + Copy, const C: usize>(
+    channels: [&[T]; C],
+    endian: Endian,
+) -> Vec<u8> {
+    let records = channels.first().map(|it| it.len()).unwrap_or_default();
+    let mut out = alloc::vec![0; records * C * N];
+    interleave_endian(channels, endian, &mut out);
+    out
+}
+
+/// Decode all of `bytes` into a new vector, according to a run-time
+/// endianness.
+///
+/// Fails if `bytes` isn't a whole number of values.
+///
+/// ```
+/// use bitendian::slice::{self, TrailingBytes};
+///
+/// let values: Vec<u16> = slice::decode_all_be(&[0, 1, 0, 2]).unwrap();
+/// assert_eq!(values, [1, 2]);
+/// assert_eq!(
+///     slice::decode_all_be::<2, u16>(&[0, 1, 0]),
+///     Err(TrailingBytes { offset: 2, len: 1 })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn decode_all_endian<const N: usize, T: BitEndian<N>>(
+    bytes: &[u8],
+    endian: Endian,
+) -> Result<Vec<T>, TrailingBytes> {
+    let values = iter_endian(bytes, endian);
+    match values.remainder().len() {
+        0 => Ok(values.collect()),
+        len => Err(TrailingBytes {
+            offset: bytes.len() - len,
+            len,
+        }),
+    }
+}
+
+/// Decode all with [`Endian::Big`], as in [`decode_all_endian`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn decode_all_be<const N: usize, T: BitEndian<N>>(
+    bytes: &[u8],
+) -> Result<Vec<T>, TrailingBytes> {
+    decode_all_endian(bytes, Endian::Big)
+}
+
+/// Decode all with [`Endian::Little`], as in [`decode_all_endian`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn decode_all_le<const N: usize, T: BitEndian<N>>(
+    bytes: &[u8],
+) -> Result<Vec<T>, TrailingBytes> {
+    decode_all_endian(bytes, Endian::Little)
+}
+
+/// Decode all with [`Endian::Native`], as in [`decode_all_endian`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(do_doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn decode_all_ne<const N: usize, T: BitEndian<N>>(
+    bytes: &[u8],
+) -> Result<Vec<T>, TrailingBytes> {
+    decode_all_endian(bytes, Endian::Native)
+}
+
+/// Error returned by [`decode_all_endian`] when the input isn't a whole
+/// number of values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrailingBytes {
+    /// The offset of the first byte which isn't part of a whole value.
+    pub offset: usize,
+    /// The number of bytes left over.
+    pub len: usize,
+}
+
+impl fmt::Display for TrailingBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "{} trailing bytes at offset {} don't make up a whole value",
+            self.len, self.offset
+        ))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TrailingBytes {}
+
+/// As [`std::io::ErrorKind::InvalidData`].
+#[cfg(feature = "std")]
+impl From<TrailingBytes> for std::io::Error {
+    fn from(value: TrailingBytes) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, value)
+    }
+}
+
+fn decode<const N: usize, T: BitEndian<N>>(chunk: &[u8], endian: Endian) -> T {
+    let bytes = <[u8; N]>::try_from(chunk).expect("ChunksExact yields chunks of length N");
+    T::from_bytes_endian(bytes, endian)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_put() {
+        let mut bytes = [0u8; 4];
+        assert_eq!(put_le(&mut bytes, 1, 0x0102u16), Some(()));
+        assert_eq!(put_le(&mut bytes, 3, 0x0304u16), None);
+        assert_eq!(put_ne(&mut bytes, usize::MAX, 0u8), None);
+        assert_eq!(bytes, [0, 2, 1, 0]);
+        assert_eq!(get_le::<2, u16>(&bytes, 1), Some(0x0102));
+        assert_eq!(get_ne::<1, u8>(&bytes, 4), None);
+        assert_eq!(get_be::<1, u8>(&bytes, 5), None);
+    }
+
+    #[test]
+    fn unchecked() {
+        let mut bytes = [0u8; 5];
+        // SAFETY: in bounds
+        unsafe {
+            put_be_unchecked(&mut bytes, 1, 0x01020304u32);
+            put_le_unchecked(&mut bytes, 0, 0xffu8);
+            assert_eq!(get_le_unchecked::<4, u32>(&bytes, 1), 0x04030201);
+            assert_eq!(get_be_unchecked::<2, u16>(&bytes, 3), 0x0304);
+        }
+        assert_eq!(bytes, [0xff, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn uninit() {
+        let mut buf = [MaybeUninit::uninit(); 5];
+        let (init, rest) = encode_uninit(-2i32, Endian::Little, &mut buf).unwrap();
+        assert_eq!(init, [0xfe, 0xff, 0xff, 0xff]);
+        assert!(encode_uninit(0u16, Endian::Little, rest).is_none());
+        assert!(encode_slice_uninit(&[0u8; 2], Endian::Little, rest).is_none());
+        let (init, rest) = encode_slice_uninit(&[7u8], Endian::Little, rest).unwrap();
+        assert_eq!((&*init, rest.len()), (&[7][..], 0));
+
+        let mut values = [MaybeUninit::<u16>::uninit(); 2];
+        assert_eq!(
+            decode_slice_uninit(&[1, 0, 2, 0, 3], Endian::Little, &mut values),
+            [1u16, 2]
+        );
+        assert_eq!(
+            decode_slice_uninit(&[1, 0, 2], Endian::Little, &mut values),
+            [1u16]
+        );
+    }
+
+    #[test]
+    fn array() {
+        let mut bytes = [0; 6];
+        encode_slice(&[1u16, 2, 3], Endian::Little, &mut bytes);
+        assert_eq!(bytes, [1, 0, 2, 0, 3, 0]);
+        assert_eq!(
+            array_endian::<2, u16, 3>(&bytes, Endian::Little),
+            Some([1, 2, 3])
+        );
+        assert_eq!(array_endian::<2, u16, 3>(&bytes[1..], Endian::Little), None);
+        assert_eq!(array_endian::<4, u32, 0>(&[], Endian::Little), Some([]));
+        assert_eq!(
+            array_endian::<0, [u8; 0], 2>(&[], Endian::Little),
+            Some([[]; 2])
+        );
+        assert_eq!(array_endian::<0, [u8; 0], 2>(&[1], Endian::Little), None);
+
+        let mut values = [0i16; 3];
+        decode_slice(&bytes, Endian::Big, &mut values);
+        assert_eq!(values, [0x100, 0x200, 0x300]);
+    }
+
+    #[test]
+    fn zero_width() {
+        let values = [[0u8; 0]; 3];
+        encode_slice(&values, Endian::Big, &mut []);
+        let mut decoded = [[0u8; 0]; 2];
+        decode_slice(&[], Endian::Big, &mut decoded);
+        assert_eq!(decoded, [[]; 2]);
+
+        let mut buf = [MaybeUninit::uninit(); 2];
+        let (init, rest) = encode_slice_uninit(&values, Endian::Little, &mut buf).unwrap();
+        assert_eq!((init.len(), rest.len()), (0, 2));
+        let mut uninit = [MaybeUninit::<[u8; 0]>::uninit(); 2];
+        assert_eq!(
+            decode_slice_uninit(&[1], Endian::Little, &mut uninit),
+            [[]; 2]
+        );
+    }
+
+    #[test]
+    #[should_panic = "input is the wrong length"]
+    fn decode_slice_zero_width_trailing() {
+        decode_slice::<0, [u8; 0]>(&[1], Endian::Big, &mut []);
+    }
+
+    #[test]
+    fn swap() {
+        let mut values = [0x0102u16, 0x0304];
+        swap_to_be_in_place(&mut values);
+        assert_eq!(values.map(u16::to_ne_bytes), [[1, 2], [3, 4]]);
+        swap_to_be_in_place(&mut values);
+        assert_eq!(values, [0x0102, 0x0304]);
+        swap_endian_in_place(&mut values, Endian::Native);
+        assert_eq!(values, [0x0102, 0x0304]);
+    }
+
+    #[test]
+    fn iter() {
+        let bytes = (0..=8).collect::<Vec<u8>>();
+        let it = iter_endian::<4, u32>(&bytes, Endian::Little);
+        assert_eq!(it.remainder(), [8]);
+        assert_eq!(it.rev().collect::<Vec<_>>(), [0x07060504, 0x03020100]);
+        assert_eq!(
+            iter_endian::<2, u16>(&bytes, Endian::Big).nth(3),
+            Some(0x0607)
+        );
+        assert_eq!(iter_endian::<2, u16>(&[], Endian::Big).count(), 0);
+        assert!(iter_le::<2, u16>(&bytes[1..]).eq([0x0201, 0x0403, 0x0605, 0x0807]));
+        assert!(iter_be::<2, i16>(&bytes[..4]).rev().eq([0x0203, 0x0001]));
+        assert_eq!(iter_ne::<2, u16>(&bytes[..1]).len(), 0);
+
+        let it = iter_be::<0, [u8; 0]>(&bytes[..2]);
+        assert_eq!(it.remainder(), [0, 1]);
+        assert_eq!(it.count(), 0);
+        assert_eq!(iter_le::<0, [u8; 0]>(&[]).next_back(), None);
+    }
+
+    #[test]
+    fn decode_all() {
+        assert_eq!(decode_all_le::<4, u32>(&[]), Ok(vec![]));
+        assert_eq!(
+            decode_all_endian::<2, i16>(&[0xff, 0xfe], Endian::Big),
+            Ok(vec![-2])
+        );
+        let e = decode_all_ne::<4, f32>(&[0; 6]).unwrap_err();
+        assert_eq!(e, TrailingBytes { offset: 4, len: 2 });
+        assert_eq!(
+            std::io::Error::from(e).kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn interleave() {
+        let xyz = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let bytes = xyz
+            .iter()
+            .flat_map(|it| it.to_le_bytes())
+            .collect::<Vec<_>>();
+        let mut with_trailer = bytes.clone();
+        with_trailer.extend([0xff; 5]);
+
+        let (mut x, mut y, mut z) = ([0.0f32; 2], [0.0; 2], [0.0; 2]);
+        let rest = deinterleave_endian(&with_trailer, Endian::Little, [&mut x, &mut y, &mut z]);
+        assert_eq!(rest, [0xff; 5]);
+        assert_eq!((x, y, z), ([1.0, 4.0], [2.0, 5.0], [3.0, 6.0]));
+
+        let [x, y, z] = deinterleave_vecs::<4, f32, 3>(&with_trailer, Endian::Little);
+        assert_eq!(interleave_vec([&x, &y, &z], Endian::Little), bytes);
+    }
+
+    #[test]
+    fn interleave_zero_width() {
+        let (mut left, mut right) = ([[0u8; 0]; 1], [[0u8; 0]; 1]);
+        let rest = deinterleave_endian(&[1, 2], Endian::Big, [&mut left, &mut right]);
+        assert_eq!(rest, [1, 2]);
+        let [left, right] = deinterleave_vecs::<0, [u8; 0], 2>(&[1, 2], Endian::Big);
+        assert!(left.is_empty() && right.is_empty());
+        assert!(interleave_vec([&left[..], &right], Endian::Big).is_empty());
+    }
+
+    #[test]
+    #[should_panic = "records must have at least one channel"]
+    fn deinterleave_no_channels() {
+        deinterleave_endian::<2, u16, 0>(&[0; 4], Endian::Little, []);
+    }
+
+    #[test]
+    #[should_panic = "records must have at least one channel"]
+    fn deinterleave_vecs_no_channels() {
+        deinterleave_vecs::<2, u16, 0>(&[0; 4], Endian::Little);
+    }
+}
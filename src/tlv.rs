@@ -0,0 +1,336 @@
+//! Type-length-value records, with configurable field widths.
+//!
+//! ```
+//! use bitendian::{tlv::{Format, Width}, Endian};
+//!
+//! let format = Format::new(Width::U8, Width::U16, Endian::Big);
+//! let bytes = [
+//!     0x01, 0x00, 0x02, b'h', b'i', // tag 1, "hi"
+//!     0x02, 0x00, 0x00,             // tag 2, empty
+//! ];
+//! let records = format
+//!     .iter(&bytes)
+//!     .map(|it| it.map(|it| (it.tag, it.value)))
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//! assert_eq!(records, [(1, &b"hi"[..]), (2, &b""[..])]);
+//! ```
+//!
+//! Nested records can be parsed from a record's [`value`](Tlv::value), using
+//! the same or a different [`Format`].
+
+use crate::{BitEndian, Endian};
+use core::{fmt, iter::FusedIterator};
+
+/// The width of a tag or length field.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Width {
+    /// One byte.
+    U8,
+    /// Two bytes.
+    U16,
+    /// Four bytes.
+    U32,
+    /// Eight bytes.
+    U64,
+}
+
+impl Width {
+    /// The number of bytes in this field.
+    pub const fn bytes(self) -> usize {
+        match self {
+            Width::U8 => 1,
+            Width::U16 => 2,
+            Width::U32 => 4,
+            Width::U64 => 8,
+        }
+    }
+    fn max(self) -> u64 {
+        match self {
+            Width::U8 => u8::MAX.into(),
+            Width::U16 => u16::MAX.into(),
+            Width::U32 => u32::MAX.into(),
+            Width::U64 => u64::MAX,
+        }
+    }
+    fn get(self, bytes: &[u8], endian: Endian) -> u64 {
+        fn get<const N: usize, T: BitEndian<N> + Into<u64>>(bytes: &[u8], endian: Endian) -> u64 {
+            let mut buf = [0u8; N];
+            buf.copy_from_slice(&bytes[..N]);
+            T::from_bytes_endian(buf, endian).into()
+        }
+        match self {
+            Width::U8 => get::<1, u8>(bytes, endian),
+            Width::U16 => get::<2, u16>(bytes, endian),
+            Width::U32 => get::<4, u32>(bytes, endian),
+            Width::U64 => get::<8, u64>(bytes, endian),
+        }
+    }
+    fn put(self, bytes: &mut [u8], value: u64, endian: Endian) {
+        match self {
+            Width::U8 => bytes[..1].copy_from_slice(&(value as u8).to_bytes_endian(endian)),
+            Width::U16 => bytes[..2].copy_from_slice(&(value as u16).to_bytes_endian(endian)),
+            Width::U32 => bytes[..4].copy_from_slice(&(value as u32).to_bytes_endian(endian)),
+            Width::U64 => bytes[..8].copy_from_slice(&value.to_bytes_endian(endian)),
+        }
+    }
+}
+
+/// The layout of a record header.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Format {
+    /// The width of the tag, which comes first.
+    pub tag: Width,
+    /// The width of the length, which follows the tag.
+    pub len: Width,
+    /// The endianness of both the tag and the length.
+    pub endian: Endian,
+    /// Whether the length counts the header as well as the value.
+    pub len_includes_header: bool,
+}
+
+/// A single record, borrowed from its input.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Tlv<'a> {
+    /// The record's type.
+    pub tag: u64,
+    /// The record's payload, excluding the header.
+    pub value: &'a [u8],
+}
+
+impl Format {
+    /// A format where the length only counts the value.
+    pub const fn new(tag: Width, len: Width, endian: Endian) -> Self {
+        Self {
+            tag,
+            len,
+            endian,
+            len_includes_header: false,
+        }
+    }
+
+    /// The number of bytes before the value.
+    pub const fn header_len(&self) -> usize {
+        self.tag.bytes() + self.len.bytes()
+    }
+
+    /// Parse a single record from the start of `input`, returning the rest of
+    /// the input.
+    pub fn parse<'a>(&self, input: &'a [u8]) -> Result<(Tlv<'a>, &'a [u8]), Error> {
+        if input.len() < self.header_len() {
+            return Err(Error::UnexpectedEnd);
+        }
+        let tag = self.tag.get(input, self.endian);
+        let len = self.len.get(&input[self.tag.bytes()..], self.endian);
+        let end = match self.len_includes_header {
+            true => match len >= self.header_len() as u64 {
+                true => len,
+                false => return Err(Error::LengthTooShort),
+            },
+            false => len.saturating_add(self.header_len() as u64),
+        };
+        match usize::try_from(end) {
+            Ok(end) if end <= input.len() => {
+                let (record, rest) = input.split_at(end);
+                let value = &record[self.header_len()..];
+                Ok((Tlv { tag, value }, rest))
+            }
+            _ => Err(Error::UnexpectedEnd),
+        }
+    }
+
+    /// Parse consecutive records from `input`.
+    ///
+    /// The iterator stops after the first error.
+    pub fn iter<'a>(&self, input: &'a [u8]) -> Iter<'a> {
+        Iter {
+            format: *self,
+            input,
+        }
+    }
+
+    /// Write a record to the start of `out`, returning the number of bytes
+    /// written.
+    pub fn encode(&self, tag: u64, value: &[u8], out: &mut [u8]) -> Result<usize, Error> {
+        let mut header = [0u8; 16];
+        let header = self.header(tag, value.len(), &mut header)?;
+        let total = header.len() + value.len();
+        if out.len() < total {
+            return Err(Error::BufferTooSmall);
+        }
+        let (head, tail) = out.split_at_mut(header.len());
+        head.copy_from_slice(header);
+        tail[..value.len()].copy_from_slice(value);
+        Ok(total)
+    }
+
+    /// Write a record.
+    ///
+    /// Tags or values too large for the format are reported as
+    /// [`std::io::ErrorKind::InvalidInput`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(do_doc_cfg, doc(cfg(feature = "std")))]
+    pub fn write(
+        &self,
+        writer: &mut (impl std::io::Write + ?Sized),
+        tag: u64,
+        value: &[u8],
+    ) -> std::io::Result<()> {
+        let mut header = [0u8; 16];
+        let header = self
+            .header(tag, value.len(), &mut header)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        writer.write_all(header)?;
+        writer.write_all(value)
+    }
+
+    fn header<'a>(
+        &self,
+        tag: u64,
+        value_len: usize,
+        buf: &'a mut [u8; 16],
+    ) -> Result<&'a [u8], Error> {
+        let len = match self.len_includes_header {
+            true => value_len.checked_add(self.header_len()),
+            false => Some(value_len),
+        };
+        let len = match len.and_then(|it| u64::try_from(it).ok()) {
+            Some(len) if len <= self.len.max() => len,
+            _ => return Err(Error::ValueTooLarge),
+        };
+        if tag > self.tag.max() {
+            return Err(Error::TagTooLarge);
+        }
+        self.tag.put(buf, tag, self.endian);
+        self.len.put(&mut buf[self.tag.bytes()..], len, self.endian);
+        Ok(&buf[..self.header_len()])
+    }
+}
+
+/// Iterator for [`Format::iter`], see that method for more.
+#[derive(Debug, Clone)]
+pub struct Iter<'a> {
+    format: Format,
+    input: &'a [u8],
+}
+
+impl<'a> Iter<'a> {
+    /// The input which has yet to be parsed.
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.input
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Result<Tlv<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() {
+            return None;
+        }
+        match self.format.parse(self.input) {
+            Ok((tlv, rest)) => {
+                self.input = rest;
+                Some(Ok(tlv))
+            }
+            Err(e) => {
+                self.input = &[];
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl FusedIterator for Iter<'_> {}
+
+/// Error returned when parsing or encoding records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input ended part-way through a record.
+    UnexpectedEnd,
+    /// The length (which includes the header) is shorter than the header.
+    LengthTooShort,
+    /// The tag doesn't fit in the format's tag width.
+    TagTooLarge,
+    /// The value's length doesn't fit in the format's length width.
+    ValueTooLarge,
+    /// The output buffer is too small for the record.
+    BufferTooSmall,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::UnexpectedEnd => "unexpected end of record",
+            Error::LengthTooShort => "record length is shorter than its header",
+            Error::TagTooLarge => "tag too large for format",
+            Error::ValueTooLarge => "value too large for format",
+            Error::BufferTooSmall => "buffer too small for record",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested() {
+        let outer = Format {
+            len_includes_header: true,
+            ..Format::new(Width::U16, Width::U32, Endian::Little)
+        };
+        let inner = Format::new(Width::U8, Width::U8, Endian::Big);
+
+        let mut child = [0u8; 4];
+        assert_eq!(inner.encode(7, b"ab", &mut child), Ok(4));
+        let mut buf = vec![];
+        outer.write(&mut buf, 0x0102, &child).unwrap();
+        assert_eq!(buf, [0x02, 0x01, 10, 0, 0, 0, 7, 2, b'a', b'b']);
+
+        let (parent, rest) = outer.parse(&buf).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(parent.tag, 0x0102);
+        let children = inner.iter(parent.value).collect::<Vec<_>>();
+        assert_eq!(
+            children,
+            [Ok(Tlv {
+                tag: 7,
+                value: b"ab"
+            })]
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let format = Format::new(Width::U8, Width::U8, Endian::Big);
+        assert_eq!(format.parse(&[1]), Err(Error::UnexpectedEnd));
+        assert_eq!(format.parse(&[1, 2, 0]), Err(Error::UnexpectedEnd));
+        let mut it = format.iter(&[1, 0, 2]);
+        assert!(matches!(it.next(), Some(Ok(_))));
+        assert_eq!(it.next(), Some(Err(Error::UnexpectedEnd)));
+        assert_eq!(it.next(), None);
+
+        let inclusive = Format {
+            len_includes_header: true,
+            ..format
+        };
+        assert_eq!(inclusive.parse(&[1, 1]), Err(Error::LengthTooShort));
+        assert_eq!(
+            format.encode(256, &[], &mut [0; 2]),
+            Err(Error::TagTooLarge)
+        );
+        assert_eq!(
+            format.encode(0, &[0; 256], &mut [0; 300]),
+            Err(Error::ValueTooLarge)
+        );
+        assert_eq!(
+            format.encode(0, &[0], &mut [0; 2]),
+            Err(Error::BufferTooSmall)
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! [`SystemTime`]s as whole seconds since the Unix epoch, as found in e.g tar
+//! headers, zip extra fields and pcap records.
+//!
+//! The width and signedness of the field are chosen by the integer type, e.g
+//! `u32` or `i64`.
+//!
+//! ```
+//! use bitendian::{unix_time, Endian};
+//! use std::time::{Duration, SystemTime};
+//!
+//! # fn doit() -> std::io::Result<()> {
+//! let time = SystemTime::UNIX_EPOCH + Duration::from_secs(0x01020304);
+//!
+//! let mut buf = vec![];
+//! unix_time::write_endian::<4, u32>(&mut buf, time, Endian::Little)?;
+//! assert_eq!(buf, [4, 3, 2, 1]);
+//!
+//! let read = unix_time::read_endian::<4, u32>(&mut buf.as_slice(), Endian::Little)?;
+//! assert_eq!(read, time);
+//! # Ok(())
+//! # }
+//! # doit().unwrap()
+//! ```
+
+use crate::{BitEndian, Endian};
+use std::{
+    io,
+    time::{Duration, SystemTime},
+};
+
+/// Whole seconds since the Unix epoch, rounding towards the past.
+///
+/// Returns [`None`] if the result doesn't fit in a `T`, e.g for times before
+/// 1970 when `T` is unsigned.
+pub fn to_seconds<T: TryFrom<i64>>(time: SystemTime) -> Option<T> {
+    let seconds = match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(after) => i64::try_from(after.as_secs()).ok()?,
+        Err(before) => {
+            let before = before.duration();
+            let whole = i64::try_from(before.as_secs()).ok()?;
+            match before.subsec_nanos() {
+                0 => -whole,
+                _ => (-whole).checked_sub(1)?,
+            }
+        }
+    };
+    T::try_from(seconds).ok()
+}
+
+/// The time `seconds` after (or before) the Unix epoch.
+///
+/// Returns [`None`] if the platform can't represent the time.
+pub fn from_seconds(seconds: i64) -> Option<SystemTime> {
+    let magnitude = Duration::from_secs(seconds.unsigned_abs());
+    match seconds.is_negative() {
+        false => SystemTime::UNIX_EPOCH.checked_add(magnitude),
+        true => SystemTime::UNIX_EPOCH.checked_sub(magnitude),
+    }
+}
+
+/// Read a time stored as a `T` according to a run-time endianness.
+///
+/// Times the platform can't represent are reported as
+/// [`io::ErrorKind::InvalidData`].
+pub fn read_endian<const N: usize, T>(
+    reader: &mut (impl io::Read + ?Sized),
+    endian: Endian,
+) -> io::Result<SystemTime>
+where
+    T: BitEndian<N> + Into<i64>,
+{
+    let mut bytes = [0u8; N];
+    reader.read_exact(&mut bytes)?;
+    from_seconds(T::from_bytes_endian(bytes, endian).into()).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "time is out of range for this platform",
+        )
+    })
+}
+
+/// Write a time as a `T` according to a run-time endianness.
+///
+/// Times which don't fit in a `T` are reported as
+/// [`io::ErrorKind::InvalidInput`].
+pub fn write_endian<const N: usize, T>(
+    writer: &mut (impl io::Write + ?Sized),
+    time: SystemTime,
+    endian: Endian,
+) -> io::Result<()>
+where
+    T: BitEndian<N> + TryFrom<i64>,
+{
+    let seconds = to_seconds::<T>(time).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "time is out of range for the field",
+        )
+    })?;
+    writer.write_all(&seconds.to_bytes_endian(endian))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn before_epoch() {
+        let time = SystemTime::UNIX_EPOCH - Duration::from_millis(1500);
+        assert_eq!(to_seconds::<i64>(time), Some(-2));
+        assert_eq!(to_seconds::<u32>(time), None);
+        assert_eq!(
+            from_seconds(-2),
+            Some(SystemTime::UNIX_EPOCH - Duration::from_secs(2))
+        );
+
+        let mut buf = vec![];
+        write_endian::<8, i64>(&mut buf, time, Endian::Big).unwrap();
+        assert_eq!(buf, (-2i64).to_be_bytes());
+        assert_eq!(
+            write_endian::<4, u32>(&mut buf, time, Endian::Big)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! [`Uuid`]s are big-endian in [RFC 4122](https://www.rfc-editor.org/rfc/rfc4122),
+//! but Microsoft's GUIDs store the first three fields little-endian.
+//!
+//! [`Endian::Big`](crate::Endian::Big) is the RFC layout, and
+//! [`Endian::Little`](crate::Endian::Little) is the GUID layout, as in
+//! [`Uuid::to_bytes_le`].
+
+use crate::BitEndian;
+use uuid::Uuid;
+
+/// Big-endian is the RFC 4122 layout, and little-endian is the Microsoft GUID
+/// layout, as in [`Uuid::to_bytes_le`].
+impl BitEndian<16> for Uuid {
+    fn to_le_bytes(self) -> [u8; 16] {
+        Uuid::to_bytes_le(&self)
+    }
+    fn to_be_bytes(self) -> [u8; 16] {
+        self.into_bytes()
+    }
+    fn to_ne_bytes(self) -> [u8; 16] {
+        match cfg!(target_endian = "big") {
+            true => BitEndian::to_be_bytes(self),
+            false => BitEndian::to_le_bytes(self),
+        }
+    }
+
+    fn from_le_bytes(bytes: [u8; 16]) -> Self {
+        Uuid::from_bytes_le(bytes)
+    }
+    fn from_be_bytes(bytes: [u8; 16]) -> Self {
+        Uuid::from_bytes(bytes)
+    }
+    fn from_ne_bytes(bytes: [u8; 16]) -> Self {
+        match cfg!(target_endian = "big") {
+            true => Self::from_be_bytes(bytes),
+            false => Self::from_le_bytes(bytes),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::io::Decode for Uuid {
+    fn decode<R: std::io::Read + ?Sized>(
+        reader: &mut R,
+        endian: crate::Endian,
+    ) -> std::io::Result<Self> {
+        let mut bytes = [0; 16];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes_endian(bytes, endian))
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::io::Encode for Uuid {
+    fn encode<W: std::io::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        endian: crate::Endian,
+    ) -> std::io::Result<()> {
+        writer.write_all(&self.to_bytes_endian(endian))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layouts() {
+        let id = Uuid::from_u128(0x00112233_4455_6677_8899_aabbccddeeff);
+        assert_eq!(
+            BitEndian::to_be_bytes(id),
+            [
+                0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+                0xee, 0xff
+            ]
+        );
+        let guid = BitEndian::to_le_bytes(id);
+        assert_eq!(
+            guid,
+            [
+                0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+                0xee, 0xff
+            ]
+        );
+        assert_eq!(<Uuid as BitEndian<16>>::from_le_bytes(guid), id);
+    }
+}
@@ -0,0 +1,109 @@
+//! [`ux`](https://docs.rs/ux/0.1/ux/) integers are read from the smallest
+//! primitive which holds them, and rejected if the value is out of range.
+//!
+//! They implement [`TryBitEndian`] rather than [`BitEndian`](crate::BitEndian),
+//! so read them with e.g `io::ReadExt::try_read_be`.
+//! Write them by converting to the primitive, e.g with `u8::from`.
+
+use crate::{int::OutOfRange, TryBitEndian};
+use core::ops::{Shl, Shr};
+
+macro_rules! ux {
+    ($repr:ty, $n:literal: $($name:ident),* $(,)?) => {
+        $(
+            impl TryBitEndian<$n> for ux::$name {
+                type Error = OutOfRange;
+
+                fn try_from_le_bytes(bytes: [u8; $n]) -> Result<Self, Self::Error> {
+                    narrow(<$repr>::from_le_bytes(bytes), Self::BITS, Self::new)
+                }
+                fn try_from_be_bytes(bytes: [u8; $n]) -> Result<Self, Self::Error> {
+                    narrow(<$repr>::from_be_bytes(bytes), Self::BITS, Self::new)
+                }
+                fn try_from_ne_bytes(bytes: [u8; $n]) -> Result<Self, Self::Error> {
+                    narrow(<$repr>::from_ne_bytes(bytes), Self::BITS, Self::new)
+                }
+            }
+        )*
+    };
+}
+
+/// Call `new` if `value` fits in `bits`.
+fn narrow<T, U>(value: T, bits: u32, new: fn(T) -> U) -> Result<U, OutOfRange>
+where
+    T: Copy + PartialEq + Shl<u32, Output = T> + Shr<u32, Output = T>,
+{
+    let shift = (size_of::<T>() * 8) as u32 - bits;
+    // shifting back down sign-extends signed types
+    match value << shift >> shift == value {
+        true => Ok(new(value)),
+        false => Err(OutOfRange),
+    }
+}
+
+ux!(u8, 1:
+    u1, u2, u3, u4, u5, u6, u7
+);
+ux!(i8, 1:
+    i1, i2, i3, i4, i5, i6, i7
+);
+ux!(u16, 2:
+    u9, u10, u11, u12, u13, u14, u15
+);
+ux!(i16, 2:
+    i9, i10, i11, i12, i13, i14, i15
+);
+ux!(u32, 4:
+    u17, u18, u19, u20, u21, u22, u23, u24, u25, u26, u27, u28, u29, u30, u31
+);
+ux!(i32, 4:
+    i17, i18, i19, i20, i21, i22, i23, i24, i25, i26, i27, i28, i29, i30, i31
+);
+ux!(u64, 8:
+    u33, u34, u35, u36, u37, u38, u39, u40, u41, u42, u43, u44, u45, u46, u47, u48, u49,
+    u50, u51, u52, u53, u54, u55, u56, u57, u58, u59, u60, u61, u62, u63
+);
+ux!(i64, 8:
+    i33, i34, i35, i36, i37, i38, i39, i40, i41, i42, i43, i44, i45, i46, i47, i48, i49,
+    i50, i51, i52, i53, i54, i55, i56, i57, i58, i59, i60, i61, i62, i63
+);
+ux!(u128, 16:
+    u65, u66, u67, u68, u69, u70, u71, u72, u73, u74, u75, u76, u77, u78, u79, u80, u81,
+    u82, u83, u84, u85, u86, u87, u88, u89, u90, u91, u92, u93, u94, u95, u96, u97, u98,
+    u99, u100, u101, u102, u103, u104, u105, u106, u107, u108, u109, u110, u111, u112, u113,
+    u114, u115, u116, u117, u118, u119, u120, u121, u122, u123, u124, u125, u126, u127
+);
+ux!(i128, 16:
+    i65, i66, i67, i68, i69, i70, i71, i72, i73, i74, i75, i76, i77, i78, i79, i80, i81,
+    i82, i83, i84, i85, i86, i87, i88, i89, i90, i91, i92, i93, i94, i95, i96, i97, i98,
+    i99, i100, i101, i102, i103, i104, i105, i106, i107, i108, i109, i110, i111, i112, i113,
+    i114, i115, i116, i117, i118, i119, i120, i121, i122, i123, i124, i125, i126, i127
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{int::OutOfRange, io::ReadExt as _, TryBitEndian as _};
+    use ux::{i127, i7, u12, u127, u7};
+
+    #[test]
+    fn narrow() {
+        assert_eq!(u7::try_from_be_bytes([0x7f]), Ok(u7::MAX));
+        assert_eq!(u7::try_from_be_bytes([0x80]), Err(OutOfRange));
+        assert_eq!(i7::try_from_le_bytes([0xc0]), Ok(i7::MIN));
+        assert_eq!(i7::try_from_le_bytes([0x40]), Err(OutOfRange));
+        assert_eq!(u12::try_from_be_bytes([0x0f, 0xff]), Ok(u12::MAX));
+        assert_eq!(u12::try_from_le_bytes([0x0f, 0xff]), Err(OutOfRange));
+        assert_eq!(u127::try_from_ne_bytes([0xff; 16]), Err(OutOfRange));
+        assert_eq!(i127::try_from_ne_bytes([0xff; 16]), Ok(i127::new(-1)));
+    }
+
+    #[test]
+    fn read() {
+        let mut r = [0x00, 0x05, 0xff, 0xff].as_slice();
+        assert_eq!(r.try_read_be::<u12>().unwrap(), u12::new(5));
+        assert_eq!(
+            r.try_read_be::<u12>().unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use crate::{BitEndian, Endian};
+use core::{cmp::Ordering, fmt, hash::Hash, marker::PhantomData, ops};
+
+/// Operators on the stored values, with either a wrapper or a plain value on
+/// the right.
+macro_rules! ops {
+    ($name:ident { $($op:ident::$method:ident, $assign:ident::$assign_method:ident),* $(,)? }) => {
+        $(
+            impl<T: BitEndian<N> + ops::$op<Output = T>, const N: usize> ops::$op for $name<T, N> {
+                type Output = Self;
+                fn $method(self, rhs: Self) -> Self {
+                    Self::new(ops::$op::$method(self.get(), rhs.get()))
+                }
+            }
+            impl<T: BitEndian<N> + ops::$op<Output = T>, const N: usize> ops::$op<T> for $name<T, N> {
+                type Output = Self;
+                fn $method(self, rhs: T) -> Self {
+                    Self::new(ops::$op::$method(self.get(), rhs))
+                }
+            }
+            impl<T: BitEndian<N> + ops::$op<Output = T>, const N: usize> ops::$assign for $name<T, N> {
+                fn $assign_method(&mut self, rhs: Self) {
+                    *self = ops::$op::$method(*self, rhs)
+                }
+            }
+            impl<T: BitEndian<N> + ops::$op<Output = T>, const N: usize> ops::$assign<T> for $name<T, N> {
+                fn $assign_method(&mut self, rhs: T) {
+                    *self = ops::$op::$method(*self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! wrapper {
+    ($name:ident, $endian:expr, $to:ident, $from:ident, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// This has the same size as `T`, but an alignment of `1`, and its
+        /// memory representation is the same on every platform, so it is suitable
+        /// for describing wire and file layouts field-by-field.
+        ///
+        /// `N` is the width of `T`, as in [`BitEndian<N>`].
+        #[repr(transparent)]
+        #[cfg_attr(
+            feature = "zerocopy",
+            derive(
+                zerocopy::FromBytes,
+                zerocopy::IntoBytes,
+                zerocopy::Unaligned,
+                zerocopy::KnownLayout,
+                zerocopy::Immutable,
+            )
+        )]
+        pub struct $name<T, const N: usize> {
+            bytes: [u8; N],
+            _value: PhantomData<T>,
+        }
+
+        impl<T: BitEndian<N>, const N: usize> $name<T, N> {
+            /// The [`Endian`] this type is stored in.
+            pub const ENDIAN: Endian = $endian;
+            /// Store `value`.
+            pub fn new(value: T) -> Self {
+                Self::from_bytes(value.$to())
+            }
+            /// Return the stored value.
+            pub fn get(self) -> T {
+                T::$from(self.bytes)
+            }
+            /// Replace the stored value.
+            pub fn set(&mut self, value: T) {
+                self.bytes = value.$to()
+            }
+        }
+
+        impl<T, const N: usize> $name<T, N> {
+            /// Wrap the stored representation of a value.
+            pub const fn from_bytes(bytes: [u8; N]) -> Self {
+                Self {
+                    bytes,
+                    _value: PhantomData,
+                }
+            }
+            /// Return the stored representation of the value.
+            pub const fn to_bytes(self) -> [u8; N] {
+                self.bytes
+            }
+            /// Borrow the stored representation of the value.
+            pub const fn as_bytes(&self) -> &[u8; N] {
+                &self.bytes
+            }
+            /// View `bytes` as stored values without copying, e.g to index
+            /// a memory-mapped file as a typed array.
+            ///
+            /// Returns [`None`] if the length of `bytes` isn't a multiple of
+            /// `N`, or if `N` is zero.
+            /// There is no alignment requirement.
+            pub fn slice_from_bytes(bytes: &[u8]) -> Option<&[Self]> {
+                if N == 0 {
+                    return None;
+                }
+                let (values, []) = bytes.as_chunks::<N>() else {
+                    return None;
+                };
+                // SAFETY: `Self` is `repr(transparent)` over `[u8; N]`
+                Some(unsafe { &*(values as *const [[u8; N]] as *const [Self]) })
+            }
+            /// Mutably view `bytes` as stored values, as in
+            /// [`Self::slice_from_bytes`].
+            pub fn slice_from_bytes_mut(bytes: &mut [u8]) -> Option<&mut [Self]> {
+                if N == 0 {
+                    return None;
+                }
+                let (values, []) = bytes.as_chunks_mut::<N>() else {
+                    return None;
+                };
+                // SAFETY: `Self` is `repr(transparent)` over `[u8; N]`
+                Some(unsafe { &mut *(values as *mut [[u8; N]] as *mut [Self]) })
+            }
+            /// View stored values as bytes without copying.
+            pub fn slice_as_bytes(values: &[Self]) -> &[u8] {
+                // SAFETY: `Self` is `repr(transparent)` over `[u8; N]`
+                let values = unsafe { &*(values as *const [Self] as *const [[u8; N]]) };
+                values.as_flattened()
+            }
+            /// Mutably view stored values as bytes, as in
+            /// [`Self::slice_as_bytes`].
+            pub fn slice_as_bytes_mut(values: &mut [Self]) -> &mut [u8] {
+                // SAFETY: `Self` is `repr(transparent)` over `[u8; N]`
+                let values = unsafe { &mut *(values as *mut [Self] as *mut [[u8; N]]) };
+                values.as_flattened_mut()
+            }
+        }
+
+        impl<T, const N: usize> Clone for $name<T, N> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+        impl<T, const N: usize> Copy for $name<T, N> {}
+        impl<T, const N: usize> PartialEq for $name<T, N> {
+            fn eq(&self, other: &Self) -> bool {
+                self.bytes == other.bytes
+            }
+        }
+        impl<T, const N: usize> Eq for $name<T, N> {}
+        impl<T, const N: usize> Hash for $name<T, N> {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.bytes.hash(state)
+            }
+        }
+        impl<T, const N: usize> Default for $name<T, N> {
+            fn default() -> Self {
+                Self::from_bytes([0; N])
+            }
+        }
+        impl<T: BitEndian<N> + fmt::Debug, const N: usize> fmt::Debug for $name<T, N> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.get()).finish()
+            }
+        }
+
+        // SAFETY: `repr(transparent)` over `[u8; N]`, for which any bytes
+        // are valid
+        #[cfg(feature = "bytemuck")]
+        unsafe impl<T, const N: usize> bytemuck::Zeroable for $name<T, N> {}
+        // SAFETY: as above, and `[u8; N]` has no padding
+        #[cfg(feature = "bytemuck")]
+        unsafe impl<T: 'static, const N: usize> bytemuck::Pod for $name<T, N> {}
+
+        impl<T: BitEndian<N> + fmt::Display, const N: usize> fmt::Display for $name<T, N> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.get().fmt(f)
+            }
+        }
+
+        /// Compares the stored values.
+        ///
+        /// This requires [`Ord`], so that it is consistent with equality of
+        /// the stored bytes.
+        impl<T: BitEndian<N> + Ord, const N: usize> PartialOrd for $name<T, N> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl<T: BitEndian<N> + Ord, const N: usize> Ord for $name<T, N> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        ops!($name {
+            Add::add, AddAssign::add_assign,
+            Sub::sub, SubAssign::sub_assign,
+            Mul::mul, MulAssign::mul_assign,
+            Div::div, DivAssign::div_assign,
+            Rem::rem, RemAssign::rem_assign,
+            BitAnd::bitand, BitAndAssign::bitand_assign,
+            BitOr::bitor, BitOrAssign::bitor_assign,
+            BitXor::bitxor, BitXorAssign::bitxor_assign,
+        });
+
+        impl<T: BitEndian<N> + ops::Not<Output = T>, const N: usize> ops::Not for $name<T, N> {
+            type Output = Self;
+            fn not(self) -> Self {
+                Self::new(!self.get())
+            }
+        }
+
+        impl<T: BitEndian<N> + ops::Neg<Output = T>, const N: usize> ops::Neg for $name<T, N> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self::new(-self.get())
+            }
+        }
+
+        impl<T: BitEndian<N>, const N: usize> From<T> for $name<T, N> {
+            fn from(value: T) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl<T: BitEndian<N>, const N: usize> BitEndian<N> for $name<T, N> {
+            fn to_le_bytes(self) -> [u8; N] {
+                self.get().to_le_bytes()
+            }
+            fn to_be_bytes(self) -> [u8; N] {
+                self.get().to_be_bytes()
+            }
+            fn to_ne_bytes(self) -> [u8; N] {
+                self.get().to_ne_bytes()
+            }
+
+            fn from_le_bytes(bytes: [u8; N]) -> Self {
+                Self::new(T::from_le_bytes(bytes))
+            }
+            fn from_be_bytes(bytes: [u8; N]) -> Self {
+                Self::new(T::from_be_bytes(bytes))
+            }
+            fn from_ne_bytes(bytes: [u8; N]) -> Self {
+                Self::new(T::from_ne_bytes(bytes))
+            }
+        }
+
+        /// Reads the stored bytes, ignoring the run-time endianness.
+        #[cfg(feature = "std")]
+        impl<T, const N: usize> crate::io::Decode for $name<T, N> {
+            fn decode<R: std::io::Read + ?Sized>(reader: &mut R, _: Endian) -> std::io::Result<Self> {
+                let mut bytes = [0; N];
+                reader.read_exact(&mut bytes)?;
+                Ok(Self::from_bytes(bytes))
+            }
+        }
+
+        /// Writes the stored bytes, ignoring the run-time endianness.
+        #[cfg(feature = "std")]
+        impl<T, const N: usize> crate::io::Encode for $name<T, N> {
+            fn encode<W: std::io::Write + ?Sized>(&self, writer: &mut W, _: Endian) -> std::io::Result<()> {
+                writer.write_all(&self.bytes)
+            }
+        }
+    };
+}
+
+wrapper!(
+    Be,
+    Endian::Big,
+    to_be_bytes,
+    from_be_bytes,
+    "A `T`, stored in big-endian byte order."
+);
+wrapper!(
+    Le,
+    Endian::Little,
+    to_le_bytes,
+    from_le_bytes,
+    "A `T`, stored in little-endian byte order."
+);
+
+macro_rules! aliases {
+    ($($width:literal { $($ty:ty => $be:ident, $le:ident),* $(,)? }),* $(,)?) => {
+        $($(
+            #[doc = concat!("A big-endian `", stringify!($ty), "`.")]
+            pub type $be = Be<$ty, $width>;
+            #[doc = concat!("A little-endian `", stringify!($ty), "`.")]
+            pub type $le = Le<$ty, $width>;
+        )*)*
+    };
+}
+aliases!(
+    2 { u16 => U16Be, U16Le, i16 => I16Be, I16Le },
+    4 { u32 => U32Be, U32Le, i32 => I32Be, I32Le, f32 => F32Be, F32Le },
+    8 { u64 => U64Be, U64Le, i64 => I64Be, I64Le, f64 => F64Be, F64Le },
+    16 { u128 => U128Be, U128Le, i128 => I128Be, I128Le },
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        assert_eq!(core::mem::size_of::<Be<u32, 4>>(), 4);
+        assert_eq!(core::mem::align_of::<Le<u64, 8>>(), 1);
+        let mut it = Be::new(1u32);
+        assert_eq!(it.to_bytes(), [0, 0, 0, 1]);
+        it.set(256);
+        assert_eq!(it.get(), 256);
+        assert_eq!(Le::<u16, 2>::from(1).to_bytes(), [1, 0]);
+        assert_eq!(
+            I64Be::new(-2).to_bytes(),
+            [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe]
+        );
+    }
+
+    #[test]
+    fn ops() {
+        let mut it = U32Be::new(1);
+        it += 2;
+        it = it * U32Be::new(3) - 1;
+        assert_eq!(it.get(), 8);
+        assert_eq!((it | 1).to_bytes(), [0, 0, 0, 9]);
+        assert_eq!((!U16Le::new(0)).get(), u16::MAX);
+        assert_eq!((-I32Le::new(2)).get(), -2);
+        assert!(U16Le::new(0x0100) > U16Le::new(0x0001));
+        assert_eq!(U64Le::new(42).to_string(), "42");
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck() {
+        let bytes = [0, 1, 0, 2];
+        let values: &[U16Be] = bytemuck::cast_slice(&bytes);
+        assert_eq!(values, [U16Be::new(1), U16Be::new(2)]);
+        assert_eq!(bytemuck::cast_slice::<_, u8>(values), bytes);
+        assert_eq!(<U32Le as bytemuck::Zeroable>::zeroed(), U32Le::new(0));
+    }
+
+    #[cfg(feature = "zerocopy")]
+    #[test]
+    fn zerocopy() {
+        use zerocopy::{FromBytes as _, IntoBytes as _};
+
+        #[derive(
+            zerocopy::FromBytes,
+            zerocopy::IntoBytes,
+            zerocopy::Unaligned,
+            zerocopy::KnownLayout,
+            zerocopy::Immutable,
+        )]
+        #[repr(C)]
+        struct Header {
+            magic: [u8; 2],
+            len: U32Be,
+            flags: U16Le,
+        }
+
+        let bytes = [b'H', b'D', 0, 0, 1, 0, 3, 0];
+        let header = Header::ref_from_bytes(&bytes).unwrap();
+        assert_eq!(&header.magic, b"HD");
+        assert_eq!(header.len.get(), 256);
+        assert_eq!(header.flags.get(), 3);
+        assert_eq!(header.as_bytes(), bytes);
+    }
+
+    #[test]
+    fn codec() {
+        use crate::io::{DecodeExt as _, EncodeExt as _};
+
+        let mut buf = vec![];
+        buf.encode_le(&U32Be::new(0x01020304)).unwrap();
+        buf.encode_be(&I16Le::new(-2)).unwrap();
+        assert_eq!(buf, [1, 2, 3, 4, 0xfe, 0xff]);
+        let mut r = buf.as_slice();
+        assert_eq!(r.decode_le::<U32Be>().unwrap().get(), 0x01020304);
+        assert_eq!(r.decode_be::<I16Le>().unwrap().get(), -2);
+    }
+
+    #[test]
+    fn view() {
+        let mut bytes = [0, 0, 0, 1, 0, 0, 0, 2, 0xff];
+        let values = Be::<u32, 4>::slice_from_bytes(&bytes[..8]).unwrap();
+        assert_eq!(values.iter().map(|it| it.get()).collect::<Vec<_>>(), [1, 2]);
+        // deliberately misaligned
+        let values = Be::<u32, 4>::slice_from_bytes(&bytes[1..]).unwrap();
+        assert_eq!(
+            values.iter().map(|it| it.get()).collect::<Vec<_>>(),
+            [0x0100, 0x02ff]
+        );
+        assert!(Be::<u32, 4>::slice_from_bytes(&bytes[2..]).is_none());
+        assert_eq!(
+            Le::<u16, 2>::slice_from_bytes(&bytes[..8]).unwrap()[3].get(),
+            0x0200
+        );
+
+        let values = Le::<u32, 4>::slice_from_bytes_mut(&mut bytes[1..]).unwrap();
+        values[0].set(0x04030201);
+        assert_eq!(Le::slice_as_bytes(values), [1, 2, 3, 4, 0, 0, 2, 0xff]);
+        Le::slice_as_bytes_mut(values)[0] = 0;
+        assert_eq!(bytes, [0, 0, 2, 3, 4, 0, 0, 2, 0xff]);
+
+        assert!(Be::<[u8; 0], 0>::slice_from_bytes(&bytes).is_none());
+        assert!(Le::<[u8; 0], 0>::slice_from_bytes_mut(&mut []).is_none());
+    }
+}
@@ -0,0 +1,244 @@
+//! The 80-bit x87 extended precision float, C's `long double` on x86.
+//!
+//! This is used by e.g the sample rate field of [AIFF](https://en.wikipedia.org/wiki/Audio_Interchange_File_Format)
+//! headers.
+//! Rust has no native type, so [`F80`] only stores the bits, and converts to
+//! and from [`f64`].
+//!
+//! ```
+//! use bitendian::{x87::F80, BitEndian};
+//!
+//! let rate = F80::from_be_bytes([0x40, 0x0e, 0xac, 0x44, 0, 0, 0, 0, 0, 0]);
+//! assert_eq!(rate.to_f64(), 44100.0);
+//! assert_eq!(F80::from_f64(44100.0), rate);
+//! ```
+
+use crate::BitEndian;
+
+const EXPONENT_BIAS: i32 = 16383;
+const F64_EXPONENT_BIAS: i32 = 1023;
+const F64_FRACTION: u64 = (1 << 52) - 1;
+const F64_QUIET: u64 = 1 << 51;
+const SIGN: u16 = 1 << 15;
+const INTEGER_BIT: u64 = 1 << 63;
+
+/// An 80-bit x87 extended precision float.
+///
+/// Unlike [`f64`], the integer bit of the significand is stored explicitly.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub struct F80 {
+    /// The sign bit, followed by the 15-bit biased exponent.
+    pub sign_exponent: u16,
+    /// The significand, including the integer bit.
+    pub mantissa: u64,
+}
+
+impl F80 {
+    /// Convert from an [`f64`].
+    ///
+    /// This is lossless, every [`f64`] is exactly representable.
+    pub fn from_f64(value: f64) -> Self {
+        let bits = value.to_bits();
+        let sign = if (bits >> 63) == 1 { SIGN } else { 0 };
+        let exponent = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & F64_FRACTION;
+        let (exponent, mantissa) = match (exponent, fraction) {
+            (0, 0) => (0, 0),
+            (0x7ff, 0) => (0x7fff, INTEGER_BIT),
+            (0x7ff, _) => (0x7fff, INTEGER_BIT | fraction << 11),
+            (0, _) => {
+                // subnormal, normalise it
+                let shift = fraction.leading_zeros();
+                let exponent = 1 - F64_EXPONENT_BIAS - 52 + 63 - shift as i32;
+                ((exponent + EXPONENT_BIAS) as u16, fraction << shift)
+            }
+            (_, _) => (
+                (exponent - F64_EXPONENT_BIAS + EXPONENT_BIAS) as u16,
+                INTEGER_BIT | fraction << 11,
+            ),
+        };
+        Self {
+            sign_exponent: sign | exponent,
+            mantissa,
+        }
+    }
+
+    /// Convert to an [`f64`].
+    ///
+    /// The significand is rounded from 64 to 53 bits (to nearest, ties to
+    /// even), values too large for an [`f64`] become infinite, and values
+    /// too small lose precision as subnormals, or become zero.
+    /// NaN payloads are truncated.
+    pub fn to_f64(self) -> f64 {
+        let sign = u64::from(self.sign_exponent >> 15) << 63;
+        let exponent = i32::from(self.sign_exponent & !SIGN);
+        let bits = match (exponent, self.mantissa) {
+            (0x7fff, m) if m << 1 == 0 => 0x7ff << 52,
+            (0x7fff, m) => 0x7ff << 52 | F64_QUIET | (m << 1) >> 12,
+            (_, 0) => 0,
+            (exponent, m) => {
+                // `m * 2^e`, where `m` has its top bit set
+                let shift = m.leading_zeros();
+                let m = m << shift;
+                let e = exponent.max(1) - EXPONENT_BIAS - 63 - shift as i32;
+                let biased = e + 63 + F64_EXPONENT_BIAS;
+                match biased {
+                    0x7ff.. => 0x7ff << 52,
+                    // the carry from the integer bit makes up the exponent,
+                    // and a rounding overflow correctly becomes infinity
+                    1.. => ((biased as u64 - 1) << 52) + round_shift(m, 11),
+                    _ => round_shift(m, (12 - biased) as u32),
+                }
+            }
+        };
+        f64::from_bits(sign | bits)
+    }
+}
+
+/// `value >> shift`, rounded to nearest, ties to even.
+fn round_shift(value: u64, shift: u32) -> u64 {
+    if shift > 64 {
+        return 0;
+    }
+    let value = u128::from(value);
+    let truncated = value >> shift;
+    let remainder = value - (truncated << shift);
+    let half = 1 << (shift - 1);
+    let round_up = remainder > half || (remainder == half && truncated & 1 == 1);
+    (truncated + u128::from(round_up)) as u64
+}
+
+impl BitEndian<10> for F80 {
+    fn to_le_bytes(self) -> [u8; 10] {
+        let mut bytes = self.to_be_bytes();
+        bytes.reverse();
+        bytes
+    }
+    fn to_be_bytes(self) -> [u8; 10] {
+        let mut bytes = [0; 10];
+        bytes[..2].copy_from_slice(&self.sign_exponent.to_be_bytes());
+        bytes[2..].copy_from_slice(&self.mantissa.to_be_bytes());
+        bytes
+    }
+    fn to_ne_bytes(self) -> [u8; 10] {
+        match cfg!(target_endian = "big") {
+            true => BitEndian::to_be_bytes(self),
+            false => BitEndian::to_le_bytes(self),
+        }
+    }
+    fn from_le_bytes(mut bytes: [u8; 10]) -> Self {
+        bytes.reverse();
+        Self::from_be_bytes(bytes)
+    }
+    fn from_be_bytes(bytes: [u8; 10]) -> Self {
+        let (sign_exponent, mantissa) = bytes.split_at(2);
+        Self {
+            sign_exponent: u16::from_be_bytes(
+                sign_exponent.try_into().expect("slice is of length 2"),
+            ),
+            mantissa: u64::from_be_bytes(mantissa.try_into().expect("slice is of length 8")),
+        }
+    }
+    fn from_ne_bytes(bytes: [u8; 10]) -> Self {
+        match cfg!(target_endian = "big") {
+            true => Self::from_be_bytes(bytes),
+            false => Self::from_le_bytes(bytes),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::io::Decode for F80 {
+    fn decode<R: std::io::Read + ?Sized>(
+        reader: &mut R,
+        endian: crate::Endian,
+    ) -> std::io::Result<Self> {
+        let mut bytes = [0; 10];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes_endian(bytes, endian))
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::io::Encode for F80 {
+    fn encode<W: std::io::Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        endian: crate::Endian,
+    ) -> std::io::Result<()> {
+        writer.write_all(&self.to_bytes_endian(endian))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for value in [
+            0.0,
+            -0.0,
+            1.0,
+            -2.5,
+            44100.0,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            f64::from_bits(1),
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ] {
+            let it = F80::from_f64(value);
+            assert_eq!(it.to_f64().to_bits(), value.to_bits());
+            assert_eq!(F80::from_le_bytes(it.to_le_bytes()), it);
+            assert_eq!(F80::from_be_bytes(it.to_be_bytes()), it);
+        }
+        assert!(F80::from_f64(f64::NAN).to_f64().is_nan());
+    }
+
+    #[test]
+    fn bytes() {
+        let one = F80::from_f64(1.0);
+        assert_eq!(one.to_be_bytes(), [0x3f, 0xff, 0x80, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(one.to_le_bytes(), [0, 0, 0, 0, 0, 0, 0, 0x80, 0xff, 0x3f]);
+    }
+
+    #[test]
+    fn lossy() {
+        // 1 + 2^-63 rounds to 1
+        let it = F80 {
+            sign_exponent: 0x3fff,
+            mantissa: INTEGER_BIT | 1,
+        };
+        assert_eq!(it.to_f64(), 1.0);
+        // rounds up past the largest f64
+        let it = F80 {
+            sign_exponent: 0x3fff + 1023,
+            mantissa: u64::MAX,
+        };
+        assert_eq!(it.to_f64(), f64::INFINITY);
+        let it = F80 {
+            sign_exponent: SIGN | 0x7ffe,
+            mantissa: INTEGER_BIT,
+        };
+        assert_eq!(it.to_f64(), f64::NEG_INFINITY);
+        // underflows
+        let it = F80 {
+            sign_exponent: 1,
+            mantissa: INTEGER_BIT,
+        };
+        assert_eq!(it.to_f64(), 0.0);
+        // half the smallest subnormal is a tie, rounding to zero
+        assert_eq!(F80::from_f64(f64::from_bits(1)).to_f64(), f64::from_bits(1));
+        let it = F80 {
+            sign_exponent: (EXPONENT_BIAS - 1075) as u16,
+            mantissa: INTEGER_BIT,
+        };
+        assert_eq!(it.to_f64(), 0.0);
+        let it = F80 {
+            sign_exponent: (EXPONENT_BIAS - 1075) as u16,
+            mantissa: INTEGER_BIT | 1,
+        };
+        assert_eq!(it.to_f64().to_bits(), 1);
+    }
+}